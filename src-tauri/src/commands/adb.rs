@@ -0,0 +1,239 @@
+//! ADB 辅助功能
+//!
+//! 带超时的 adb 命令执行、手动地址探测，以及设备稳定标识（端口变化后仍能识别同一设备）
+
+use log::{debug, info};
+use std::process::{Command, Stdio};
+use std::sync::{mpsc, Arc};
+use std::time::{Duration, Instant};
+
+use tauri::State;
+
+use maa_framework::common::{AdbInputMethod, AdbScreencapMethod};
+
+use super::discovery_cache;
+use super::error::MxuError;
+use super::types::{AdbDevice, MaaState};
+
+/// 单条 adb / 管理工具命令的超时时间
+pub(super) const COMMAND_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 查询设备属性的超时时间（设备离线时 adb shell 可能长时间挂起）
+const PROPERTY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 不可用作标识的通用序列号（部分模拟器 / 白牌设备所有实例相同）
+const GENERIC_SERIALS: &[&str] = &["", "unknown", "0123456789abcdef", "0123456789", "emulator"];
+
+/// 创建不弹出控制台窗口的命令
+pub(super) fn hidden_command(program: impl AsRef<std::ffi::OsStr>) -> Command {
+    #[allow(unused_mut)]
+    let mut cmd = Command::new(program);
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        cmd.creation_flags(CREATE_NO_WINDOW);
+    }
+    cmd
+}
+
+/// 执行命令并在超时前返回 stdout，失败或超时返回 None
+/// stdout 在独立线程中读取：adb 拉起的 server 守护进程会继承管道，子进程退出后也可能读不到 EOF
+pub(super) fn run_with_timeout(mut cmd: Command, timeout: Duration) -> Option<String> {
+    let mut child = cmd
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+    let deadline = Instant::now() + timeout;
+
+    let (tx, rx) = mpsc::channel();
+    if let Some(mut stdout) = child.stdout.take() {
+        std::thread::spawn(move || {
+            use std::io::Read;
+            let mut output = String::new();
+            let _ = stdout.read_to_string(&mut output);
+            let _ = tx.send(output);
+        });
+    }
+
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if Instant::now() < deadline => {
+                std::thread::sleep(Duration::from_millis(100));
+            }
+            _ => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return None;
+            }
+        }
+    };
+
+    // 读取线程在超时后仍可能阻塞，直接放弃等待
+    let output = rx
+        .recv_timeout(deadline.saturating_duration_since(Instant::now()))
+        .ok();
+    if output.is_none() {
+        debug!("[adb] stdout not closed before deadline, giving up");
+    }
+    output.filter(|_| status.success())
+}
+
+/// 执行 adb 命令
+pub(super) fn run_adb(adb_path: &str, args: &[&str]) -> Option<String> {
+    run_adb_with_timeout(adb_path, args, COMMAND_TIMEOUT)
+}
+
+fn run_adb_with_timeout(adb_path: &str, args: &[&str], timeout: Duration) -> Option<String> {
+    let mut cmd = hidden_command(adb_path);
+    cmd.args(args);
+    run_with_timeout(cmd, timeout)
+}
+
+/// 读取设备属性
+fn get_prop(adb_path: &str, address: &str, name: &str) -> Option<String> {
+    run_adb_with_timeout(
+        adb_path,
+        &["-s", address, "shell", "getprop", name],
+        PROPERTY_TIMEOUT,
+    )
+    .map(|out| out.trim().to_string())
+    .filter(|v| !v.is_empty())
+}
+
+/// 设备的稳定标识，依次尝试：
+/// - `serial:<ro.serialno>`（排除各实例相同的通用序列号）
+/// - `avd:<name>`（Android SDK 模拟器的 AVD 名称）
+/// - `serial:<address>`（USB 设备的 adb 序列号本身即稳定）
+pub(super) fn device_identity(adb_path: &str, address: &str) -> Option<String> {
+    if let Some(serial) = get_prop(adb_path, address, "ro.serialno") {
+        if !GENERIC_SERIALS.contains(&serial.to_ascii_lowercase().as_str()) {
+            return Some(format!("serial:{}", serial));
+        }
+    }
+
+    if address.starts_with("emulator-") {
+        let avd = run_adb_with_timeout(
+            adb_path,
+            &["-s", address, "emu", "avd", "name"],
+            PROPERTY_TIMEOUT,
+        )
+        .and_then(|out| out.lines().next().map(|l| l.trim().to_string()))
+        .filter(|name| !name.is_empty() && name != "OK");
+        if let Some(name) = avd {
+            return Some(format!("avd:{}", name));
+        }
+    }
+
+    // 不含端口的地址是 USB 序列号
+    if !address.contains(':') && !address.starts_with("emulator-") {
+        return Some(format!("serial:{}", address));
+    }
+    None
+}
+
+/// 并行查询所有设备的稳定标识
+pub(super) fn fill_identities(devices: &mut [AdbDevice]) {
+    std::thread::scope(|scope| {
+        for device in devices.iter_mut() {
+            scope.spawn(move || {
+                device.identity = device_identity(&device.adb_path, &device.address);
+                debug!(
+                    "[adb] {} ({}) identity: {:?}",
+                    device.name, device.address, device.identity
+                );
+            });
+        }
+    });
+}
+
+/// 校验 host:port 格式
+fn validate_address(address: &str) -> Result<(), MxuError> {
+    let (host, port) = address
+        .rsplit_once(':')
+        .ok_or_else(|| MxuError::invalid_param("address", "expected host:port"))?;
+    if host.trim().is_empty() {
+        return Err(MxuError::invalid_param("address", "host is empty"));
+    }
+    port.parse::<u16>().map_err(|e| {
+        MxuError::invalid_param("address", format!("invalid port '{}': {}", port, e))
+    })?;
+    Ok(())
+}
+
+/// 手动探测 ADB 地址：adb connect 后确认设备在线，返回可直接用于连接的设备信息（同时写入缓存）
+/// adb_path 缺省时使用缓存中第一个设备的 adb，再缺省时使用 PATH 中的 adb
+#[tauri::command]
+pub async fn maa_probe_adb_address(
+    state: State<'_, Arc<MaaState>>,
+    address: String,
+    adb_path: Option<String>,
+) -> Result<AdbDevice, MxuError> {
+    info!("maa_probe_adb_address called: {}", address);
+    let address = address.trim().to_string();
+    validate_address(&address)?;
+
+    let state_arc = state.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let adb_path = match adb_path.filter(|p| !p.is_empty()) {
+            Some(path) => path,
+            None => state_arc
+                .cached_adb_devices
+                .lock()?
+                .first()
+                .map(|d| d.adb_path.clone())
+                .unwrap_or_else(|| "adb".to_string()),
+        };
+
+        let connect_failed = |detail: String| MxuError::AdbConnectFailed {
+            address: address.clone(),
+            detail,
+        };
+
+        let output = run_adb(&adb_path, &["connect", &address])
+            .ok_or_else(|| connect_failed(format!("failed to run {}", adb_path)))?;
+        let output = output.trim();
+        // adb connect 失败时退出码仍为 0，需要检查输出
+        if !output.contains("connected to") {
+            return Err(connect_failed(output.to_string()));
+        }
+
+        let device_state = run_adb(&adb_path, &["-s", &address, "get-state"])
+            .map(|s| s.trim().to_string())
+            .unwrap_or_default();
+        if device_state != "device" {
+            return Err(connect_failed(format!(
+                "device state: {}",
+                if device_state.is_empty() {
+                    "unknown"
+                } else {
+                    &device_state
+                }
+            )));
+        }
+
+        let name =
+            get_prop(&adb_path, &address, "ro.product.model").unwrap_or_else(|| address.clone());
+        let device = AdbDevice {
+            name,
+            identity: device_identity(&adb_path, &address),
+            adb_path,
+            address: address.clone(),
+            screencap_methods: AdbScreencapMethod::DEFAULT.bits(),
+            input_methods: AdbInputMethod::DEFAULT.bits(),
+            config: "{}".to_string(),
+            manual_added_at: Some(chrono::Utc::now().timestamp_millis()),
+        };
+        info!(
+            "[adb] Probed {}: {} (identity: {:?})",
+            address, device.name, device.identity
+        );
+
+        discovery_cache::upsert_adb_device(&state_arc, &device)?;
+        Ok(device)
+    })
+    .await?
+}
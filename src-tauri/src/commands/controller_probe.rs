@@ -0,0 +1,317 @@
+//! ADB 控制方式探测
+//!
+//! 对指定设备逐一测试各截图方式（耗时、成功率、黑屏）与输入方式，
+//! 返回按可用性和速度排序的报告，供前端保存最快的可用组合
+
+use log::{debug, info, warn};
+use std::time::Instant;
+
+use maa_framework::common::{AdbInputMethod, AdbScreencapMethod};
+use maa_framework::controller::{AdbControllerBuilder, Controller};
+use maa_framework::MaaStatus;
+
+use super::error::MxuError;
+use super::maa_core::adb_agent_path;
+use super::types::{ControllerMethodProbe, ControllerProbeReport};
+
+/// 待测试的截图方式
+const SCREENCAP_METHODS: &[(AdbScreencapMethod, &str)] = &[
+    (
+        AdbScreencapMethod::ENCODE_TO_FILE_AND_PULL,
+        "EncodeToFileAndPull",
+    ),
+    (AdbScreencapMethod::ENCODE, "Encode"),
+    (AdbScreencapMethod::RAW_WITH_GZIP, "RawWithGzip"),
+    (AdbScreencapMethod::RAW_BY_NETCAT, "RawByNetcat"),
+    (AdbScreencapMethod::MINICAP_DIRECT, "MinicapDirect"),
+    (AdbScreencapMethod::MINICAP_STREAM, "MinicapStream"),
+    (AdbScreencapMethod::EMULATOR_EXTRAS, "EmulatorExtras"),
+];
+
+/// 待测试的输入方式
+const INPUT_METHODS: &[(AdbInputMethod, &str)] = &[
+    (AdbInputMethod::ADB_SHELL, "AdbShell"),
+    (AdbInputMethod::MINITOUCH_AND_ADB_KEY, "MinitouchAndAdbKey"),
+    (AdbInputMethod::MAATOUCH, "Maatouch"),
+    (AdbInputMethod::EMULATOR_EXTRAS, "EmulatorExtras"),
+];
+
+/// 每种截图方式默认截图次数
+const DEFAULT_SAMPLES: u32 = 5;
+/// 每种截图方式最多截图次数
+const MAX_SAMPLES: u32 = 20;
+
+/// 亮度低于该值的像素视为黑色
+const BLACK_LUMA_THRESHOLD: u8 = 8;
+
+/// 单次探测使用的设备参数
+struct ProbeTarget<'a> {
+    adb_path: &'a str,
+    address: &'a str,
+    config: &'a str,
+    agent_path: String,
+}
+
+impl ProbeTarget<'_> {
+    /// 以指定截图 / 输入方式创建临时控制器并连接
+    fn connect(&self, screencap: u64, input: u64) -> Result<Controller, String> {
+        let controller = AdbControllerBuilder::new(self.adb_path, self.address)
+            .screencap_methods(screencap)
+            .input_methods(input)
+            .config(self.config)
+            .agent_path(&self.agent_path)
+            .build()
+            .map_err(|e| format!("create controller: {}", e))?;
+        if let Err(e) = controller.set_screenshot_target_short_side(720) {
+            warn!(
+                "[controller_probe] Failed to set screenshot short side: {}",
+                e
+            );
+        }
+
+        let conn_id = controller
+            .post_connection()
+            .map_err(|e| format!("post_connection: {}", e))?;
+        controller.wait(conn_id);
+        if !controller.connected() {
+            return Err("connection failed".to_string());
+        }
+        Ok(controller)
+    }
+}
+
+/// 截图是否为（几乎）全黑画面，部分截图方式在不支持的模拟器上会返回黑屏
+fn is_black_frame(encoded: &[u8]) -> bool {
+    let Ok(image) = image::load_from_memory(encoded) else {
+        return false;
+    };
+    let luma = image.to_luma8();
+    // 抽样检测即可，无需遍历全部像素
+    luma.pixels()
+        .step_by(97)
+        .all(|p| p.0[0] < BLACK_LUMA_THRESHOLD)
+}
+
+fn new_probe(method: u64, name: &str) -> ControllerMethodProbe {
+    ControllerMethodProbe {
+        method: method.to_string(),
+        name: name.to_string(),
+        connected: false,
+        attempts: 0,
+        successes: 0,
+        black_frames: 0,
+        avg_latency_ms: None,
+        min_latency_ms: None,
+        usable: false,
+        error: None,
+    }
+}
+
+/// 记录耗时统计并判定是否可用（全部成功且无黑屏）
+fn finish_probe(probe: &mut ControllerMethodProbe, latencies: &[u64]) {
+    if !latencies.is_empty() {
+        probe.avg_latency_ms = Some(latencies.iter().sum::<u64>() / latencies.len() as u64);
+        probe.min_latency_ms = latencies.iter().min().copied();
+    }
+    probe.usable = probe.connected
+        && probe.attempts > 0
+        && probe.successes == probe.attempts
+        && probe.black_frames == 0;
+}
+
+/// 测试单个截图方式
+fn probe_screencap(
+    target: &ProbeTarget,
+    method: u64,
+    name: &str,
+    samples: u32,
+) -> ControllerMethodProbe {
+    let mut probe = new_probe(method, name);
+    let controller = match target.connect(method, INPUT_METHODS[0].0.bits()) {
+        Ok(controller) => controller,
+        Err(e) => {
+            probe.error = Some(e);
+            return probe;
+        }
+    };
+    probe.connected = true;
+
+    let mut latencies = Vec::with_capacity(samples as usize);
+    for _ in 0..samples {
+        probe.attempts += 1;
+        let started = Instant::now();
+        let status = match controller.post_screencap() {
+            Ok(id) => controller.wait(id),
+            Err(e) => {
+                probe.error = Some(format!("post_screencap: {}", e));
+                continue;
+            }
+        };
+        let elapsed_ms = started.elapsed().as_millis() as u64;
+        if status != MaaStatus::SUCCEEDED {
+            probe.error = Some(format!("screencap status: {:?}", status));
+            continue;
+        }
+
+        let data = controller
+            .cached_image()
+            .ok()
+            .and_then(|buffer| buffer.to_vec())
+            .filter(|d| !d.is_empty());
+        let Some(data) = data else {
+            probe.error = Some("empty image".to_string());
+            continue;
+        };
+        probe.successes += 1;
+        latencies.push(elapsed_ms);
+        if is_black_frame(&data) {
+            probe.black_frames += 1;
+        }
+    }
+
+    finish_probe(&mut probe, &latencies);
+    probe
+}
+
+/// 测试单个输入方式：输入方式在连接时初始化，默认只验证连接并以连接耗时排序；
+/// tap 为 true 时额外点击屏幕左上角一次（可能触发游戏内操作，需用户主动开启）
+fn probe_input(
+    target: &ProbeTarget,
+    method: u64,
+    name: &str,
+    screencap: u64,
+    tap: bool,
+) -> ControllerMethodProbe {
+    let mut probe = new_probe(method, name);
+    let started = Instant::now();
+    let controller = match target.connect(screencap, method) {
+        Ok(controller) => controller,
+        Err(e) => {
+            probe.error = Some(e);
+            return probe;
+        }
+    };
+    probe.connected = true;
+    probe.attempts = 1;
+    if !tap {
+        probe.successes = 1;
+        finish_probe(&mut probe, &[started.elapsed().as_millis() as u64]);
+        return probe;
+    }
+
+    let started = Instant::now();
+    let status = match controller.post_click(1, 1) {
+        Ok(id) => controller.wait(id),
+        Err(e) => {
+            probe.error = Some(format!("post_click: {}", e));
+            finish_probe(&mut probe, &[]);
+            return probe;
+        }
+    };
+    let elapsed_ms = started.elapsed().as_millis() as u64;
+    if status == MaaStatus::SUCCEEDED {
+        probe.successes = 1;
+        finish_probe(&mut probe, &[elapsed_ms]);
+    } else {
+        probe.error = Some(format!("click status: {:?}", status));
+        finish_probe(&mut probe, &[]);
+    }
+    probe
+}
+
+/// 排序：可用的在前（按平均耗时），其次按失败 + 黑屏次数，完全失败的在最后
+fn rank(probes: &mut [ControllerMethodProbe]) {
+    probes.sort_by_key(|p| {
+        (
+            !p.usable,
+            !p.connected,
+            p.attempts - p.successes + p.black_frames,
+            p.avg_latency_ms.unwrap_or(u64::MAX),
+        )
+    });
+}
+
+/// 探测设备支持的截图 / 输入方式
+///
+/// 每种方式都会创建独立的临时控制器，不影响实例当前连接；
+/// 输入方式使用最快的可用截图方式测试，tap 为 true 时会实际点击屏幕。
+/// best_* 为最快的可用方式（u64 字符串），没有可用方式时为 None
+#[tauri::command]
+pub async fn maa_probe_adb_controller(
+    adb_path: String,
+    address: String,
+    config: Option<String>,
+    samples: Option<u32>,
+    tap: Option<bool>,
+) -> Result<ControllerProbeReport, MxuError> {
+    info!(
+        "maa_probe_adb_controller called, address: {}, samples: {:?}, tap: {:?}",
+        address, samples, tap
+    );
+    let tap = tap.unwrap_or(false);
+    if address.trim().is_empty() {
+        return Err(MxuError::invalid_param("address", "empty address"));
+    }
+    let samples = samples.unwrap_or(DEFAULT_SAMPLES).clamp(1, MAX_SAMPLES);
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let started = Instant::now();
+        let config = config.unwrap_or_else(|| "{}".to_string());
+        let target = ProbeTarget {
+            adb_path: &adb_path,
+            address: &address,
+            config: &config,
+            agent_path: adb_agent_path(),
+        };
+
+        let mut screencap: Vec<ControllerMethodProbe> = SCREENCAP_METHODS
+            .iter()
+            .map(|&(method, name)| {
+                let probe = probe_screencap(&target, method.bits(), name, samples);
+                debug!(
+                    "[controller_probe] screencap {}: {}/{} ok, {} black, avg {:?} ms",
+                    name, probe.successes, probe.attempts, probe.black_frames, probe.avg_latency_ms
+                );
+                probe
+            })
+            .collect();
+        rank(&mut screencap);
+        let best_screencap = screencap
+            .first()
+            .filter(|p| p.usable)
+            .map(|p| p.method.clone());
+
+        // 没有可用截图方式时仍用默认截图方式测试输入，连接结果同样有参考价值
+        let input_screencap = best_screencap
+            .as_deref()
+            .and_then(|m| m.parse::<u64>().ok())
+            .unwrap_or(AdbScreencapMethod::DEFAULT.bits());
+        let mut input: Vec<ControllerMethodProbe> = INPUT_METHODS
+            .iter()
+            .map(|&(method, name)| {
+                let probe = probe_input(&target, method.bits(), name, input_screencap, tap);
+                debug!(
+                    "[controller_probe] input {}: usable {}, {:?} ms",
+                    name, probe.usable, probe.avg_latency_ms
+                );
+                probe
+            })
+            .collect();
+        rank(&mut input);
+        let best_input = input.first().filter(|p| p.usable).map(|p| p.method.clone());
+
+        info!(
+            "[controller_probe] {} best screencap: {:?}, best input: {:?}",
+            address, best_screencap, best_input
+        );
+        Ok(ControllerProbeReport {
+            address: address.clone(),
+            screencap,
+            input,
+            best_screencap_methods: best_screencap,
+            best_input_methods: best_input,
+            elapsed_ms: started.elapsed().as_millis() as u64,
+        })
+    })
+    .await?
+}
@@ -0,0 +1,291 @@
+//! 设备 / 窗口搜索结果的持久化缓存
+//!
+//! 搜索结果带时间戳写入数据目录下的 cache/discovery.json，启动时加载，
+//! 前端无需等待完整的 ADB 扫描即可选择设备；加载后在后台重新扫描 ADB 设备并合并进缓存，
+//! 列表变化时发送 maa-adb-devices-changed 事件
+
+use log::{debug, info, warn};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+
+use super::error::MxuError;
+use super::types::{AdbDevice, AdbDevicesChangedEvent, MaaState, Win32Window};
+use super::utils::get_app_data_dir;
+
+/// 缓存文件格式版本
+const CACHE_FILE_VERSION: u32 = 1;
+
+/// 手动添加的设备在扫描不到时保留的时间（毫秒）
+const MANUAL_DEVICE_RETENTION_MS: i64 = 7 * 24 * 60 * 60 * 1000;
+
+/// 串行化缓存写入，避免并发保存共用同一个临时文件
+static SAVE_LOCK: Mutex<()> = Mutex::new(());
+
+/// 缓存文件内容
+#[derive(Default, Serialize, Deserialize)]
+struct DiscoveryCacheFile {
+    #[serde(default)]
+    version: u32,
+    #[serde(default)]
+    adb_devices: Vec<AdbDevice>,
+    /// ADB 设备列表更新时间（Unix 毫秒）
+    #[serde(default)]
+    adb_updated_at: Option<i64>,
+    #[serde(default)]
+    win32_windows: Vec<Win32Window>,
+    /// Win32 窗口列表更新时间（Unix 毫秒）
+    #[serde(default)]
+    win32_updated_at: Option<i64>,
+}
+
+fn cache_path() -> Result<PathBuf, MxuError> {
+    Ok(get_app_data_dir()?.join("cache").join("discovery.json"))
+}
+
+fn now_ms() -> i64 {
+    chrono::Utc::now().timestamp_millis()
+}
+
+/// 窗口句柄是否仍然有效（重启后旧窗口可能已关闭）
+fn window_exists(handle: u64) -> bool {
+    #[cfg(windows)]
+    {
+        use windows::Win32::Foundation::HWND;
+        use windows::Win32::UI::WindowsAndMessaging::IsWindow;
+        unsafe { IsWindow(HWND(handle as usize as *mut _)).as_bool() }
+    }
+
+    #[cfg(not(windows))]
+    {
+        let _ = handle;
+        true
+    }
+}
+
+/// 启动时从数据目录加载缓存，已失效的窗口句柄会被丢弃
+pub fn load(state: &MaaState) {
+    let path = match cache_path() {
+        Ok(path) => path,
+        Err(e) => {
+            warn!("[discovery_cache] Cannot resolve cache path: {}", e);
+            return;
+        }
+    };
+    let content = match std::fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return,
+        Err(e) => {
+            warn!("[discovery_cache] Failed to read {:?}: {}", path, e);
+            return;
+        }
+    };
+    let mut file: DiscoveryCacheFile = match serde_json::from_str(&content) {
+        Ok(file) => file,
+        Err(e) => {
+            warn!(
+                "[discovery_cache] Ignoring invalid cache file {:?}: {}",
+                path, e
+            );
+            return;
+        }
+    };
+    if file.version != CACHE_FILE_VERSION {
+        info!(
+            "[discovery_cache] Ignoring cache file version {} (expected {})",
+            file.version, CACHE_FILE_VERSION
+        );
+        return;
+    }
+
+    file.win32_windows.retain(|w| window_exists(w.handle));
+    info!(
+        "[discovery_cache] Loaded {} ADB device(s), {} window(s) from cache",
+        file.adb_devices.len(),
+        file.win32_windows.len()
+    );
+
+    if let Ok(mut cached) = state.cached_adb_devices.lock() {
+        *cached = file.adb_devices;
+    }
+    if let Ok(mut updated_at) = state.cached_adb_updated_at.lock() {
+        *updated_at = file.adb_updated_at;
+    }
+    if let Ok(mut cached) = state.cached_win32_windows.lock() {
+        *cached = file.win32_windows;
+    }
+    if let Ok(mut updated_at) = state.cached_win32_updated_at.lock() {
+        *updated_at = file.win32_updated_at;
+    }
+}
+
+/// 将当前缓存写入数据目录（先写临时文件再替换，避免写一半时退出导致文件损坏）
+fn save(state: &MaaState) -> Result<(), MxuError> {
+    // 在锁内读取快照，保证后写入的总是较新的内容
+    let _guard = SAVE_LOCK.lock()?;
+    let file = DiscoveryCacheFile {
+        version: CACHE_FILE_VERSION,
+        adb_devices: state.cached_adb_devices.lock()?.clone(),
+        adb_updated_at: *state.cached_adb_updated_at.lock()?,
+        win32_windows: state.cached_win32_windows.lock()?.clone(),
+        win32_updated_at: *state.cached_win32_updated_at.lock()?,
+    };
+    let content = serde_json::to_string_pretty(&file).map_err(MxuError::internal)?;
+
+    let path = cache_path()?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| MxuError::io("create cache directory", parent, e))?;
+    }
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, content).map_err(|e| MxuError::io("write discovery cache", &tmp, e))?;
+    std::fs::rename(&tmp, &path).map_err(|e| MxuError::io("replace discovery cache", &path, e))
+}
+
+fn save_or_warn(state: &MaaState) {
+    if let Err(e) = save(state) {
+        warn!("[discovery_cache] Failed to save cache: {}", e);
+    }
+}
+
+/// 替换缓存的 ADB 设备列表并持久化
+pub(super) fn set_adb_devices(state: &MaaState, devices: Vec<AdbDevice>) {
+    if let Ok(mut cached) = state.cached_adb_devices.lock() {
+        *cached = devices;
+    }
+    if let Ok(mut updated_at) = state.cached_adb_updated_at.lock() {
+        *updated_at = Some(now_ms());
+    }
+    save_or_warn(state);
+}
+
+/// 两条记录是否为同一设备（标识相同，其次地址相同）
+fn same_device(a: &AdbDevice, b: &AdbDevice) -> bool {
+    (b.identity.is_some() && a.identity == b.identity) || a.address == b.address
+}
+
+/// 按标识（其次按地址）更新列表中的单个 ADB 设备，不存在时追加
+fn upsert_into(cached: &mut Vec<AdbDevice>, device: &AdbDevice) {
+    let existing = cached.iter_mut().find(|d| same_device(d, device));
+    match existing {
+        Some(d) => *d = device.clone(),
+        None => cached.push(device.clone()),
+    }
+}
+
+/// 按标识（其次按地址）更新缓存中的单个 ADB 设备并持久化
+pub(super) fn upsert_adb_device(state: &MaaState, device: &AdbDevice) -> Result<(), MxuError> {
+    upsert_into(&mut state.cached_adb_devices.lock()?, device);
+    *state.cached_adb_updated_at.lock()? = Some(now_ms());
+    save_or_warn(state);
+    Ok(())
+}
+
+/// 替换缓存的 Win32 窗口列表并持久化
+pub(super) fn set_win32_windows(state: &MaaState, windows: Vec<Win32Window>) {
+    if let Ok(mut cached) = state.cached_win32_windows.lock() {
+        *cached = windows;
+    }
+    if let Ok(mut updated_at) = state.cached_win32_updated_at.lock() {
+        *updated_at = Some(now_ms());
+    }
+    save_or_warn(state);
+}
+
+/// 将后台扫描结果合并进缓存并持久化：移除扫描未发现的设备，
+/// 手动添加的设备在 MANUAL_DEVICE_RETENTION_MS 内保留。
+/// 扫描期间缓存已被更新（如用户重新搜索）时放弃写入；返回变化后的列表
+fn merge_adb_devices(
+    state: &MaaState,
+    devices: &[AdbDevice],
+    scan_started_at: i64,
+) -> Result<Option<Vec<AdbDevice>>, MxuError> {
+    let merged = {
+        let mut cached = state.cached_adb_devices.lock()?;
+        let mut updated_at = state.cached_adb_updated_at.lock()?;
+        if updated_at.is_some_and(|t| t > scan_started_at) {
+            debug!("[discovery_cache] Cache updated during background scan, skipped");
+            return Ok(None);
+        }
+        let now = now_ms();
+        let mut merged: Vec<AdbDevice> = cached
+            .iter()
+            .filter(|d| {
+                d.manual_added_at
+                    .is_some_and(|t| now - t < MANUAL_DEVICE_RETENTION_MS)
+            })
+            .cloned()
+            .collect();
+        for device in devices {
+            // 扫描到的设备若是手动添加的，保留其添加时间
+            let manual_added_at = merged
+                .iter()
+                .find(|d| same_device(d, device))
+                .and_then(|d| d.manual_added_at);
+            upsert_into(
+                &mut merged,
+                &AdbDevice {
+                    manual_added_at,
+                    ..device.clone()
+                },
+            );
+        }
+        if merged == *cached {
+            return Ok(None);
+        }
+        *cached = merged.clone();
+        *updated_at = Some(now_ms());
+        merged
+    };
+    save_or_warn(state);
+    Ok(Some(merged))
+}
+
+/// 在后台重新扫描 ADB 设备，列表变化时发送 maa-adb-devices-changed 事件
+/// 需在 MaaFramework 加载成功后调用
+pub fn spawn_background_refresh(app: AppHandle) {
+    std::thread::spawn(move || {
+        let Some(state) = app.try_state::<Arc<MaaState>>() else {
+            return;
+        };
+        let state = state.inner().clone();
+
+        debug!("[discovery_cache] Refreshing ADB devices in background...");
+        let scan_started_at = now_ms();
+        let devices = match super::maa_core::scan_adb_devices() {
+            Ok(devices) => devices,
+            Err(e) => {
+                warn!("[discovery_cache] Background ADB scan failed: {}", e);
+                return;
+            }
+        };
+
+        let devices = match merge_adb_devices(&state, &devices, scan_started_at) {
+            Ok(Some(devices)) => devices,
+            Ok(None) => {
+                debug!("[discovery_cache] ADB device list unchanged");
+                return;
+            }
+            Err(e) => {
+                warn!("[discovery_cache] Failed to merge ADB devices: {}", e);
+                return;
+            }
+        };
+        info!(
+            "[discovery_cache] ADB device list changed: {} device(s)",
+            devices.len()
+        );
+        let event = AdbDevicesChangedEvent {
+            devices,
+            updated_at: now_ms(),
+        };
+        if let Err(e) = app.emit("maa-adb-devices-changed", event) {
+            warn!(
+                "[discovery_cache] Failed to emit maa-adb-devices-changed: {}",
+                e
+            );
+        }
+    });
+}
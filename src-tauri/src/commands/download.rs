@@ -4,9 +4,11 @@
 
 use log::{error, info, warn};
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
 
 use tauri::Emitter;
 
+use super::error::MxuError;
 use super::types::{DownloadProgressEvent, DownloadResult};
 use super::update::move_to_old_folder;
 use super::utils::build_user_agent;
@@ -15,6 +17,31 @@ use super::utils::build_user_agent;
 static DOWNLOAD_CANCELLED: AtomicBool = AtomicBool::new(false);
 /// 当前下载的 session ID，用于区分不同的下载任务
 static CURRENT_DOWNLOAD_SESSION: AtomicU64 = AtomicU64::new(0);
+/// 下载代理设置（由前端同步），供 MXU_HTTP_CHECK 等后端发起的请求复用
+static DOWNLOAD_PROXY: Mutex<Option<String>> = Mutex::new(None);
+
+/// 获取当前配置的下载代理
+pub fn download_proxy() -> Option<String> {
+    DOWNLOAD_PROXY.lock().ok().and_then(|p| p.clone())
+}
+
+/// 同步下载代理设置，空字符串或 None 表示直连
+#[tauri::command]
+pub fn set_download_proxy(proxy_url: Option<String>) -> Result<(), MxuError> {
+    let proxy = proxy_url.filter(|p| !p.trim().is_empty());
+    if let Some(ref proxy) = proxy {
+        reqwest::Proxy::all(proxy).map_err(|e| MxuError::InvalidProxy {
+            proxy: proxy.clone(),
+            detail: e.to_string(),
+        })?;
+    }
+    info!(
+        "set_download_proxy: {}",
+        proxy.as_deref().unwrap_or("(direct)")
+    );
+    *DOWNLOAD_PROXY.lock()? = proxy;
+    Ok(())
+}
 
 /// 流式下载文件，支持进度回调和取消
 ///
@@ -31,7 +58,7 @@ pub async fn download_file(
     save_path: String,
     total_size: Option<u64>,
     proxy_url: Option<String>,
-) -> Result<DownloadResult, String> {
+) -> Result<DownloadResult, MxuError> {
     use futures_util::StreamExt;
     use std::io::Write;
 
@@ -48,7 +75,7 @@ pub async fn download_file(
 
     // 确保目录存在
     if let Some(parent) = save_path_obj.parent() {
-        std::fs::create_dir_all(parent).map_err(|e| format!("无法创建目录: {}", e))?;
+        std::fs::create_dir_all(parent).map_err(|e| MxuError::io("create dir", parent, e))?;
     }
 
     // 构建 HTTP 客户端和请求
@@ -64,10 +91,10 @@ pub async fn download_file(
             info!("[下载] 目标: {}", url);
             let reqwest_proxy = reqwest::Proxy::all(proxy).map_err(|e| {
                 error!("代理配置失败: {} (代理地址: {})", e, proxy);
-                format!(
-                    "代理配置失败: {}。请检查代理格式是否正确（支持 http:// 或 socks5://）",
-                    e
-                )
+                MxuError::InvalidProxy {
+                    proxy: proxy.clone(),
+                    detail: e.to_string(),
+                }
             })?;
             client_builder = client_builder.proxy(reqwest_proxy);
         } else {
@@ -79,16 +106,25 @@ pub async fn download_file(
 
     let client = client_builder
         .build()
-        .map_err(|e| format!("创建 HTTP 客户端失败: {}", e))?;
+        .map_err(|e| MxuError::DownloadFailed {
+            url: url.clone(),
+            detail: e.to_string(),
+        })?;
 
     let response = client
         .get(&url)
         .send()
         .await
-        .map_err(|e| format!("请求失败: {}", e))?;
+        .map_err(|e| MxuError::DownloadFailed {
+            url: url.clone(),
+            detail: e.to_string(),
+        })?;
 
     if !response.status().is_success() {
-        return Err(format!("HTTP 错误: {}", response.status()));
+        return Err(MxuError::HttpStatus {
+            url: url.clone(),
+            status: response.status().as_u16(),
+        });
     }
 
     // 尝试从 Content-Disposition header 或最终 URL 提取文件名
@@ -119,7 +155,8 @@ pub async fn download_file(
     let total = total_size.or(content_length).unwrap_or(0);
 
     // 创建临时文件
-    let mut file = std::fs::File::create(&temp_path).map_err(|e| format!("无法创建文件: {}", e))?;
+    let mut file = std::fs::File::create(&temp_path)
+        .map_err(|e| MxuError::io("create file", &temp_path, e))?;
 
     // 流式下载
     let mut stream = response.bytes_stream();
@@ -139,10 +176,13 @@ pub async fn download_file(
             drop(file);
             // 清理临时文件
             let _ = std::fs::remove_file(&temp_path);
-            return Err("下载已取消".to_string());
+            return Err(MxuError::DownloadCancelled { session_id });
         }
 
-        let chunk = chunk.map_err(|e| format!("下载数据失败: {}", e))?;
+        let chunk = chunk.map_err(|e| MxuError::DownloadFailed {
+            url: url.clone(),
+            detail: e.to_string(),
+        })?;
 
         buffer.extend_from_slice(&chunk);
         downloaded += chunk.len() as u64;
@@ -150,7 +190,7 @@ pub async fn download_file(
         // 当缓冲区达到一定大小时写入磁盘
         if buffer.len() >= 256 * 1024 {
             file.write_all(&buffer)
-                .map_err(|e| format!("写入文件失败: {}", e))?;
+                .map_err(|e| MxuError::io("write file", &temp_path, e))?;
             buffer.clear();
         }
 
@@ -192,18 +232,18 @@ pub async fn download_file(
         );
         drop(file);
         let _ = std::fs::remove_file(&temp_path);
-        return Err("下载已取消".to_string());
+        return Err(MxuError::DownloadCancelled { session_id });
     }
 
     // 写入剩余缓冲区
     if !buffer.is_empty() {
         file.write_all(&buffer)
-            .map_err(|e| format!("写入文件失败: {}", e))?;
+            .map_err(|e| MxuError::io("write file", &temp_path, e))?;
     }
 
     // 确保数据写入磁盘
     file.sync_all()
-        .map_err(|e| format!("同步文件失败: {}", e))?;
+        .map_err(|e| MxuError::io("sync file", &temp_path, e))?;
     drop(file);
 
     // 发送最终进度
@@ -224,7 +264,8 @@ pub async fn download_file(
     }
 
     // 重命名临时文件
-    std::fs::rename(&temp_path, &actual_save_path).map_err(|e| format!("重命名文件失败: {}", e))?;
+    std::fs::rename(&temp_path, &actual_save_path)
+        .map_err(|e| MxuError::io("rename file", &temp_path, e))?;
 
     info!(
         "download_file completed: {} bytes -> {} (session {})",
@@ -240,7 +281,7 @@ pub async fn download_file(
 
 /// 取消下载
 #[tauri::command]
-pub fn cancel_download(save_path: String) -> Result<(), String> {
+pub fn cancel_download(save_path: String) -> Result<(), MxuError> {
     info!("cancel_download called for: {}", save_path);
 
     // 设置取消标志，让下载循环退出
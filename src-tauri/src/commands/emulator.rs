@@ -0,0 +1,323 @@
+//! 模拟器生命周期管理
+//!
+//! 按实例序号启动 / 关闭常见模拟器（MuMu 12、雷电 9、BlueStacks 5、Android SDK 模拟器），
+//! 并通过 ADB 等待 sys.boot_completed。可单独调用，也可作为 maa_start_tasks 的前置 / 后置步骤
+
+use log::{debug, info, warn};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use super::adb::{hidden_command, run_adb, run_with_timeout, COMMAND_TIMEOUT};
+use super::error::MxuError;
+use super::types::{EmulatorConfig, EmulatorKind, EmulatorStatus, MaaState};
+
+/// 默认开机等待时间
+const DEFAULT_BOOT_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// 开机检测间隔
+const BOOT_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// 模拟器安装目录（AVD 缺省时使用 ANDROID_HOME / ANDROID_SDK_ROOT）
+fn install_dir(config: &EmulatorConfig) -> Option<PathBuf> {
+    if let Some(dir) = config.install_dir.as_deref().filter(|d| !d.is_empty()) {
+        return Some(PathBuf::from(dir));
+    }
+    if config.kind == EmulatorKind::Avd {
+        return ["ANDROID_HOME", "ANDROID_SDK_ROOT"]
+            .iter()
+            .find_map(|key| std::env::var_os(key))
+            .map(PathBuf::from);
+    }
+    None
+}
+
+/// 依次查找安装目录下的候选文件
+fn find_in_dir(dir: &Path, candidates: &[&str]) -> Option<PathBuf> {
+    candidates.iter().map(|c| dir.join(c)).find(|p| p.exists())
+}
+
+/// 模拟器安装目录，MuMu / 雷电 / BlueStacks 必填
+fn require_install_dir(config: &EmulatorConfig) -> Result<PathBuf, MxuError> {
+    install_dir(config).ok_or_else(|| {
+        MxuError::invalid_param("install_dir", "emulator install directory is required")
+    })
+}
+
+/// 模拟器自带的 adb 路径，找不到时使用 PATH 中的 adb
+pub(super) fn resolve_adb_path(config: &EmulatorConfig) -> String {
+    if let Some(path) = config.adb_path.as_deref().filter(|p| !p.is_empty()) {
+        return path.to_string();
+    }
+    let candidates: &[&str] = match config.kind {
+        EmulatorKind::Mumu => &["shell/adb.exe", "nx_main/adb.exe"],
+        EmulatorKind::Ldplayer => &["adb.exe"],
+        EmulatorKind::Bluestacks => &["HD-Adb.exe"],
+        EmulatorKind::Avd => &["platform-tools/adb", "platform-tools/adb.exe"],
+    };
+    install_dir(config)
+        .and_then(|dir| find_in_dir(&dir, candidates))
+        .map(|p| p.to_string_lossy().to_string())
+        .unwrap_or_else(|| "adb".to_string())
+}
+
+/// 按实例序号推算的 adb 地址（可通过 address 覆盖）
+pub(super) fn resolve_address(config: &EmulatorConfig) -> String {
+    if let Some(address) = config.address.as_deref().filter(|a| !a.is_empty()) {
+        return address.to_string();
+    }
+    let index = config.index;
+    match config.kind {
+        EmulatorKind::Mumu => format!("127.0.0.1:{}", 16384 + 32 * index),
+        EmulatorKind::Ldplayer => format!("127.0.0.1:{}", 5555 + 2 * index),
+        EmulatorKind::Bluestacks => format!("127.0.0.1:{}", 5555 + 10 * index),
+        EmulatorKind::Avd => format!("emulator-{}", 5554 + 2 * index),
+    }
+}
+
+/// BlueStacks 实例名，缺省按序号推算（Nougat64、Nougat64_1 ...）
+fn bluestacks_instance(config: &EmulatorConfig) -> String {
+    match config.instance_name.as_deref().filter(|n| !n.is_empty()) {
+        Some(name) => name.to_string(),
+        None if config.index == 0 => "Nougat64".to_string(),
+        None => format!("Nougat64_{}", config.index),
+    }
+}
+
+/// 仅 Windows 提供的模拟器
+fn ensure_windows_emulator(config: &EmulatorConfig) -> Result<(), MxuError> {
+    if cfg!(windows) || config.kind == EmulatorKind::Avd {
+        Ok(())
+    } else {
+        Err(MxuError::Unsupported {
+            feature: format!("emulator {:?}", config.kind),
+        })
+    }
+}
+
+/// 设备是否在线且已开机
+fn is_booted(adb_path: &str, address: &str) -> bool {
+    // 网络地址需要先 adb connect，emulator-xxxx 由 adb server 自动发现
+    if address.contains(':') {
+        let _ = run_adb(adb_path, &["connect", address]);
+    }
+    run_adb(
+        adb_path,
+        &["-s", address, "shell", "getprop", "sys.boot_completed"],
+    )
+    .is_some_and(|out| out.trim() == "1")
+}
+
+/// 启动模拟器进程（不等待开机）
+fn launch(config: &EmulatorConfig) -> Result<(), MxuError> {
+    ensure_windows_emulator(config)?;
+    let index = config.index.to_string();
+
+    match config.kind {
+        EmulatorKind::Mumu => {
+            let dir = require_install_dir(config)?;
+            let manager = find_in_dir(&dir, &["shell/MuMuManager.exe", "nx_main/MuMuManager.exe"])
+                .ok_or_else(|| MxuError::io("find MuMuManager", &dir, "not found"))?;
+            let mut cmd = hidden_command(&manager);
+            cmd.args(["control", "-v", &index, "launch"]);
+            run_with_timeout(cmd, COMMAND_TIMEOUT)
+                .ok_or_else(|| MxuError::io("launch MuMu", &manager, "command failed"))?;
+        }
+        EmulatorKind::Ldplayer => {
+            let dir = require_install_dir(config)?;
+            let console = find_in_dir(&dir, &["ldconsole.exe", "dnconsole.exe"])
+                .ok_or_else(|| MxuError::io("find ldconsole", &dir, "not found"))?;
+            let mut cmd = hidden_command(&console);
+            cmd.args(["launch", "--index", &index]);
+            run_with_timeout(cmd, COMMAND_TIMEOUT)
+                .ok_or_else(|| MxuError::io("launch LDPlayer", &console, "command failed"))?;
+        }
+        EmulatorKind::Bluestacks => {
+            let dir = require_install_dir(config)?;
+            let player = dir.join("HD-Player.exe");
+            Command::new(&player)
+                .args(["--instance", &bluestacks_instance(config)])
+                .spawn()
+                .map_err(|e| MxuError::io("launch BlueStacks", &player, e))?;
+        }
+        EmulatorKind::Avd => {
+            let name = config
+                .instance_name
+                .as_deref()
+                .filter(|n| !n.is_empty())
+                .ok_or_else(|| MxuError::invalid_param("instance_name", "AVD name is required"))?;
+            let emulator = install_dir(config)
+                .and_then(|dir| find_in_dir(&dir, &["emulator/emulator", "emulator/emulator.exe"]))
+                .unwrap_or_else(|| PathBuf::from("emulator"));
+            let port = (5554 + 2 * config.index).to_string();
+            Command::new(&emulator)
+                .args(["-avd", name, "-port", &port])
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .spawn()
+                .map_err(|e| MxuError::io("launch AVD", &emulator, e))?;
+        }
+    }
+    Ok(())
+}
+
+/// 确保模拟器已开机：未开机时启动并等待 sys.boot_completed
+pub(super) fn ensure_emulator_ready(config: &EmulatorConfig) -> Result<EmulatorStatus, MxuError> {
+    let started = Instant::now();
+    let adb_path = resolve_adb_path(config);
+    let address = resolve_address(config);
+    let status = |booted: bool, launched: bool| EmulatorStatus {
+        address: address.clone(),
+        adb_path: adb_path.clone(),
+        booted,
+        launched,
+        elapsed_ms: started.elapsed().as_millis() as u64,
+    };
+
+    if is_booted(&adb_path, &address) {
+        debug!("[emulator] {} already booted", address);
+        return Ok(status(true, false));
+    }
+
+    info!(
+        "[emulator] Launching {:?} #{} (address: {})",
+        config.kind, config.index, address
+    );
+    launch(config)?;
+
+    let timeout = config
+        .boot_timeout_secs
+        .map(Duration::from_secs)
+        .unwrap_or(DEFAULT_BOOT_TIMEOUT);
+    while started.elapsed() < timeout {
+        std::thread::sleep(BOOT_POLL_INTERVAL);
+        if is_booted(&adb_path, &address) {
+            info!(
+                "[emulator] {} booted in {} ms",
+                address,
+                started.elapsed().as_millis()
+            );
+            return Ok(status(true, true));
+        }
+    }
+
+    warn!("[emulator] {} did not boot within {:?}", address, timeout);
+    Err(MxuError::EmulatorBootTimeout {
+        address,
+        timeout_secs: timeout.as_secs(),
+    })
+}
+
+/// 关闭模拟器
+pub(super) fn stop_emulator(config: &EmulatorConfig) -> Result<(), MxuError> {
+    ensure_windows_emulator(config)?;
+    let adb_path = resolve_adb_path(config);
+    let address = resolve_address(config);
+    let index = config.index.to_string();
+    info!(
+        "[emulator] Stopping {:?} #{} ({})",
+        config.kind, config.index, address
+    );
+
+    let ok = match config.kind {
+        EmulatorKind::Mumu => {
+            let dir = require_install_dir(config)?;
+            find_in_dir(&dir, &["shell/MuMuManager.exe", "nx_main/MuMuManager.exe"])
+                .and_then(|manager| {
+                    let mut cmd = hidden_command(manager);
+                    cmd.args(["control", "-v", &index, "shutdown"]);
+                    run_with_timeout(cmd, COMMAND_TIMEOUT)
+                })
+                .is_some()
+        }
+        EmulatorKind::Ldplayer => {
+            let dir = require_install_dir(config)?;
+            find_in_dir(&dir, &["ldconsole.exe", "dnconsole.exe"])
+                .and_then(|console| {
+                    let mut cmd = hidden_command(console);
+                    cmd.args(["quit", "--index", &index]);
+                    run_with_timeout(cmd, COMMAND_TIMEOUT)
+                })
+                .is_some()
+        }
+        // BlueStacks 没有关闭实例的命令行，通过 adb 关机
+        EmulatorKind::Bluestacks => {
+            run_adb(&adb_path, &["-s", &address, "shell", "reboot", "-p"]).is_some()
+        }
+        EmulatorKind::Avd => run_adb(&adb_path, &["-s", &address, "emu", "kill"]).is_some(),
+    };
+
+    if ok {
+        Ok(())
+    } else {
+        Err(MxuError::io("stop emulator", &address, "command failed"))
+    }
+}
+
+/// maa_start_tasks 前置步骤：确保模拟器已开机，刚启动时让实例控制器重新连接
+pub(super) fn prepare_for_tasks(
+    state: &MaaState,
+    instance_id: &str,
+    config: &EmulatorConfig,
+) -> Result<EmulatorStatus, MxuError> {
+    let status = ensure_emulator_ready(config)?;
+
+    let controller = {
+        let instances = state.instances.lock()?;
+        instances
+            .get(instance_id)
+            .and_then(|i| i.controller.clone())
+    };
+    let Some(controller) = controller else {
+        return Ok(status);
+    };
+    // 模拟器刚启动时旧连接已失效，connected() 可能仍为 true，需无条件重连
+    if controller.connected() && !status.launched {
+        return Ok(status);
+    }
+
+    info!("[emulator] Reconnecting controller of {}", instance_id);
+    let conn_id = controller
+        .post_connection()
+        .map_err(|e| MxuError::maa("post_connection", e))?;
+    controller.wait(conn_id);
+    if !controller.connected() {
+        return Err(MxuError::ControllerNotConnected {
+            instance_id: instance_id.to_string(),
+        });
+    }
+    Ok(status)
+}
+
+/// 启动模拟器并等待开机完成
+#[tauri::command]
+pub async fn maa_start_emulator(config: EmulatorConfig) -> Result<EmulatorStatus, MxuError> {
+    info!("maa_start_emulator called: {:?}", config);
+    tauri::async_runtime::spawn_blocking(move || ensure_emulator_ready(&config)).await?
+}
+
+/// 关闭模拟器
+#[tauri::command]
+pub async fn maa_stop_emulator(config: EmulatorConfig) -> Result<(), MxuError> {
+    info!("maa_stop_emulator called: {:?}", config);
+    tauri::async_runtime::spawn_blocking(move || stop_emulator(&config)).await?
+}
+
+/// 查询模拟器是否已开机（不启动）
+#[tauri::command]
+pub async fn maa_get_emulator_status(config: EmulatorConfig) -> Result<EmulatorStatus, MxuError> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let adb_path = resolve_adb_path(&config);
+        let address = resolve_address(&config);
+        let booted = is_booted(&adb_path, &address);
+        Ok(EmulatorStatus {
+            address,
+            adb_path,
+            booted,
+            launched: false,
+            elapsed_ms: 0,
+        })
+    })
+    .await?
+}
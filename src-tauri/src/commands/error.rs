@@ -0,0 +1,319 @@
+//! 错误类型定义
+//!
+//! 所有 Tauri 命令统一返回 `MxuError`，序列化后前端可按 `code` 分支处理，
+//! 并通过 `message_key` 交给 i18n 层翻译（`context` 提供插值参数）
+
+use std::fmt;
+use std::path::Path;
+use std::sync::PoisonError;
+
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use serde_json::json;
+
+use super::types::PipelineDiagnostic;
+
+/// MXU 后端错误
+#[derive(Debug, Clone)]
+pub enum MxuError {
+    /// MaaFramework 库目录不存在
+    LibraryNotFound { path: String },
+    /// MaaFramework 库加载失败（可能缺少运行库）
+    LibraryLoadFailed { path: String, detail: String },
+    /// MaaFramework 尚未初始化
+    NotInitialized,
+    /// 实例不存在
+    InstanceNotFound { instance_id: String },
+    /// 控制器未连接
+    ControllerNotConnected { instance_id: String },
+    /// 资源未加载
+    ResourceNotLoaded { instance_id: String },
+    /// Tasker 未创建
+    TaskerNotCreated { instance_id: String },
+    /// Tasker 未正确初始化（资源或控制器绑定失败）
+    TaskerNotInitialized { instance_id: String },
+    /// Tasker 正在运行任务
+    TaskerBusy { instance_id: String },
+    /// 参数无效
+    InvalidParameter { name: String, detail: String },
+    /// Pipeline override 校验未通过
+    InvalidPipelineOverride {
+        entry: String,
+        diagnostics: Vec<PipelineDiagnostic>,
+    },
+    /// MaaFramework 接口调用失败
+    MaaCallFailed { operation: String, detail: String },
+    /// Agent 启动失败
+    AgentStartFailed { index: usize, detail: String },
+    /// 下载已取消
+    DownloadCancelled { session_id: u64 },
+    /// 下载失败
+    DownloadFailed { url: String, detail: String },
+    /// HTTP 返回非成功状态码
+    HttpStatus { url: String, status: u16 },
+    /// 代理配置无效
+    InvalidProxy { proxy: String, detail: String },
+    /// 解压失败
+    ExtractFailed { path: String, detail: String },
+    /// 非法路径（路径穿越等）
+    InvalidPath { path: String },
+    /// 文件系统操作失败
+    IoFailed {
+        operation: String,
+        path: String,
+        detail: String,
+    },
+    /// adb connect 失败或设备不在线
+    AdbConnectFailed { address: String, detail: String },
+    /// 模拟器未在超时时间内开机
+    EmulatorBootTimeout { address: String, timeout_secs: u64 },
+    /// 当前平台不支持
+    Unsupported { feature: String },
+    /// 内部错误（锁中毒、线程 join 失败等）
+    Internal { detail: String },
+}
+
+impl MxuError {
+    /// 构造 MaaFramework 调用失败错误
+    pub fn maa(operation: &str, err: impl fmt::Display) -> Self {
+        MxuError::MaaCallFailed {
+            operation: operation.to_string(),
+            detail: err.to_string(),
+        }
+    }
+
+    /// 构造文件系统操作失败错误
+    pub fn io(operation: &str, path: impl AsRef<Path>, err: impl fmt::Display) -> Self {
+        MxuError::IoFailed {
+            operation: operation.to_string(),
+            path: path.as_ref().display().to_string(),
+            detail: err.to_string(),
+        }
+    }
+
+    /// 构造参数无效错误
+    pub fn invalid_param(name: &str, detail: impl fmt::Display) -> Self {
+        MxuError::InvalidParameter {
+            name: name.to_string(),
+            detail: detail.to_string(),
+        }
+    }
+
+    /// 构造内部错误
+    pub fn internal(err: impl fmt::Display) -> Self {
+        MxuError::Internal {
+            detail: err.to_string(),
+        }
+    }
+
+    /// 稳定的错误码（与变体名一致，前端据此分支）
+    pub fn code(&self) -> &'static str {
+        match self {
+            MxuError::LibraryNotFound { .. } => "LibraryNotFound",
+            MxuError::LibraryLoadFailed { .. } => "LibraryLoadFailed",
+            MxuError::NotInitialized => "NotInitialized",
+            MxuError::InstanceNotFound { .. } => "InstanceNotFound",
+            MxuError::ControllerNotConnected { .. } => "ControllerNotConnected",
+            MxuError::ResourceNotLoaded { .. } => "ResourceNotLoaded",
+            MxuError::TaskerNotCreated { .. } => "TaskerNotCreated",
+            MxuError::TaskerNotInitialized { .. } => "TaskerNotInitialized",
+            MxuError::TaskerBusy { .. } => "TaskerBusy",
+            MxuError::InvalidParameter { .. } => "InvalidParameter",
+            MxuError::InvalidPipelineOverride { .. } => "InvalidPipelineOverride",
+            MxuError::MaaCallFailed { .. } => "MaaCallFailed",
+            MxuError::AgentStartFailed { .. } => "AgentStartFailed",
+            MxuError::DownloadCancelled { .. } => "DownloadCancelled",
+            MxuError::DownloadFailed { .. } => "DownloadFailed",
+            MxuError::HttpStatus { .. } => "HttpStatus",
+            MxuError::InvalidProxy { .. } => "InvalidProxy",
+            MxuError::ExtractFailed { .. } => "ExtractFailed",
+            MxuError::InvalidPath { .. } => "InvalidPath",
+            MxuError::IoFailed { .. } => "IoFailed",
+            MxuError::AdbConnectFailed { .. } => "AdbConnectFailed",
+            MxuError::EmulatorBootTimeout { .. } => "EmulatorBootTimeout",
+            MxuError::Unsupported { .. } => "Unsupported",
+            MxuError::Internal { .. } => "Internal",
+        }
+    }
+
+    /// i18n 翻译 key（如 "errors.backend.instanceNotFound"）
+    pub fn message_key(&self) -> String {
+        let code = self.code();
+        let mut chars = code.chars();
+        let camel = match chars.next() {
+            Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+            None => String::new(),
+        };
+        format!("errors.backend.{}", camel)
+    }
+
+    /// 结构化上下文字段（作为 i18n 插值参数）
+    pub fn context(&self) -> serde_json::Value {
+        match self {
+            MxuError::LibraryNotFound { path } | MxuError::InvalidPath { path } => {
+                json!({ "path": path })
+            }
+            MxuError::LibraryLoadFailed { path, detail }
+            | MxuError::ExtractFailed { path, detail } => {
+                json!({ "path": path, "detail": detail })
+            }
+            MxuError::NotInitialized => json!({}),
+            MxuError::InstanceNotFound { instance_id }
+            | MxuError::ControllerNotConnected { instance_id }
+            | MxuError::ResourceNotLoaded { instance_id }
+            | MxuError::TaskerNotCreated { instance_id }
+            | MxuError::TaskerNotInitialized { instance_id }
+            | MxuError::TaskerBusy { instance_id } => {
+                json!({ "instance_id": instance_id })
+            }
+            MxuError::InvalidParameter { name, detail } => {
+                json!({ "name": name, "detail": detail })
+            }
+            MxuError::InvalidPipelineOverride { entry, diagnostics } => {
+                json!({ "entry": entry, "count": diagnostics.len(), "diagnostics": diagnostics })
+            }
+            MxuError::MaaCallFailed { operation, detail } => {
+                json!({ "operation": operation, "detail": detail })
+            }
+            MxuError::AgentStartFailed { index, detail } => {
+                json!({ "index": index, "detail": detail })
+            }
+            MxuError::DownloadCancelled { session_id } => json!({ "session_id": session_id }),
+            MxuError::DownloadFailed { url, detail } => json!({ "url": url, "detail": detail }),
+            MxuError::HttpStatus { url, status } => json!({ "url": url, "status": status }),
+            MxuError::InvalidProxy { proxy, detail } => {
+                json!({ "proxy": proxy, "detail": detail })
+            }
+            MxuError::IoFailed {
+                operation,
+                path,
+                detail,
+            } => json!({ "operation": operation, "path": path, "detail": detail }),
+            MxuError::AdbConnectFailed { address, detail } => {
+                json!({ "address": address, "detail": detail })
+            }
+            MxuError::EmulatorBootTimeout {
+                address,
+                timeout_secs,
+            } => json!({ "address": address, "timeout_secs": timeout_secs }),
+            MxuError::Unsupported { feature } => json!({ "feature": feature }),
+            MxuError::Internal { detail } => json!({ "detail": detail }),
+        }
+    }
+}
+
+impl fmt::Display for MxuError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MxuError::LibraryNotFound { path } => {
+                write!(f, "MaaFramework library directory not found: {}", path)
+            }
+            MxuError::LibraryLoadFailed { path, detail } => {
+                write!(f, "Failed to load MaaFramework from {}: {}", path, detail)
+            }
+            MxuError::NotInitialized => write!(f, "MaaFramework not initialized"),
+            MxuError::InstanceNotFound { instance_id } => {
+                write!(f, "Instance not found: {}", instance_id)
+            }
+            MxuError::ControllerNotConnected { instance_id } => {
+                write!(f, "Controller not connected (instance: {})", instance_id)
+            }
+            MxuError::ResourceNotLoaded { instance_id } => {
+                write!(f, "Resource not loaded (instance: {})", instance_id)
+            }
+            MxuError::TaskerNotCreated { instance_id } => {
+                write!(f, "Tasker not created (instance: {})", instance_id)
+            }
+            MxuError::TaskerNotInitialized { instance_id } => {
+                write!(f, "Tasker not initialized (instance: {})", instance_id)
+            }
+            MxuError::TaskerBusy { instance_id } => {
+                write!(f, "Tasker is running a task (instance: {})", instance_id)
+            }
+            MxuError::InvalidParameter { name, detail } => {
+                write!(f, "Invalid parameter '{}': {}", name, detail)
+            }
+            MxuError::InvalidPipelineOverride { entry, diagnostics } => {
+                write!(
+                    f,
+                    "Invalid pipeline override for '{}': {} error(s)",
+                    entry,
+                    diagnostics.len()
+                )?;
+                if let Some(first) = diagnostics.first() {
+                    write!(f, ", first: {}", first.message)?;
+                }
+                Ok(())
+            }
+            MxuError::MaaCallFailed { operation, detail } => {
+                write!(f, "MaaFramework call '{}' failed: {}", operation, detail)
+            }
+            MxuError::AgentStartFailed { index, detail } => {
+                write!(f, "Agent #{} failed to start: {}", index, detail)
+            }
+            MxuError::DownloadCancelled { session_id } => {
+                write!(f, "Download cancelled (session {})", session_id)
+            }
+            MxuError::DownloadFailed { url, detail } => {
+                write!(f, "Download failed [{}]: {}", url, detail)
+            }
+            MxuError::HttpStatus { url, status } => {
+                write!(f, "HTTP error {} [{}]", status, url)
+            }
+            MxuError::InvalidProxy { proxy, detail } => {
+                write!(f, "Invalid proxy '{}': {}", proxy, detail)
+            }
+            MxuError::ExtractFailed { path, detail } => {
+                write!(f, "Failed to extract [{}]: {}", path, detail)
+            }
+            MxuError::InvalidPath { path } => write!(f, "Invalid path: {}", path),
+            MxuError::IoFailed {
+                operation,
+                path,
+                detail,
+            } => write!(f, "Failed to {} [{}]: {}", operation, path, detail),
+            MxuError::AdbConnectFailed { address, detail } => {
+                write!(f, "Failed to connect to {}: {}", address, detail)
+            }
+            MxuError::EmulatorBootTimeout {
+                address,
+                timeout_secs,
+            } => write!(
+                f,
+                "Emulator {} did not boot within {} seconds",
+                address, timeout_secs
+            ),
+            MxuError::Unsupported { feature } => {
+                write!(f, "Not supported on this platform: {}", feature)
+            }
+            MxuError::Internal { detail } => write!(f, "Internal error: {}", detail),
+        }
+    }
+}
+
+impl std::error::Error for MxuError {}
+
+impl Serialize for MxuError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_struct("MxuError", 4)?;
+        s.serialize_field("code", self.code())?;
+        s.serialize_field("message_key", &self.message_key())?;
+        s.serialize_field("message", &self.to_string())?;
+        s.serialize_field("context", &self.context())?;
+        s.end()
+    }
+}
+
+impl<T> From<PoisonError<T>> for MxuError {
+    fn from(e: PoisonError<T>) -> Self {
+        MxuError::internal(format!("lock poisoned: {}", e))
+    }
+}
+
+impl From<tauri::Error> for MxuError {
+    fn from(e: tauri::Error) -> Self {
+        MxuError::internal(e)
+    }
+}
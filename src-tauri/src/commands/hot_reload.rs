@@ -0,0 +1,422 @@
+//! 资源热重载
+//!
+//! 轮询已加载资源包目录下的文件变化，在没有任务运行时将所有资源包重新加载到
+//! 新的 Resource 中并重新绑定 Tasker，省去手动销毁/重新加载资源的步骤
+
+use log::{debug, info, warn};
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+
+use tauri::{AppHandle, Emitter, State};
+
+use maa_framework::MaaStatus;
+
+use super::error::MxuError;
+use super::maa_core::create_resource;
+use super::types::{MaaState, ResourceFileError, ResourceReloadEvent};
+
+/// 默认轮询间隔
+const DEFAULT_INTERVAL_MS: u64 = 1000;
+/// 最小轮询间隔
+const MIN_INTERVAL_MS: u64 = 200;
+
+/// 文件快照：路径 -> (修改时间, 大小)
+type Snapshot = HashMap<String, (Option<SystemTime>, u64)>;
+
+/// 待处理的文件变化
+#[derive(Default)]
+struct FileChanges {
+    added: BTreeSet<String>,
+    modified: BTreeSet<String>,
+    removed: BTreeSet<String>,
+}
+
+impl FileChanges {
+    fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.modified.is_empty() && self.removed.is_empty()
+    }
+
+    /// 合并后续变化（先新增后删除的文件视为未变化）
+    fn merge(&mut self, other: FileChanges) {
+        for path in other.added {
+            if !self.removed.remove(&path) {
+                self.added.insert(path);
+            } else {
+                self.modified.insert(path);
+            }
+        }
+        for path in other.modified {
+            if !self.added.contains(&path) {
+                self.modified.insert(path);
+            }
+        }
+        for path in other.removed {
+            self.modified.remove(&path);
+            if !self.added.remove(&path) {
+                self.removed.insert(path);
+            }
+        }
+    }
+}
+
+/// 递归收集资源包目录下所有文件的修改时间和大小
+fn snapshot(paths: &[String]) -> Snapshot {
+    fn walk(dir: &Path, out: &mut Snapshot) {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Ok(meta) = entry.metadata() else {
+                continue;
+            };
+            if meta.is_dir() {
+                walk(&path, out);
+            } else {
+                out.insert(
+                    path.to_string_lossy().to_string(),
+                    (meta.modified().ok(), meta.len()),
+                );
+            }
+        }
+    }
+
+    let mut out = Snapshot::new();
+    for path in paths {
+        walk(Path::new(path), &mut out);
+    }
+    out
+}
+
+/// 比较两次快照
+fn diff(old: &Snapshot, new: &Snapshot) -> FileChanges {
+    let mut changes = FileChanges::default();
+    for (path, meta) in new {
+        match old.get(path) {
+            None => {
+                changes.added.insert(path.clone());
+            }
+            Some(old_meta) if old_meta != meta => {
+                changes.modified.insert(path.clone());
+            }
+            _ => {}
+        }
+    }
+    for path in old.keys() {
+        if !new.contains_key(path) {
+            changes.removed.insert(path.clone());
+        }
+    }
+    changes
+}
+
+/// 去除 JSONC 注释（保留换行以维持行号）
+fn strip_json_comments(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    let mut chars = input.chars().peekable();
+    let mut in_string = false;
+
+    while let Some(c) = chars.next() {
+        if in_string {
+            out.push(c);
+            if c == '\\' {
+                if let Some(next) = chars.next() {
+                    out.push(next);
+                }
+            } else if c == '"' {
+                in_string = false;
+            }
+            continue;
+        }
+
+        match (c, chars.peek()) {
+            ('"', _) => {
+                in_string = true;
+                out.push(c);
+            }
+            ('/', Some('/')) => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        out.push('\n');
+                        break;
+                    }
+                }
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                let mut prev = '\0';
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        out.push('\n');
+                    }
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    prev = c;
+                }
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// 检查变化的 JSON 文件能否解析
+fn check_json_files(changes: &FileChanges) -> Vec<ResourceFileError> {
+    changes
+        .added
+        .iter()
+        .chain(changes.modified.iter())
+        .filter(|path| {
+            let lower = path.to_lowercase();
+            lower.ends_with(".json") || lower.ends_with(".jsonc")
+        })
+        .filter_map(|path| {
+            let content = match std::fs::read_to_string(path) {
+                Ok(content) => content,
+                Err(e) => {
+                    return Some(ResourceFileError {
+                        file: path.clone(),
+                        line: None,
+                        column: None,
+                        message: e.to_string(),
+                    })
+                }
+            };
+            let content = content.trim_start_matches('\u{feff}');
+            serde_json::from_str::<serde_json::Value>(&strip_json_comments(content))
+                .err()
+                .map(|e| ResourceFileError {
+                    file: path.clone(),
+                    line: Some(e.line()),
+                    column: Some(e.column()),
+                    message: e.to_string(),
+                })
+        })
+        .collect()
+}
+
+/// 将所有资源包加载到新的 Resource，成功后替换实例资源并重新绑定 Tasker
+fn reload_resource(
+    app: &AppHandle,
+    state: &MaaState,
+    instance_id: &str,
+    paths: &[String],
+) -> Result<(), MxuError> {
+    let res = create_resource(app)?;
+    for path in paths {
+        let job = res
+            .post_bundle(path)
+            .map_err(|e| MxuError::maa("post_bundle", e))?;
+        if res.wait(job.id) != MaaStatus::SUCCEEDED {
+            return Err(MxuError::maa(
+                "post_bundle",
+                format!("failed to load bundle {}", path),
+            ));
+        }
+    }
+
+    let mut instances = state.instances.lock()?;
+    let instance = instances
+        .get_mut(instance_id)
+        .ok_or_else(|| MxuError::InstanceNotFound {
+            instance_id: instance_id.to_string(),
+        })?;
+
+    // 加载期间可能已有任务启动，此时放弃本次替换
+    if instance.tasker.as_ref().is_some_and(|t| t.running()) {
+        return Err(MxuError::maa(
+            "hot_reload",
+            "task started while reloading, skipped",
+        ));
+    }
+
+    match (instance.tasker.as_ref(), instance.controller.as_ref()) {
+        (Some(tasker), Some(controller)) => {
+            tasker
+                .bind(&res, controller)
+                .map_err(|e| MxuError::maa("tasker_bind", e))?;
+        }
+        // 无法重新绑定时丢弃 tasker，下次运行任务时重新创建
+        _ => {
+            instance.tasker = None;
+            state.unregister_tasker(instance_id);
+        }
+    }
+    instance.resource = Some(res);
+
+    Ok(())
+}
+
+/// 热重载监视循环
+fn watch_loop(
+    app: AppHandle,
+    state: Arc<MaaState>,
+    instance_id: String,
+    interval: Duration,
+    stop: Arc<AtomicBool>,
+) {
+    info!(
+        "[hot_reload] Watching resources of instance {}",
+        instance_id
+    );
+
+    let mut paths: Vec<String> = Vec::new();
+    let mut last = Snapshot::new();
+    let mut pending = FileChanges::default();
+
+    while !stop.load(Ordering::SeqCst) {
+        std::thread::sleep(interval);
+        if stop.load(Ordering::SeqCst) {
+            break;
+        }
+
+        let (current_paths, running) = {
+            let Ok(instances) = state.instances.lock() else {
+                break;
+            };
+            let Some(instance) = instances.get(&instance_id) else {
+                break;
+            };
+            (
+                instance.bundle_paths.clone(),
+                instance.tasker.as_ref().is_some_and(|t| t.running()),
+            )
+        };
+
+        // 资源包列表变化（前端重新加载/切换资源）时只更新基准快照
+        if current_paths != paths {
+            debug!("[hot_reload] Bundle paths changed: {:?}", current_paths);
+            paths = current_paths;
+            last = snapshot(&paths);
+            pending = FileChanges::default();
+            continue;
+        }
+        if paths.is_empty() {
+            continue;
+        }
+
+        let current = snapshot(&paths);
+        let changes = diff(&last, &current);
+        last = current;
+
+        // 仍在变化时等待文件写入稳定
+        if !changes.is_empty() {
+            pending.merge(changes);
+            continue;
+        }
+        if pending.is_empty() || running {
+            continue;
+        }
+
+        let changes = std::mem::take(&mut pending);
+        let started = Instant::now();
+        let mut errors = check_json_files(&changes);
+        let reloaded = if errors.is_empty() {
+            info!(
+                "[hot_reload] Reloading instance {}: {} added, {} modified, {} removed",
+                instance_id,
+                changes.added.len(),
+                changes.modified.len(),
+                changes.removed.len()
+            );
+            match reload_resource(&app, &state, &instance_id, &paths) {
+                Ok(()) => true,
+                Err(e) => {
+                    warn!("[hot_reload] Reload failed: {}", e);
+                    errors.push(ResourceFileError {
+                        file: String::new(),
+                        line: None,
+                        column: None,
+                        message: e.to_string(),
+                    });
+                    false
+                }
+            }
+        } else {
+            warn!(
+                "[hot_reload] Skipped reload, {} file(s) failed to parse",
+                errors.len()
+            );
+            false
+        };
+
+        let event = ResourceReloadEvent {
+            instance_id: instance_id.clone(),
+            added: changes.added.into_iter().collect(),
+            modified: changes.modified.into_iter().collect(),
+            removed: changes.removed.into_iter().collect(),
+            reloaded,
+            errors,
+            elapsed_ms: started.elapsed().as_millis() as u64,
+        };
+        if let Err(e) = app.emit("maa-resource-reloaded", event) {
+            warn!("[hot_reload] Failed to emit maa-resource-reloaded: {}", e);
+        }
+    }
+
+    info!("[hot_reload] Stopped watching instance {}", instance_id);
+}
+
+/// 开启或关闭资源热重载
+/// 开启后文件变化会触发 maa-resource-reloaded 事件
+#[tauri::command]
+pub fn maa_set_hot_reload(
+    app: AppHandle,
+    state: State<Arc<MaaState>>,
+    instance_id: String,
+    enabled: bool,
+    interval_ms: Option<u64>,
+) -> Result<(), MxuError> {
+    info!(
+        "maa_set_hot_reload called, instance_id: {}, enabled: {}",
+        instance_id, enabled
+    );
+
+    let mut instances = state.instances.lock()?;
+    let instance = instances
+        .get_mut(&instance_id)
+        .ok_or_else(|| MxuError::InstanceNotFound {
+            instance_id: instance_id.clone(),
+        })?;
+
+    // 先停止已有的监视线程
+    if let Some(stop) = instance.hot_reload_stop.take() {
+        stop.store(true, Ordering::SeqCst);
+    }
+    if !enabled {
+        return Ok(());
+    }
+
+    let interval = Duration::from_millis(
+        interval_ms
+            .unwrap_or(DEFAULT_INTERVAL_MS)
+            .max(MIN_INTERVAL_MS),
+    );
+    let stop = Arc::new(AtomicBool::new(false));
+    instance.hot_reload_stop = Some(stop.clone());
+
+    let state_arc = state.inner().clone();
+    std::thread::spawn(move || watch_loop(app, state_arc, instance_id, interval, stop));
+
+    Ok(())
+}
+
+/// 查询资源热重载是否开启
+#[tauri::command]
+pub fn maa_is_hot_reload_enabled(
+    state: State<Arc<MaaState>>,
+    instance_id: String,
+) -> Result<bool, MxuError> {
+    let instances = state.instances.lock()?;
+    let instance = instances
+        .get(&instance_id)
+        .ok_or_else(|| MxuError::InstanceNotFound {
+            instance_id: instance_id.clone(),
+        })?;
+
+    Ok(instance.hot_reload_stop.is_some())
+}
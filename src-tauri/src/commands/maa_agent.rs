@@ -17,8 +17,12 @@ use maa_framework::controller::Controller;
 use maa_framework::resource::Resource;
 use maa_framework::tasker::Tasker;
 
-use super::types::{AgentConfig, MaaState, TaskConfig};
-use super::utils::{emit_callback_event, get_logs_dir, normalize_path};
+use super::emulator;
+use super::error::MxuError;
+use super::pipeline::ensure_valid_override;
+use super::task_plan;
+use super::types::{AgentConfig, EmulatorConfig, MaaState, StartTasksResult, TaskConfig};
+use super::utils::{get_logs_dir, normalize_path};
 use regex::Regex;
 use std::sync::LazyLock;
 
@@ -61,7 +65,7 @@ async fn start_single_agent(
     resource: Resource,
     controller: Controller,
     tasker: Tasker,
-) -> Result<(AgentClient, std::process::Child), String> {
+) -> Result<(AgentClient, std::process::Child), MxuError> {
     info!("[agent#{}] Starting agent: {:?}", agent_index, agent);
 
     // 将整个启动过程移入 spawn_blocking，避免阻塞 async runtime 线程
@@ -74,20 +78,23 @@ async fn start_single_agent(
                     agent_index, e
                 );
                 AgentClient::new(None)
-            }).map_err(|e| e.to_string())?
+            }).map_err(|e| MxuError::maa("create_agent_client", e))?
         } else {
             debug!("[agent#{}] Creating default agent client...", agent_index);
-            AgentClient::new(None).map_err(|e| e.to_string())?
+            AgentClient::new(None).map_err(|e| MxuError::maa("create_agent_client", e))?
         };
 
         if let Err(e) = client.bind(resource.clone()) {
             warn!("[agent#{}] Failed to bind resource: {}", agent_index, e);
-            return Err(e.to_string());
+            return Err(MxuError::maa("agent_bind", e));
         }
 
         let socket_id = client
             .identifier()
-            .ok_or_else(|| format!("Failed to get identifier for agent #{}", agent_index))?;
+            .ok_or_else(|| MxuError::AgentStartFailed {
+                index: agent_index,
+                detail: "failed to get identifier".to_string(),
+            })?;
         info!("[agent#{}] Agent socket_id: {}", agent_index, socket_id);
 
         // 启动子进程
@@ -121,11 +128,9 @@ async fn start_single_agent(
             .stdout(Stdio::piped())
             .stderr(Stdio::piped());
 
-        let mut child = cmd.spawn().map_err(|e| {
-            format!(
-                "Failed to spawn agent #{}: {} (path: {:?})",
-                agent_index, e, exec_path
-            )
+        let mut child = cmd.spawn().map_err(|e| MxuError::AgentStartFailed {
+            index: agent_index,
+            detail: format!("{} (path: {:?})", e, exec_path),
         })?;
 
         // 创建 agent 日志文件（多 agent、多实例时使用不同文件名，包含进程 PID）
@@ -211,7 +216,7 @@ async fn start_single_agent(
         if let Err(e) = client.connect() {
              error!("[agent#{}] Connection failed: {}", agent_index, e);
              let _ = child.kill();
-             return Err(e.to_string());
+             return Err(MxuError::maa("agent_connect", e));
         }
 
         info!("[agent#{}] Connected successfully!", agent_index);
@@ -220,82 +225,53 @@ async fn start_single_agent(
         if let Err(e) = client.register_sinks(resource, controller, tasker) {
             error!("[agent#{}] Failed to register sinks: {}", agent_index, e);
             let _ = child.kill();
-            return Err(e.to_string());
+            return Err(MxuError::maa("agent_register_sinks", e));
         }
 
         Ok((client, child))
-    }).await.map_err(|e| e.to_string())?
+    }).await?
 }
 
-/// 启动任务（支持多个 Agent）
-#[tauri::command]
-pub async fn maa_start_tasks(
-    app: tauri::AppHandle,
-    state: State<'_, Arc<MaaState>>,
-    instance_id: String,
-    tasks: Vec<TaskConfig>,
-    agent_configs: Option<Vec<AgentConfig>>,
-    cwd: String,
-    tcp_compat_mode: bool,
-) -> Result<Vec<i64>, String> {
-    info!("maa_start_tasks called");
-
-    info!("instance_id: {}", instance_id);
-    info!("tasks: {:?}", tasks);
-    info!("agent_configs: {:?}", agent_configs);
-    info!("cwd: {}, tcp_compat_mode: {}", cwd, tcp_compat_mode);
-
+/// 获取实例的资源、控制器和 tasker（tasker 不存在时创建并注册回调）
+pub(super) fn acquire_task_handles(
+    app: &tauri::AppHandle,
+    state: &MaaState,
+    instance_id: &str,
+) -> Result<(Resource, Controller, Tasker), MxuError> {
     let (resource, controller, tasker) = {
         debug!("[start_tasks] Acquiring instances lock...");
-        let mut instances = state.instances.lock().map_err(|e| e.to_string())?;
+        let mut instances = state.instances.lock()?;
         debug!("[start_tasks] Instances lock acquired");
-        let instance = instances
-            .get_mut(&instance_id)
-            .ok_or("Instance not found")?;
+        let instance =
+            instances
+                .get_mut(instance_id)
+                .ok_or_else(|| MxuError::InstanceNotFound {
+                    instance_id: instance_id.to_string(),
+                })?;
         debug!("[start_tasks] Instance found: {}", instance_id);
 
         let res = instance
             .resource
             .as_ref()
-            .ok_or("Resource not loaded")?
+            .ok_or_else(|| MxuError::ResourceNotLoaded {
+                instance_id: instance_id.to_string(),
+            })?
             .clone();
         debug!("[start_tasks] Resource acquired");
 
         let ctrl = instance
             .controller
             .as_ref()
-            .ok_or("Controller not connected")?
+            .ok_or_else(|| MxuError::ControllerNotConnected {
+                instance_id: instance_id.to_string(),
+            })?
             .clone();
         debug!("[start_tasks] Controller acquired");
 
         // 创建或获取 tasker
         if instance.tasker.is_none() {
             debug!("[start_tasks] Creating new tasker...");
-            let t = Tasker::new().map_err(|e| e.to_string())?;
-            debug!("[start_tasks] Tasker created");
-
-            // 添加回调 Sink，用于接收任务状态通知
-            debug!("[start_tasks] Adding tasker sink...");
-            let app_handle = app.clone();
-            t.add_sink(move |msg, detail| {
-                emit_callback_event(&app_handle, msg, detail);
-            })
-            .map_err(|e| e.to_string())?;
-            debug!("[start_tasks] Tasker sink added");
-
-            // 添加 Context Sink，用于接收 Node 级别的通知（包含 focus 消息）
-            debug!("[start_tasks] Adding tasker context sink...");
-            let app_handle = app.clone();
-            t.add_context_sink(move |msg, detail| {
-                emit_callback_event(&app_handle, msg, detail);
-            })
-            .map_err(|e| e.to_string())?;
-            debug!("[start_tasks] Tasker context sink added");
-
-            debug!("[start_tasks] Binding resource and controller...");
-            t.bind(&res, &ctrl).map_err(|e| e.to_string())?;
-            debug!("[start_tasks] Resource and controller bound");
-
+            let t = super::maa_core::create_tasker(app, state, instance_id, &res, &ctrl)?;
             instance.tasker = Some(t);
             debug!("[start_tasks] Tasker created and stored");
         } else {
@@ -310,9 +286,27 @@ pub async fn maa_start_tasks(
     // 检查 Tasker 初始化状态
     if !tasker.inited() {
         error!("[start_tasks] Tasker not properly initialized");
-        return Err("Tasker not properly initialized".to_string());
+        return Err(MxuError::TaskerNotInitialized {
+            instance_id: instance_id.to_string(),
+        });
     }
 
+    Ok((resource, controller, tasker))
+}
+
+/// 启动所有 Agent 并保存到实例（任一失败时回滚已启动的 Agent）
+#[allow(clippy::too_many_arguments)]
+pub(super) async fn start_agents(
+    app: &tauri::AppHandle,
+    state: &MaaState,
+    instance_id: &str,
+    agent_configs: Option<Vec<AgentConfig>>,
+    cwd: &str,
+    tcp_compat_mode: bool,
+    resource: &Resource,
+    controller: &Controller,
+    tasker: &Tasker,
+) -> Result<(), MxuError> {
     // 启动所有 Agent（如果配置了）
     debug!("[start_tasks] Checking agent configs...");
     if let Some(configs) = agent_configs {
@@ -330,8 +324,8 @@ pub async fn maa_start_tasks(
                 let ctrl_clone = controller.clone();
                 let tasker_clone = tasker.clone();
                 let app_handle = app.clone();
-                let inst_id = instance_id.clone();
-                let cwd_clone = cwd.clone();
+                let inst_id = instance_id.to_string();
+                let cwd_clone = cwd.to_string();
 
                 match start_single_agent(
                     app_handle,
@@ -364,14 +358,20 @@ pub async fn maa_start_tasks(
                             let _ = child.kill();
                             let _ = child.wait();
                         }
-                        return Err(format!("Agent start failed: {}", e));
+                        return Err(match e {
+                            MxuError::AgentStartFailed { .. } => e,
+                            other => MxuError::AgentStartFailed {
+                                index: idx,
+                                detail: other.to_string(),
+                            },
+                        });
                     }
                 }
             }
 
             // 保存所有 agent 状态到 instance
-            let mut instances = state.instances.lock().map_err(|e| e.to_string())?;
-            if let Some(instance) = instances.get_mut(&instance_id) {
+            let mut instances = state.instances.lock()?;
+            if let Some(instance) = instances.get_mut(instance_id) {
                 instance.agent_clients.extend(new_clients);
                 instance.agent_children.extend(new_children);
             }
@@ -387,6 +387,98 @@ pub async fn maa_start_tasks(
         debug!("[start_tasks] No agent configs, skipping agent setup");
     };
 
+    Ok(())
+}
+
+/// 启动任务（支持多个 Agent）
+/// 任务设置了执行计划策略时返回 plan: true 且不含 task_id，实际 task_id 通过 maa-plan-step 事件通知
+/// 配置了 emulator 时先确保模拟器已开机，close_after_tasks 时任务结束后关闭模拟器
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn maa_start_tasks(
+    app: tauri::AppHandle,
+    state: State<'_, Arc<MaaState>>,
+    instance_id: String,
+    tasks: Vec<TaskConfig>,
+    agent_configs: Option<Vec<AgentConfig>>,
+    cwd: String,
+    tcp_compat_mode: bool,
+    emulator: Option<EmulatorConfig>,
+) -> Result<StartTasksResult, MxuError> {
+    info!("maa_start_tasks called");
+
+    info!("instance_id: {}", instance_id);
+    info!("tasks: {:?}", tasks);
+    info!("agent_configs: {:?}", agent_configs);
+    info!("cwd: {}, tcp_compat_mode: {}", cwd, tcp_compat_mode);
+
+    // 前置步骤：模拟器未开机时启动并等待，控制器断开时重新连接
+    if let Some(config) = emulator.clone() {
+        let state_arc = state.inner().clone();
+        let inst_id = instance_id.clone();
+        let status = tauri::async_runtime::spawn_blocking(move || {
+            emulator::prepare_for_tasks(&state_arc, &inst_id, &config)
+        })
+        .await??;
+        info!(
+            "[start_tasks] Emulator ready: {} (launched: {})",
+            status.address, status.launched
+        );
+    }
+
+    let (resource, controller, tasker) = acquire_task_handles(&app, &state, &instance_id)?;
+
+    // 启动 Agent 前先校验所有任务的 pipeline_override，避免启动后才发现选项错误
+    for task in &tasks {
+        ensure_valid_override(&resource, Some(&task.entry), &task.pipeline_override)?;
+    }
+
+    start_agents(
+        &app,
+        &state,
+        &instance_id,
+        agent_configs,
+        &cwd,
+        tcp_compat_mode,
+        &resource,
+        &controller,
+        &tasker,
+    )
+    .await?;
+
+    // 设置了执行计划策略时，由计划执行器逐个提交任务，task_id 通过 maa-plan-step 事件通知
+    let close_emulator = emulator.filter(|c| c.close_after_tasks);
+    if task_plan::needs_plan(&tasks) {
+        info!(
+            "[start_tasks] Running {} task(s) with execution plan",
+            tasks.len()
+        );
+        {
+            let mut instances = state.instances.lock()?;
+            if let Some(instance) = instances.get_mut(&instance_id) {
+                instance.task_ids.clear();
+            }
+        }
+        let state_arc = state.inner().clone();
+        let cancel = task_plan::plan_cancel_flag(&state_arc, &instance_id);
+        match close_emulator {
+            Some(config) => {
+                thread::spawn(move || {
+                    task_plan::run_plan(app, state_arc, instance_id, tasker, tasks, cancel);
+                    // 后置步骤：关闭模拟器
+                    if let Err(e) = emulator::stop_emulator(&config) {
+                        warn!("[start_tasks] Failed to close emulator: {}", e);
+                    }
+                });
+            }
+            None => task_plan::spawn_plan(app, state_arc, instance_id, tasker, tasks, cancel),
+        }
+        return Ok(StartTasksResult {
+            task_ids: Vec::new(),
+            plan: true,
+        });
+    }
+
     debug!("[start_tasks] Submitting {} tasks...", tasks.len());
     let mut task_ids = Vec::new();
     for (idx, task) in tasks.iter().enumerate() {
@@ -416,10 +508,25 @@ pub async fn maa_start_tasks(
         task_ids.len()
     );
 
+    // 后置步骤：最后一个任务结束后关闭模拟器
+    if let Some(config) = close_emulator {
+        let last_task_id = task_ids.last().copied();
+        let tasker = tasker.clone();
+        thread::spawn(move || {
+            if let Some(task_id) = last_task_id {
+                tasker.wait(task_id);
+            }
+            info!("[start_tasks] Tasks finished, closing emulator");
+            if let Err(e) = emulator::stop_emulator(&config) {
+                warn!("[start_tasks] Failed to close emulator: {}", e);
+            }
+        });
+    }
+
     // 缓存 task_ids，用于刷新后恢复状态
     debug!("[start_tasks] Caching task_ids...");
     {
-        let mut instances = state.instances.lock().map_err(|e| e.to_string())?;
+        let mut instances = state.instances.lock()?;
         if let Some(instance) = instances.get_mut(&instance_id) {
             instance.task_ids = task_ids.clone();
         }
@@ -430,20 +537,33 @@ pub async fn maa_start_tasks(
         "[start_tasks] maa_start_tasks completed successfully, returning {} task_ids",
         task_ids.len()
     );
-    Ok(task_ids)
+    Ok(StartTasksResult {
+        task_ids,
+        plan: false,
+    })
 }
 
 /// 停止所有 Agent 并断开连接（异步执行，避免阻塞 UI）
 /// 不强制 kill 子进程，等待 MaaTaskerPostStop 触发子进程自行退出
 #[tauri::command]
-pub fn maa_stop_agent(state: State<'_, Arc<MaaState>>, instance_id: String) -> Result<(), String> {
+pub fn maa_stop_agent(
+    state: State<'_, Arc<MaaState>>,
+    instance_id: String,
+) -> Result<(), MxuError> {
     info!("maa_stop_agent called for instance: {}", instance_id);
+    stop_agents(&state, &instance_id)
+}
 
+/// 取出实例的所有 Agent，在后台线程断开连接并等待子进程退出
+pub(super) fn stop_agents(state: &MaaState, instance_id: &str) -> Result<(), MxuError> {
     let (clients, children) = {
-        let mut instances = state.instances.lock().map_err(|e| e.to_string())?;
-        let instance = instances
-            .get_mut(&instance_id)
-            .ok_or("Instance not found")?;
+        let mut instances = state.instances.lock()?;
+        let instance =
+            instances
+                .get_mut(instance_id)
+                .ok_or_else(|| MxuError::InstanceNotFound {
+                    instance_id: instance_id.to_string(),
+                })?;
 
         // 取出所有 agent clients 和 children，准备在后台线程清理
         (
@@ -3,10 +3,12 @@
 //! 提供 MaaFramework 初始化、版本检查、设备搜索、控制器、资源和任务管理
 
 use log::{debug, error, info, warn};
+use std::collections::HashMap;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
-use tauri::State;
+use tauri::{Emitter, State};
 
 use maa_framework::controller::{AdbControllerBuilder, Controller};
 use maa_framework::resource::Resource;
@@ -14,11 +16,17 @@ use maa_framework::tasker::Tasker;
 use maa_framework::toolkit::Toolkit;
 use maa_framework::MaaStatus;
 
+use super::adb;
+use super::discovery_cache;
+use super::error::MxuError;
+use super::pipeline::ensure_valid_override;
 use super::types::{
-    AdbDevice, ConnectionStatus, ControllerConfig, MaaState, TaskStatus, VersionCheckResult,
-    Win32Window,
+    AdbDevice, BundleLoadEntry, ConnectionStatus, ControllerConfig, InstanceRuntime, MaaState,
+    ReplayInputRecord, ResourceBundleEvent, ResourceCustomList, ResourceLoadReport, TaskStatus,
+    VersionCheckResult, Win32Window,
 };
 use super::utils::{emit_callback_event, get_maafw_dir, normalize_path};
+use crate::replay_controller::ReplayController;
 
 /// MaaFramework 最小支持版本
 const MIN_MAAFW_VERSION: &str = "5.5.0-beta.1";
@@ -30,7 +38,7 @@ const MIN_MAAFW_VERSION: &str = "5.5.0-beta.1";
 /// 初始化 MaaFramework
 /// 如果提供 lib_dir 则使用该路径，否则自动从 exe 目录/maafw 加载
 #[tauri::command]
-pub fn maa_init(state: State<Arc<MaaState>>, lib_dir: Option<String>) -> Result<String, String> {
+pub fn maa_init(state: State<Arc<MaaState>>, lib_dir: Option<String>) -> Result<String, MxuError> {
     info!("maa_init called, lib_dir: {:?}", lib_dir);
 
     let lib_path = match lib_dir {
@@ -41,10 +49,9 @@ pub fn maa_init(state: State<Arc<MaaState>>, lib_dir: Option<String>) -> Result<
     info!("maa_init using path: {:?}", lib_path);
 
     if !lib_path.exists() {
-        let err = format!(
-            "MaaFramework library directory not found: {}",
-            lib_path.display()
-        );
+        let err = MxuError::LibraryNotFound {
+            path: lib_path.display().to_string(),
+        };
         error!("{}", err);
         return Err(err);
     }
@@ -78,7 +85,7 @@ pub fn maa_init(state: State<Arc<MaaState>>, lib_dir: Option<String>) -> Result<
     }
 
     // 先设置 lib_dir
-    *state.lib_dir.lock().map_err(|e| e.to_string())? = Some(lib_path.clone());
+    *state.lib_dir.lock()? = Some(lib_path.clone());
 
     // 加载库
     // 允许用户指定具体的文件路径，或者只指定目录
@@ -95,7 +102,10 @@ pub fn maa_init(state: State<Arc<MaaState>>, lib_dir: Option<String>) -> Result<
     };
 
     info!("maa_init loading library from {:?}...", dll_path);
-    maa_framework::load_library(&dll_path).map_err(|e| e.to_string())?;
+    maa_framework::load_library(&dll_path).map_err(|e| MxuError::LibraryLoadFailed {
+        path: dll_path.display().to_string(),
+        detail: e.to_string(),
+    })?;
 
     // 初始化 Toolkit
     // 初始化 Toolkit 配置，user_path 指向应用数据目录
@@ -120,20 +130,19 @@ pub fn maa_init(state: State<Arc<MaaState>>, lib_dir: Option<String>) -> Result<
 pub fn maa_set_resource_dir(
     state: State<Arc<MaaState>>,
     resource_dir: String,
-) -> Result<(), String> {
+) -> Result<(), MxuError> {
     info!(
         "maa_set_resource_dir called, resource_dir: {}",
         resource_dir
     );
-    *state.resource_dir.lock().map_err(|e| e.to_string())? =
-        Some(std::path::PathBuf::from(&resource_dir));
+    *state.resource_dir.lock()? = Some(std::path::PathBuf::from(&resource_dir));
     info!("maa_set_resource_dir success");
     Ok(())
 }
 
 /// 获取 MaaFramework 版本
 #[tauri::command]
-pub fn maa_get_version() -> Result<String, String> {
+pub fn maa_get_version() -> Result<String, MxuError> {
     debug!("maa_get_version called");
     let version = maa_framework::maa_version().to_string();
     info!("maa_get_version result: {}", version);
@@ -142,10 +151,10 @@ pub fn maa_get_version() -> Result<String, String> {
 
 /// 检查 MaaFramework 版本是否满足最小要求
 #[tauri::command]
-pub fn maa_check_version(state: State<Arc<MaaState>>) -> Result<VersionCheckResult, String> {
+pub fn maa_check_version(state: State<Arc<MaaState>>) -> Result<VersionCheckResult, MxuError> {
     debug!("maa_check_version called");
 
-    let lib_dir = state.lib_dir.lock().map_err(|e| e.to_string())?.clone();
+    let lib_dir = state.lib_dir.lock()?.clone();
 
     if let Some(dir) = lib_dir {
         #[cfg(windows)]
@@ -166,7 +175,7 @@ pub fn maa_check_version(state: State<Arc<MaaState>>) -> Result<VersionCheckResu
     let current_str = maa_framework::maa_version().to_string();
 
     if current_str == "unknown" || current_str.is_empty() {
-        return Err("MaaFramework not initialized".to_string());
+        return Err(MxuError::NotInitialized);
     }
 
     // 去掉版本号前缀 'v'（如 "v5.5.0-beta.1" -> "5.5.0-beta.1"）
@@ -174,8 +183,12 @@ pub fn maa_check_version(state: State<Arc<MaaState>>) -> Result<VersionCheckResu
     let min_clean = MIN_MAAFW_VERSION.trim_start_matches('v');
 
     // 解析最小版本（这个应该总是成功的）
-    let minimum = semver::Version::parse(min_clean)
-        .map_err(|e| format!("Failed to parse minimum version '{}': {}", min_clean, e))?;
+    let minimum = semver::Version::parse(min_clean).map_err(|e| {
+        MxuError::internal(format!(
+            "Failed to parse minimum version '{}': {}",
+            min_clean, e
+        ))
+    })?;
 
     // 尝试解析当前版本，如果解析失败（如 "DEBUG_VERSION"），视为不兼容
     let is_compatible = semver::Version::parse(current_clean).is_ok_and(|v| v >= minimum);
@@ -191,101 +204,302 @@ pub fn maa_check_version(state: State<Arc<MaaState>>) -> Result<VersionCheckResu
 // 设备搜索命令
 // ============================================================================
 
-/// 查找 ADB 设备（结果会缓存到 MaaState）
+/// 扫描 ADB 设备并查询各设备的稳定标识
+pub(super) fn scan_adb_devices() -> Result<Vec<AdbDevice>, MxuError> {
+    let devices = Toolkit::find_adb_devices().map_err(|e| MxuError::maa("find_adb_devices", e))?;
+
+    let mut result_devices: Vec<AdbDevice> = devices
+        .into_iter()
+        .map(|d| AdbDevice {
+            name: d.name,
+            adb_path: d.adb_path.to_string_lossy().to_string(),
+            address: d.address,
+            screencap_methods: d.screencap_methods,
+            input_methods: d.input_methods,
+            config: d.config.to_string(),
+            identity: None,
+            manual_added_at: None,
+        })
+        .collect();
+    adb::fill_identities(&mut result_devices);
+    Ok(result_devices)
+}
+
+/// 查找 ADB 设备（结果会缓存到 MaaState 并持久化）
 #[tauri::command]
 pub async fn maa_find_adb_devices(
     state: State<'_, Arc<MaaState>>,
-) -> Result<Vec<AdbDevice>, String> {
+) -> Result<Vec<AdbDevice>, MxuError> {
     info!("maa_find_adb_devices called");
 
     let state_arc = state.inner().clone();
 
     tauri::async_runtime::spawn_blocking(move || {
-        let devices = Toolkit::find_adb_devices().map_err(|e| e.to_string())?;
-
-        let result_devices: Vec<AdbDevice> = devices
-            .into_iter()
-            .map(|d| AdbDevice {
-                name: d.name,
-                adb_path: d.adb_path.to_string_lossy().to_string(),
-                address: d.address,
-                screencap_methods: d.screencap_methods,
-                input_methods: d.input_methods,
-                config: d.config.to_string(),
-            })
-            .collect();
+        let result_devices = scan_adb_devices()?;
 
         // 缓存搜索结果
-        if let Ok(mut cached) = state_arc.cached_adb_devices.lock() {
-            *cached = result_devices.clone();
-        }
+        discovery_cache::set_adb_devices(&state_arc, result_devices.clone());
 
         info!("Returning {} device(s)", result_devices.len());
         Ok(result_devices)
     })
-    .await
-    .map_err(|e| e.to_string())?
+    .await?
+}
+
+/// 等待窗口出现的默认超时时间（毫秒）
+const WAIT_WINDOW_DEFAULT_TIMEOUT_MS: u64 = 60_000;
+
+/// 等待窗口出现时的轮询间隔
+const WAIT_WINDOW_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Win32 窗口过滤条件（全部满足才匹配）
+struct Win32WindowFilter {
+    class_regex: Option<regex::Regex>,
+    window_regex: Option<regex::Regex>,
+    /// 所属进程名（不区分大小写）
+    process_name: Option<String>,
+    /// 所属进程的可执行文件路径（已规范化）
+    exe_path: Option<String>,
+    pid: Option<u32>,
+}
+
+impl Win32WindowFilter {
+    fn new(
+        class_regex: Option<&str>,
+        window_regex: Option<&str>,
+        process_name: Option<String>,
+        exe_path: Option<String>,
+        pid: Option<u32>,
+    ) -> Self {
+        Self {
+            class_regex: class_regex.and_then(|r| regex::Regex::new(r).ok()),
+            window_regex: window_regex.and_then(|r| regex::Regex::new(r).ok()),
+            process_name: process_name.filter(|n| !n.is_empty()),
+            exe_path: exe_path
+                .filter(|p| !p.is_empty())
+                .map(|p| crate::mxu_actions::normalize_exe_path(std::path::Path::new(&p))),
+            pid,
+        }
+    }
+
+    /// 是否需要查询窗口所属进程
+    fn needs_process(&self) -> bool {
+        self.process_name.is_some() || self.exe_path.is_some() || self.pid.is_some()
+    }
+
+    /// exe_cache 按 PID 缓存规范化后的可执行文件路径，同一进程的多个窗口只规范化一次
+    fn matches(&self, window: &Win32Window, exe_cache: &mut HashMap<u32, Option<String>>) -> bool {
+        if let Some(re) = &self.class_regex {
+            if !re.is_match(&window.class_name) {
+                return false;
+            }
+        }
+        if let Some(re) = &self.window_regex {
+            if !re.is_match(&window.window_name) {
+                return false;
+            }
+        }
+        if let Some(pid) = self.pid {
+            if window.pid != Some(pid) {
+                return false;
+            }
+        }
+        if let Some(name) = &self.process_name {
+            if !window
+                .process_name
+                .as_deref()
+                .is_some_and(|n| n.eq_ignore_ascii_case(name))
+            {
+                return false;
+            }
+        }
+        if let Some(expected) = &self.exe_path {
+            let normalize = || {
+                window
+                    .exe_path
+                    .as_deref()
+                    .map(|exe| crate::mxu_actions::normalize_exe_path(std::path::Path::new(exe)))
+            };
+            let normalized = match window.pid {
+                Some(pid) => exe_cache.entry(pid).or_insert_with(normalize).clone(),
+                None => normalize(),
+            };
+            if normalized.as_ref() != Some(expected) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// 窗口所属进程 ID
+fn window_process_id(handle: u64) -> Option<u32> {
+    #[cfg(windows)]
+    {
+        use windows::Win32::Foundation::HWND;
+        use windows::Win32::UI::WindowsAndMessaging::GetWindowThreadProcessId;
+
+        let mut pid = 0u32;
+        unsafe {
+            GetWindowThreadProcessId(HWND(handle as usize as *mut _), Some(&mut pid));
+        }
+        (pid != 0).then_some(pid)
+    }
+
+    #[cfg(not(windows))]
+    {
+        let _ = handle;
+        None
+    }
+}
+
+/// 枚举桌面窗口并按条件过滤
+/// with_process 为 true 或按进程过滤时才枚举进程，补充窗口所属进程名和可执行文件路径
+fn collect_win32_windows(
+    filter: &Win32WindowFilter,
+    with_process: bool,
+) -> Result<Vec<Win32Window>, MxuError> {
+    let windows =
+        Toolkit::find_desktop_windows().map_err(|e| MxuError::maa("find_desktop_windows", e))?;
+
+    // 进程列表只查询一次，用于补充窗口所属进程信息
+    let processes: HashMap<u32, crate::mxu_actions::ProcessInfo> =
+        if with_process || filter.needs_process() {
+            crate::mxu_actions::list_processes()
+                .into_iter()
+                .map(|p| (p.pid, p))
+                .collect()
+        } else {
+            HashMap::new()
+        };
+    let mut exe_cache = HashMap::new();
+
+    let result = windows
+        .into_iter()
+        .map(|w| {
+            let handle = w.hwnd as u64;
+            let pid = window_process_id(handle);
+            let process = pid.and_then(|pid| processes.get(&pid));
+            Win32Window {
+                handle,
+                class_name: w.class_name,
+                window_name: w.window_name,
+                pid,
+                process_name: process.map(|p| p.name.clone()),
+                exe_path: process
+                    .and_then(|p| p.exe.as_ref())
+                    .map(|exe| exe.to_string_lossy().to_string()),
+            }
+        })
+        .filter(|w| filter.matches(w, &mut exe_cache))
+        .collect::<Vec<_>>();
+
+    if filter.needs_process() {
+        debug!(
+            "Filtered by process (name: {:?}, exe: {:?}, pid: {:?}): {} window(s)",
+            filter.process_name,
+            filter.exe_path,
+            filter.pid,
+            result.len()
+        );
+    }
+    Ok(result)
 }
 
 /// 查找 Win32 窗口（结果会缓存到 MaaState）
+/// 除类名 / 标题正则外，还可按所属进程名、可执行文件路径或 PID 过滤
 #[tauri::command]
 pub async fn maa_find_win32_windows(
     state: State<'_, Arc<MaaState>>,
     class_regex: Option<String>,
     window_regex: Option<String>,
-) -> Result<Vec<Win32Window>, String> {
+    process_name: Option<String>,
+    exe_path: Option<String>,
+    pid: Option<u32>,
+) -> Result<Vec<Win32Window>, MxuError> {
     info!(
-        "maa_find_win32_windows called, class_regex: {:?}, window_regex: {:?}",
-        class_regex, window_regex
+        "maa_find_win32_windows called, class_regex: {:?}, window_regex: {:?}, process_name: {:?}, exe_path: {:?}, pid: {:?}",
+        class_regex, window_regex, process_name, exe_path, pid
     );
 
     let state_arc = state.inner().clone();
-    let class_re_str = class_regex.clone();
-    let window_re_str = window_regex.clone();
 
     tauri::async_runtime::spawn_blocking(move || {
-        let windows = Toolkit::find_desktop_windows().map_err(|e| e.to_string())?;
+        let filter = Win32WindowFilter::new(
+            class_regex.as_deref(),
+            window_regex.as_deref(),
+            process_name,
+            exe_path,
+            pid,
+        );
+        let result_windows = collect_win32_windows(&filter, true)?;
 
-        // 编译正则表达式
-        let class_re = class_re_str
-            .as_ref()
-            .and_then(|r| regex::Regex::new(r).ok());
-        let window_re = window_re_str
-            .as_ref()
-            .and_then(|r| regex::Regex::new(r).ok());
+        // 缓存搜索结果
+        discovery_cache::set_win32_windows(&state_arc, result_windows.clone());
 
-        let mut result_windows = Vec::new();
+        info!("Returning {} filtered window(s)", result_windows.len());
+        Ok(result_windows)
+    })
+    .await?
+}
 
-        for w in windows {
-            // 过滤
-            if let Some(re) = &class_re {
-                if !re.is_match(&w.class_name) {
-                    continue;
-                }
-            }
-            if let Some(re) = &window_re {
-                if !re.is_match(&w.window_name) {
-                    continue;
-                }
-            }
+/// 等待匹配的 Win32 窗口出现（如 MXU_LAUNCH 启动游戏后）
+/// 过滤条件与 maa_find_win32_windows 相同，超时仍未出现时返回空列表
+#[tauri::command]
+pub async fn maa_wait_for_win32_window(
+    state: State<'_, Arc<MaaState>>,
+    class_regex: Option<String>,
+    window_regex: Option<String>,
+    process_name: Option<String>,
+    exe_path: Option<String>,
+    pid: Option<u32>,
+    timeout_ms: Option<u64>,
+) -> Result<Vec<Win32Window>, MxuError> {
+    let timeout = Duration::from_millis(timeout_ms.unwrap_or(WAIT_WINDOW_DEFAULT_TIMEOUT_MS));
+    info!(
+        "maa_wait_for_win32_window called, class_regex: {:?}, window_regex: {:?}, process_name: {:?}, exe_path: {:?}, pid: {:?}, timeout: {:?}",
+        class_regex, window_regex, process_name, exe_path, pid, timeout
+    );
 
-            result_windows.push(Win32Window {
-                handle: w.hwnd as u64,
-                class_name: w.class_name,
-                window_name: w.window_name,
-            });
-        }
+    let state_arc = state.inner().clone();
 
-        // 缓存搜索结果
-        if let Ok(mut cached) = state_arc.cached_win32_windows.lock() {
-            *cached = result_windows.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let filter = Win32WindowFilter::new(
+            class_regex.as_deref(),
+            window_regex.as_deref(),
+            process_name,
+            exe_path,
+            pid,
+        );
+        let started = Instant::now();
+        loop {
+            // 轮询时不为补充进程信息而枚举进程，窗口出现后再完整查询一次
+            let windows = collect_win32_windows(&filter, false)?;
+            if !windows.is_empty() {
+                let windows = if filter.needs_process() {
+                    windows
+                } else {
+                    match collect_win32_windows(&filter, true)? {
+                        full if full.is_empty() => windows,
+                        full => full,
+                    }
+                };
+                info!(
+                    "Window appeared after {} ms: {} window(s)",
+                    started.elapsed().as_millis(),
+                    windows.len()
+                );
+                discovery_cache::set_win32_windows(&state_arc, windows.clone());
+                return Ok(windows);
+            }
+            if started.elapsed() >= timeout {
+                warn!("Timed out waiting for window after {:?}", timeout);
+                return Ok(Vec::new());
+            }
+            std::thread::sleep(WAIT_WINDOW_POLL_INTERVAL);
         }
-
-        info!("Returning {} filtered window(s)", result_windows.len());
-        Ok(result_windows)
     })
-    .await
-    .map_err(|e| e.to_string())?
+    .await?
 }
 
 // ============================================================================
@@ -294,10 +508,13 @@ pub async fn maa_find_win32_windows(
 
 /// 创建实例（幂等操作，实例已存在时直接返回成功）
 #[tauri::command]
-pub fn maa_create_instance(state: State<Arc<MaaState>>, instance_id: String) -> Result<(), String> {
+pub fn maa_create_instance(
+    state: State<Arc<MaaState>>,
+    instance_id: String,
+) -> Result<(), MxuError> {
     info!("maa_create_instance called, instance_id: {}", instance_id);
 
-    let mut instances = state.instances.lock().map_err(|e| e.to_string())?;
+    let mut instances = state.instances.lock()?;
 
     if instances.contains_key(&instance_id) {
         debug!("maa_create_instance: instance already exists, returning success");
@@ -317,13 +534,16 @@ pub fn maa_create_instance(state: State<Arc<MaaState>>, instance_id: String) ->
 pub fn maa_destroy_instance(
     state: State<Arc<MaaState>>,
     instance_id: String,
-) -> Result<(), String> {
+) -> Result<(), MxuError> {
     info!("maa_destroy_instance called, instance_id: {}", instance_id);
 
-    let mut instances = state.instances.lock().map_err(|e| e.to_string())?;
-    let removed = instances.remove(&instance_id).is_some();
+    // 先移出再在锁外销毁：销毁 tasker 会等待其运行中的任务结束
+    let removed = state.instances.lock()?.remove(&instance_id);
+    state.unregister_tasker(&instance_id);
+    crate::mxu_actions::kill_launched_processes(&instance_id);
 
-    if removed {
+    if let Some(instance) = removed {
+        drop(instance);
         info!("maa_destroy_instance success, instance_id: {}", instance_id);
     } else {
         warn!(
@@ -346,7 +566,7 @@ pub async fn maa_connect_controller(
     state: State<'_, Arc<MaaState>>,
     instance_id: String,
     config: ControllerConfig,
-) -> Result<i64, String> {
+) -> Result<i64, MxuError> {
     info!(
         "maa_connect_controller called, instance_id: {}",
         instance_id
@@ -357,6 +577,7 @@ pub async fn maa_connect_controller(
 
     // Move blocking controller creation and connection to spawn_blocking
     tauri::async_runtime::spawn_blocking(move || {
+        let mut replay_inputs = None;
         let controller = match &config {
             ControllerConfig::Adb {
                 adb_path,
@@ -367,14 +588,15 @@ pub async fn maa_connect_controller(
             } => {
                 // 将字符串解析为 u64
                 let screencap = screencap_methods.parse::<u64>().map_err(|e| {
-                    format!("Invalid screencap_methods '{}': {}", screencap_methods, e)
+                    MxuError::invalid_param(
+                        "screencap_methods",
+                        format!("'{}': {}", screencap_methods, e),
+                    )
+                })?;
+                let input = input_methods.parse::<u64>().map_err(|e| {
+                    MxuError::invalid_param("input_methods", format!("'{}': {}", input_methods, e))
                 })?;
-                let input = input_methods
-                    .parse::<u64>()
-                    .map_err(|e| format!("Invalid input_methods '{}': {}", input_methods, e))?;
-                let agent_path = get_maafw_dir()
-                    .map(|p| p.join("MaaAgentBinary").to_string_lossy().to_string())
-                    .unwrap_or_else(|_| "./MaaAgentBinary".to_string());
+                let agent_path = adb_agent_path();
 
                 AdbControllerBuilder::new(adb_path, address)
                     .screencap_methods(
@@ -387,7 +609,7 @@ pub async fn maa_connect_controller(
                     .config(config)
                     .agent_path(&agent_path)
                     .build()
-                    .map_err(|e| e.to_string())?
+                    .map_err(|e| MxuError::maa("create_adb_controller", e))?
             }
             ControllerConfig::Win32 {
                 handle,
@@ -407,11 +629,12 @@ pub async fn maa_connect_controller(
                     maa_framework::common::Win32InputMethod::from_bits_truncate(*keyboard_method)
                         .bits(),
                 )
-                .map_err(|e| e.to_string())?
+                .map_err(|e| MxuError::maa("create_win32_controller", e))?
             }
             ControllerConfig::PlayCover { address, uuid } => {
                 let uuid_str = uuid.as_deref().unwrap_or("");
-                Controller::new_playcover(address, uuid_str).map_err(|e| e.to_string())?
+                Controller::new_playcover(address, uuid_str)
+                    .map_err(|e| MxuError::maa("create_playcover_controller", e))?
             }
             ControllerConfig::Gamepad {
                 handle,
@@ -430,7 +653,28 @@ pub async fn maa_connect_controller(
                     .map(|v| maa_framework::common::Win32ScreencapMethod::from_bits_truncate(v))
                     .unwrap_or(maa_framework::common::Win32ScreencapMethod::DXGI_DESKTOP_DUP);
 
-                Controller::new_gamepad(hwnd, gp_type, screencap).map_err(|e| e.to_string())?
+                Controller::new_gamepad(hwnd, gp_type, screencap)
+                    .map_err(|e| MxuError::maa("create_gamepad_controller", e))?
+            }
+            ControllerConfig::Replay {
+                image_dir,
+                script,
+                loop_images,
+            } => {
+                let script_path = script
+                    .as_deref()
+                    .filter(|s| !s.is_empty())
+                    .map(normalize_path);
+                let replay = ReplayController::new(
+                    app_handle.clone(),
+                    &instance_id,
+                    &normalize_path(image_dir),
+                    script_path.as_deref(),
+                    *loop_images,
+                )?;
+                replay_inputs = Some(replay.input_log());
+                Controller::new_custom(Box::new(replay))
+                    .map_err(|e| MxuError::maa("create_replay_controller", e))?
             }
         };
 
@@ -440,7 +684,7 @@ pub async fn maa_connect_controller(
             .add_sink(move |msg, detail| {
                 emit_callback_event(&app_handle_clone, msg, detail);
             })
-            .map_err(|e| e.to_string())?;
+            .map_err(|e| MxuError::maa("controller_add_sink", e))?;
 
         // 设置默认参数
         if let Err(e) = controller.set_screenshot_target_short_side(720) {
@@ -448,24 +692,37 @@ pub async fn maa_connect_controller(
         }
 
         // 发起连接
-        let conn_id = controller.post_connection().map_err(|e| e.to_string())?;
+        let conn_id = controller
+            .post_connection()
+            .map_err(|e| MxuError::maa("post_connection", e))?;
 
         // 更新实例状态
         debug!("Updating instance state...");
         {
-            let mut instances = state_arc.instances.lock().map_err(|e| e.to_string())?;
-            let instance = instances
-                .get_mut(&instance_id)
-                .ok_or("Instance not found")?;
+            let mut instances = state_arc.instances.lock()?;
+            let instance =
+                instances
+                    .get_mut(&instance_id)
+                    .ok_or_else(|| MxuError::InstanceNotFound {
+                        instance_id: instance_id.clone(),
+                    })?;
 
             instance.controller = Some(controller);
             instance.tasker = None;
+            instance.replay_inputs = replay_inputs;
+            state_arc.unregister_tasker(&instance_id);
         }
 
         Ok(conn_id)
     })
-    .await
-    .map_err(|e| e.to_string())?
+    .await?
+}
+
+/// ADB 控制器使用的 MaaAgentBinary 目录
+pub(super) fn adb_agent_path() -> String {
+    get_maafw_dir()
+        .map(|p| p.join("MaaAgentBinary").to_string_lossy().to_string())
+        .unwrap_or_else(|_| "./MaaAgentBinary".to_string())
 }
 
 /// 获取连接状态（通过 MaaControllerConnected API 查询）
@@ -473,9 +730,13 @@ pub async fn maa_connect_controller(
 pub fn maa_get_connection_status(
     state: State<Arc<MaaState>>,
     instance_id: String,
-) -> Result<ConnectionStatus, String> {
-    let instances = state.instances.lock().map_err(|e| e.to_string())?;
-    let instance = instances.get(&instance_id).ok_or("Instance not found")?;
+) -> Result<ConnectionStatus, MxuError> {
+    let instances = state.instances.lock()?;
+    let instance = instances
+        .get(&instance_id)
+        .ok_or_else(|| MxuError::InstanceNotFound {
+            instance_id: instance_id.clone(),
+        })?;
 
     if instance.controller.as_ref().is_some_and(|c| c.connected()) {
         Ok(ConnectionStatus::Connected)
@@ -484,63 +745,273 @@ pub fn maa_get_connection_status(
     }
 }
 
+/// 获取回放控制器收到的输入记录
+/// clear 为 true 时读取后清空
+#[tauri::command]
+pub fn maa_get_replay_inputs(
+    state: State<Arc<MaaState>>,
+    instance_id: String,
+    clear: Option<bool>,
+) -> Result<Vec<ReplayInputRecord>, MxuError> {
+    let instances = state.instances.lock()?;
+    let instance = instances
+        .get(&instance_id)
+        .ok_or_else(|| MxuError::InstanceNotFound {
+            instance_id: instance_id.clone(),
+        })?;
+    let replay_inputs =
+        instance
+            .replay_inputs
+            .as_ref()
+            .ok_or_else(|| MxuError::ControllerNotConnected {
+                instance_id: instance_id.clone(),
+            })?;
+
+    let mut inputs = replay_inputs.lock()?;
+    if clear.unwrap_or(false) {
+        Ok(std::mem::take(&mut *inputs))
+    } else {
+        Ok(inputs.clone())
+    }
+}
+
 // ============================================================================
 // 资源命令
 // ============================================================================
 
+/// 资源包常规子目录，缺失时在报告中提示
+const BUNDLE_EXPECTED_DIRS: [&str; 2] = ["pipeline", "image"];
+
 /// 加载资源（异步，通过回调通知完成状态）
-/// 返回资源加载请求 ID 列表，前端通过监听 maa-callback 事件获取完成状态
+/// 返回与 paths 一一对应的加载报告；wait 为 true 时等待所有资源包加载完成后再返回
+/// 每个资源包完成时发送 maa-resource-bundle 事件（含耗时）
 #[tauri::command]
-pub fn maa_load_resource(
+pub async fn maa_load_resource(
     app: tauri::AppHandle,
-    state: State<Arc<MaaState>>,
+    state: State<'_, Arc<MaaState>>,
     instance_id: String,
     paths: Vec<String>,
-) -> Result<Vec<i64>, String> {
+    wait: Option<bool>,
+) -> Result<ResourceLoadReport, MxuError> {
     info!(
-        "maa_load_resource called, instance: {}, paths: {:?}",
-        instance_id, paths
+        "maa_load_resource called, instance: {}, paths: {:?}, wait: {:?}",
+        instance_id, paths, wait
     );
 
-    let mut instances = state.instances.lock().map_err(|e| e.to_string())?;
-    let instance = instances
-        .get_mut(&instance_id)
-        .ok_or("Instance not found")?;
+    let state_arc = state.inner().clone();
 
-    // 创建或获取资源
-    if instance.resource.is_none() {
-        let res = Resource::new().map_err(|e| e.to_string())?;
+    tauri::async_runtime::spawn_blocking(move || {
+        let (resource, mut bundles, posted_at) = {
+            let mut instances = state_arc.instances.lock()?;
+            let instance =
+                instances
+                    .get_mut(&instance_id)
+                    .ok_or_else(|| MxuError::InstanceNotFound {
+                        instance_id: instance_id.clone(),
+                    })?;
+
+            // 创建或获取资源
+            if instance.resource.is_none() {
+                instance.resource = Some(create_resource(&app)?);
+            }
 
-        // 注册回调
-        let app_handle = app.clone();
-        res.add_sink(move |msg, detail| {
+            let resource = instance.resource.clone().unwrap();
+            let posted_at = Instant::now();
+            let bundles: Vec<BundleLoadEntry> = paths
+                .iter()
+                .map(|path| post_bundle_entry(&resource, path))
+                .collect();
+
+            // 记录已提交的资源包路径（供热重载使用）
+            instance.bundle_paths.extend(
+                bundles
+                    .iter()
+                    .filter(|b| b.job_id.is_some())
+                    .map(|b| normalize_path(&b.path).to_string_lossy().to_string()),
+            );
+
+            (resource, bundles, posted_at)
+        };
+
+        let pending: Vec<(usize, i64)> = bundles
+            .iter()
+            .enumerate()
+            .filter_map(|(i, b)| b.job_id.map(|id| (i, id)))
+            .collect();
+
+        if pending.is_empty() {
+            return Ok(ResourceLoadReport { bundles });
+        }
+
+        if wait.unwrap_or(false) {
+            let results = watch_bundle_jobs(&app, &instance_id, &resource, &bundles, posted_at);
+            for (i, status, elapsed_ms) in results {
+                bundles[i].status = Some(status);
+                bundles[i].elapsed_ms = Some(elapsed_ms);
+            }
+        } else {
+            // 后台等待，仅通过事件通知各资源包完成情况
+            let report_bundles = bundles.clone();
+            std::thread::spawn(move || {
+                watch_bundle_jobs(&app, &instance_id, &resource, &report_bundles, posted_at);
+            });
+        }
+
+        Ok(ResourceLoadReport { bundles })
+    })
+    .await?
+}
+
+/// 创建资源并注册回调和 MXU 内置 custom actions / recognitions
+pub(super) fn create_resource(app: &tauri::AppHandle) -> Result<Resource, MxuError> {
+    let res = Resource::new().map_err(|e| MxuError::maa("create_resource", e))?;
+
+    // 注册回调
+    let app_handle = app.clone();
+    res.add_sink(move |msg, detail| {
+        emit_callback_event(&app_handle, msg, detail);
+    })
+    .map_err(|e| MxuError::maa("resource_add_sink", e))?;
+
+    // 注册 MXU Custom Actions / Recognitions
+    crate::mxu_actions::register_all_mxu_actions(&res)?;
+    crate::mxu_recognitions::register_all_mxu_recognitions(&res)?;
+    crate::mxu_plugins::register_plugin_actions(&res)?;
+
+    Ok(res)
+}
+
+/// 创建 tasker，登记所属实例，注册回调并绑定资源和控制器
+pub(super) fn create_tasker(
+    app: &tauri::AppHandle,
+    state: &MaaState,
+    instance_id: &str,
+    resource: &Resource,
+    controller: &Controller,
+) -> Result<Tasker, MxuError> {
+    let tasker = Tasker::new().map_err(|e| MxuError::maa("create_tasker", e))?;
+    let running_tasks = state.register_tasker(instance_id, &tasker);
+
+    // 添加回调 Sink，用于接收任务状态通知
+    let app_handle = app.clone();
+    tasker
+        .add_sink(move |msg, detail| {
+            super::task_plan::track_running_tasks(&running_tasks, msg);
+            super::task_plan::on_tasker_event(msg, detail);
             emit_callback_event(&app_handle, msg, detail);
         })
-        .map_err(|e| e.to_string())?;
+        .map_err(|e| MxuError::maa("tasker_add_sink", e))?;
+
+    // 添加 Context Sink，用于接收 Node 级别的通知（包含 focus 消息）
+    let app_handle = app.clone();
+    tasker
+        .add_context_sink(move |msg, detail| {
+            emit_callback_event(&app_handle, msg, detail);
+        })
+        .map_err(|e| MxuError::maa("tasker_add_context_sink", e))?;
+
+    // 绑定资源和控制器
+    tasker
+        .bind(resource, controller)
+        .map_err(|e| MxuError::maa("tasker_bind", e))?;
 
-        // 注册 MXU Custom Actions
-        crate::mxu_actions::register_all_mxu_actions(&res)?;
+    Ok(tasker)
+}
+
+/// 检查并提交单个资源包
+fn post_bundle_entry(resource: &Resource, path: &str) -> BundleLoadEntry {
+    let normalized = normalize_path(path);
+    let mut entry = BundleLoadEntry {
+        path: path.to_string(),
+        job_id: None,
+        error: None,
+        missing_dirs: Vec::new(),
+        status: None,
+        elapsed_ms: None,
+    };
+
+    if !normalized.is_dir() {
+        warn!(
+            "Resource bundle directory not found: {}",
+            normalized.display()
+        );
+        entry.error = Some(format!("directory not found: {}", normalized.display()));
+        return entry;
+    }
 
-        instance.resource = Some(res);
+    entry.missing_dirs = BUNDLE_EXPECTED_DIRS
+        .iter()
+        .filter(|dir| !normalized.join(dir).is_dir())
+        .map(|dir| dir.to_string())
+        .collect();
+    if !entry.missing_dirs.is_empty() {
+        warn!(
+            "Resource bundle {} is missing sub directories: {:?}",
+            normalized.display(),
+            entry.missing_dirs
+        );
     }
 
-    let resource = instance.resource.as_ref().unwrap();
-    let mut res_ids = Vec::new();
+    let normalized = normalized.to_string_lossy().to_string();
+    match resource.post_bundle(&normalized) {
+        Ok(job) => {
+            info!("Posted resource bundle: {} -> id: {}", normalized, job.id);
+            entry.job_id = Some(job.id);
+        }
+        Err(e) => {
+            warn!("Failed to post resource bundle {}: {}", normalized, e);
+            entry.error = Some(e.to_string());
+        }
+    }
 
-    for path in paths {
-        let normalized = normalize_path(&path).to_string_lossy().to_string();
-        match resource.post_bundle(&normalized) {
-            Ok(job) => {
-                info!("Posted resource bundle: {} -> id: {}", normalized, job.id);
-                res_ids.push(job.id);
-            }
-            Err(e) => {
-                warn!("Failed to post resource bundle {}: {}", normalized, e);
-            }
+    entry
+}
+
+/// 按提交顺序等待资源包加载完成，逐个发送 maa-resource-bundle 事件
+/// 资源包串行加载，耗时从上一个资源包完成（或提交）时刻算起
+fn watch_bundle_jobs(
+    app: &tauri::AppHandle,
+    instance_id: &str,
+    resource: &Resource,
+    bundles: &[BundleLoadEntry],
+    posted_at: Instant,
+) -> Vec<(usize, TaskStatus, u64)> {
+    let mut results = Vec::new();
+    let mut last_done = posted_at;
+
+    for (i, bundle) in bundles.iter().enumerate() {
+        let Some(job_id) = bundle.job_id else {
+            continue;
+        };
+
+        let status = match resource.wait(job_id) {
+            MaaStatus::SUCCEEDED => TaskStatus::Succeeded,
+            _ => TaskStatus::Failed,
+        };
+        let now = Instant::now();
+        let elapsed_ms = now.duration_since(last_done).as_millis() as u64;
+        last_done = now;
+
+        info!(
+            "Resource bundle {} (id: {}) finished: {:?}, {}ms",
+            bundle.path, job_id, status, elapsed_ms
+        );
+
+        let event = ResourceBundleEvent {
+            instance_id: instance_id.to_string(),
+            path: bundle.path.clone(),
+            job_id,
+            status: status.clone(),
+            elapsed_ms,
+        };
+        if let Err(e) = app.emit("maa-resource-bundle", event) {
+            warn!("Failed to emit maa-resource-bundle: {}", e);
         }
+
+        results.push((i, status, elapsed_ms));
     }
 
-    Ok(res_ids)
+    results
 }
 
 /// 检查资源是否已加载（通过 MaaResourceLoaded API 查询）
@@ -548,9 +1019,13 @@ pub fn maa_load_resource(
 pub fn maa_is_resource_loaded(
     state: State<Arc<MaaState>>,
     instance_id: String,
-) -> Result<bool, String> {
-    let instances = state.instances.lock().map_err(|e| e.to_string())?;
-    let instance = instances.get(&instance_id).ok_or("Instance not found")?;
+) -> Result<bool, MxuError> {
+    let instances = state.instances.lock()?;
+    let instance = instances
+        .get(&instance_id)
+        .ok_or_else(|| MxuError::InstanceNotFound {
+            instance_id: instance_id.clone(),
+        })?;
 
     Ok(instance.resource.as_ref().is_some_and(|r| r.loaded()))
 }
@@ -560,19 +1035,124 @@ pub fn maa_is_resource_loaded(
 pub fn maa_destroy_resource(
     state: State<Arc<MaaState>>,
     instance_id: String,
-) -> Result<(), String> {
-    let mut instances = state.instances.lock().map_err(|e| e.to_string())?;
+) -> Result<(), MxuError> {
+    let mut instances = state.instances.lock()?;
     let instance = instances
         .get_mut(&instance_id)
-        .ok_or("Instance not found")?;
+        .ok_or_else(|| MxuError::InstanceNotFound {
+            instance_id: instance_id.clone(),
+        })?;
 
     // 销毁旧的资源
     instance.resource = None;
     instance.tasker = None;
+    instance.bundle_paths.clear();
+    state.unregister_tasker(&instance_id);
 
     Ok(())
 }
 
+/// 获取实例中已加载完成的资源
+fn loaded_resource<'a>(
+    instances: &'a HashMap<String, InstanceRuntime>,
+    instance_id: &str,
+) -> Result<&'a Resource, MxuError> {
+    let instance = instances
+        .get(instance_id)
+        .ok_or_else(|| MxuError::InstanceNotFound {
+            instance_id: instance_id.to_string(),
+        })?;
+    instance
+        .resource
+        .as_ref()
+        .filter(|r| r.loaded())
+        .ok_or_else(|| MxuError::ResourceNotLoaded {
+            instance_id: instance_id.to_string(),
+        })
+}
+
+/// 获取资源中所有节点名（按名称排序）
+#[tauri::command]
+pub fn maa_get_node_list(
+    state: State<Arc<MaaState>>,
+    instance_id: String,
+) -> Result<Vec<String>, MxuError> {
+    let instances = state.instances.lock()?;
+    let resource = loaded_resource(&instances, &instance_id)?;
+
+    let mut nodes = resource
+        .node_list()
+        .map_err(|e| MxuError::maa("node_list", e))?;
+    nodes.sort();
+    Ok(nodes)
+}
+
+/// 获取节点合并后的定义（多个 bundle 及 override 叠加后的结果）
+/// 节点不存在时返回 None
+#[tauri::command]
+pub fn maa_get_node_data(
+    state: State<Arc<MaaState>>,
+    instance_id: String,
+    node_name: String,
+) -> Result<Option<serde_json::Value>, MxuError> {
+    debug!(
+        "maa_get_node_data called, instance_id: {}, node: {}",
+        instance_id, node_name
+    );
+
+    let instances = state.instances.lock()?;
+    let resource = loaded_resource(&instances, &instance_id)?;
+
+    let Some(raw) = resource
+        .get_node_data(&node_name)
+        .map_err(|e| MxuError::maa("get_node_data", e))?
+    else {
+        return Ok(None);
+    };
+
+    serde_json::from_str(&raw)
+        .map(Some)
+        .map_err(|e| MxuError::maa("get_node_data", format!("invalid node JSON: {}", e)))
+}
+
+/// 获取资源中已注册的自定义动作和识别（包含 MXU 内置动作）
+#[tauri::command]
+pub fn maa_get_custom_list(
+    state: State<Arc<MaaState>>,
+    instance_id: String,
+) -> Result<ResourceCustomList, MxuError> {
+    let instances = state.instances.lock()?;
+    let resource = loaded_resource(&instances, &instance_id)?;
+
+    let mut actions = resource
+        .custom_action_list()
+        .map_err(|e| MxuError::maa("custom_action_list", e))?;
+    let mut recognitions = resource
+        .custom_recognition_list()
+        .map_err(|e| MxuError::maa("custom_recognition_list", e))?;
+    actions.sort();
+    recognitions.sort();
+
+    Ok(ResourceCustomList {
+        actions,
+        recognitions,
+    })
+}
+
+/// 获取资源哈希（资源内容变化时改变，可用于缓存失效判断）
+#[tauri::command]
+pub fn maa_get_resource_hash(
+    state: State<Arc<MaaState>>,
+    instance_id: String,
+) -> Result<String, MxuError> {
+    let instances = state.instances.lock()?;
+    let resource = loaded_resource(&instances, &instance_id)?;
+
+    resource
+        .hash()
+        .map_err(|e| MxuError::maa("resource_hash", e))
+}
+
 // ============================================================================
 // 任务命令
 // ============================================================================
@@ -586,58 +1166,54 @@ pub fn maa_run_task(
     instance_id: String,
     entry: String,
     pipeline_override: String,
-) -> Result<i64, String> {
+) -> Result<i64, MxuError> {
     info!("maa_run_task called, entry: {}", entry);
 
-    let mut instances = state.instances.lock().map_err(|e| e.to_string())?;
+    let mut instances = state.instances.lock()?;
     let instance = instances
         .get_mut(&instance_id)
-        .ok_or("Instance not found")?;
+        .ok_or_else(|| MxuError::InstanceNotFound {
+            instance_id: instance_id.clone(),
+        })?;
 
-    let resource = instance.resource.as_ref().ok_or("Resource not loaded")?;
-    let controller = instance
-        .controller
+    let resource = instance
+        .resource
         .as_ref()
-        .ok_or("Controller not connected")?;
+        .ok_or_else(|| MxuError::ResourceNotLoaded {
+            instance_id: instance_id.clone(),
+        })?;
+    let controller =
+        instance
+            .controller
+            .as_ref()
+            .ok_or_else(|| MxuError::ControllerNotConnected {
+                instance_id: instance_id.clone(),
+            })?;
 
     // 创建或获取 tasker
     if instance.tasker.is_none() {
-        let tasker = Tasker::new().map_err(|e| e.to_string())?;
-
-        // 添加回调 Sink，用于接收任务状态通知
-        let app_handle = app.clone();
-        tasker
-            .add_sink(move |msg, detail| {
-                emit_callback_event(&app_handle, msg, detail);
-            })
-            .map_err(|e| e.to_string())?;
-
-        // 添加 Context Sink，用于接收 Node 级别的通知（包含 focus 消息）
-        let app_handle = app.clone();
-        tasker
-            .add_context_sink(move |msg, detail| {
-                emit_callback_event(&app_handle, msg, detail);
-            })
-            .map_err(|e| e.to_string())?;
-
-        // 绑定资源和控制器
-        tasker
-            .bind(resource, controller)
-            .map_err(|e| e.to_string())?;
-
-        instance.tasker = Some(tasker);
+        instance.tasker = Some(create_tasker(
+            &app,
+            &state,
+            &instance_id,
+            resource,
+            controller,
+        )?);
     }
 
     let tasker = instance.tasker.as_ref().unwrap();
 
     // 检查初始化状态
     if !tasker.inited() {
-        return Err("Tasker not initialized".to_string());
+        return Err(MxuError::TaskerNotInitialized { instance_id });
     }
 
+    // 提交前校验 pipeline_override
+    ensure_valid_override(resource, Some(&entry), &pipeline_override)?;
+
     let job = tasker
         .post_task(&entry, &pipeline_override)
-        .map_err(|e| e.to_string())?;
+        .map_err(|e| MxuError::maa("post_task", e))?;
     let task_id = job.id;
 
     instance.task_ids.push(task_id);
@@ -651,14 +1227,23 @@ pub fn maa_get_task_status(
     state: State<Arc<MaaState>>,
     instance_id: String,
     task_id: i64,
-) -> Result<TaskStatus, String> {
-    let instances = state.instances.lock().map_err(|e| e.to_string())?;
-    let instance = instances.get(&instance_id).ok_or("Instance not found")?;
-    let tasker = instance.tasker.as_ref().ok_or("Tasker not created")?;
+) -> Result<TaskStatus, MxuError> {
+    let instances = state.instances.lock()?;
+    let instance = instances
+        .get(&instance_id)
+        .ok_or_else(|| MxuError::InstanceNotFound {
+            instance_id: instance_id.clone(),
+        })?;
+    let tasker = instance
+        .tasker
+        .as_ref()
+        .ok_or_else(|| MxuError::TaskerNotCreated {
+            instance_id: instance_id.clone(),
+        })?;
 
     let status = tasker
         .get_task_detail(task_id)
-        .map_err(|e| e.to_string())?
+        .map_err(|e| MxuError::maa("get_task_detail", e))?
         .map(|d| d.status)
         .unwrap_or(MaaStatus::INVALID);
 
@@ -674,12 +1259,19 @@ pub fn maa_get_task_status(
 
 /// 停止任务
 #[tauri::command]
-pub fn maa_stop_task(state: State<Arc<MaaState>>, instance_id: String) -> Result<(), String> {
-    let mut instances = state.instances.lock().map_err(|e| e.to_string())?;
+pub fn maa_stop_task(state: State<Arc<MaaState>>, instance_id: String) -> Result<(), MxuError> {
+    let mut instances = state.instances.lock()?;
     let instance = instances
         .get_mut(&instance_id)
-        .ok_or("Instance not found")?;
-    let tasker = instance.tasker.as_ref().ok_or("Tasker not created")?;
+        .ok_or_else(|| MxuError::InstanceNotFound {
+            instance_id: instance_id.clone(),
+        })?;
+    let tasker = instance
+        .tasker
+        .as_ref()
+        .ok_or_else(|| MxuError::TaskerNotCreated {
+            instance_id: instance_id.clone(),
+        })?;
 
     if instance.stop_in_progress {
         if !tasker.running() {
@@ -698,10 +1290,17 @@ pub fn maa_stop_task(state: State<Arc<MaaState>>, instance_id: String) -> Result
 
     instance.stop_in_progress = true;
     instance.stop_started_at = Some(Instant::now());
+    // 通知执行计划停止，避免当前任务因停止失败后计划继续提交下一个任务
+    instance.plan_cancel.store(true, Ordering::SeqCst);
     // 清空缓存的 task_ids
     instance.task_ids.clear();
 
-    tasker.post_stop().map_err(|e| e.to_string())?;
+    tasker
+        .post_stop()
+        .map_err(|e| MxuError::maa("post_stop", e))?;
+
+    // 结束 MXU_LAUNCH 启动并登记的进程，避免等待中的动作阻塞停止
+    crate::mxu_actions::kill_launched_processes(&instance_id);
     Ok(())
 }
 
@@ -712,21 +1311,38 @@ pub fn maa_override_pipeline(
     instance_id: String,
     task_id: i64,
     pipeline_override: String,
-) -> Result<bool, String> {
-    let instances = state.instances.lock().map_err(|e| e.to_string())?;
-    let instance = instances.get(&instance_id).ok_or("Instance not found")?;
-    let tasker = instance.tasker.as_ref().ok_or("Tasker not created")?;
+) -> Result<bool, MxuError> {
+    let instances = state.instances.lock()?;
+    let instance = instances
+        .get(&instance_id)
+        .ok_or_else(|| MxuError::InstanceNotFound {
+            instance_id: instance_id.clone(),
+        })?;
+    let tasker = instance
+        .tasker
+        .as_ref()
+        .ok_or_else(|| MxuError::TaskerNotCreated {
+            instance_id: instance_id.clone(),
+        })?;
+
+    if let Some(resource) = instance.resource.as_ref() {
+        ensure_valid_override(resource, None, &pipeline_override)?;
+    }
 
     tasker
         .override_pipeline(task_id, &pipeline_override)
-        .map_err(|e| e.to_string())
+        .map_err(|e| MxuError::maa("override_pipeline", e))
 }
 
 /// 检查是否正在运行
 #[tauri::command]
-pub fn maa_is_running(state: State<Arc<MaaState>>, instance_id: String) -> Result<bool, String> {
-    let instances = state.instances.lock().map_err(|e| e.to_string())?;
-    let instance = instances.get(&instance_id).ok_or("Instance not found")?;
+pub fn maa_is_running(state: State<Arc<MaaState>>, instance_id: String) -> Result<bool, MxuError> {
+    let instances = state.instances.lock()?;
+    let instance = instances
+        .get(&instance_id)
+        .ok_or_else(|| MxuError::InstanceNotFound {
+            instance_id: instance_id.clone(),
+        })?;
 
     Ok(instance.tasker.as_ref().is_some_and(|t| t.running()))
 }
@@ -737,15 +1353,27 @@ pub fn maa_is_running(state: State<Arc<MaaState>>, instance_id: String) -> Resul
 
 /// 发起截图请求
 #[tauri::command]
-pub fn maa_post_screencap(state: State<Arc<MaaState>>, instance_id: String) -> Result<i64, String> {
-    let instances = state.instances.lock().map_err(|e| e.to_string())?;
-    let instance = instances.get(&instance_id).ok_or("Instance not found")?;
-    let controller = instance
-        .controller
-        .as_ref()
-        .ok_or("Controller not connected")?;
+pub fn maa_post_screencap(
+    state: State<Arc<MaaState>>,
+    instance_id: String,
+) -> Result<i64, MxuError> {
+    let instances = state.instances.lock()?;
+    let instance = instances
+        .get(&instance_id)
+        .ok_or_else(|| MxuError::InstanceNotFound {
+            instance_id: instance_id.clone(),
+        })?;
+    let controller =
+        instance
+            .controller
+            .as_ref()
+            .ok_or_else(|| MxuError::ControllerNotConnected {
+                instance_id: instance_id.clone(),
+            })?;
 
-    controller.post_screencap().map_err(|e| e.to_string())
+    controller
+        .post_screencap()
+        .map_err(|e| MxuError::maa("post_screencap", e))
 }
 
 /// 获取缓存的截图（返回 base64 编码的 PNG 图像）
@@ -753,21 +1381,30 @@ pub fn maa_post_screencap(state: State<Arc<MaaState>>, instance_id: String) -> R
 pub fn maa_get_cached_image(
     state: State<Arc<MaaState>>,
     instance_id: String,
-) -> Result<String, String> {
-    let instances = state.instances.lock().map_err(|e| e.to_string())?;
-    let instance = instances.get(&instance_id).ok_or("Instance not found")?;
-    let controller = instance
-        .controller
-        .as_ref()
-        .ok_or("Controller not connected")?;
+) -> Result<String, MxuError> {
+    let instances = state.instances.lock()?;
+    let instance = instances
+        .get(&instance_id)
+        .ok_or_else(|| MxuError::InstanceNotFound {
+            instance_id: instance_id.clone(),
+        })?;
+    let controller =
+        instance
+            .controller
+            .as_ref()
+            .ok_or_else(|| MxuError::ControllerNotConnected {
+                instance_id: instance_id.clone(),
+            })?;
 
-    let buffer = controller.cached_image().map_err(|e| e.to_string())?;
+    let buffer = controller
+        .cached_image()
+        .map_err(|e| MxuError::maa("cached_image", e))?;
     let data = buffer
         .to_vec()
-        .ok_or("Failed to convert image buffer".to_string())?;
+        .ok_or_else(|| MxuError::maa("cached_image", "Failed to convert image buffer"))?;
 
     if data.is_empty() {
-        return Err("No image data available".to_string());
+        return Err(MxuError::maa("cached_image", "No image data available"));
     }
 
     // 复制数据并转换为 base64
@@ -3,10 +3,20 @@
 //! 提供前端调用的 MaaFramework 功能接口
 //!
 //! 模块结构：
+//! - `error`: 错误类型定义
 //! - `types`: 数据类型定义
 //! - `utils`: 辅助函数
 //! - `maa_core`: Maa 核心命令（初始化、设备搜索、控制器、资源、任务）
 //! - `maa_agent`: Agent 相关命令
+//! - `adb`: ADB 辅助（地址探测、设备稳定标识）
+//! - `controller_probe`: ADB 截图 / 输入方式探测与排序
+//! - `discovery_cache`: 设备 / 窗口搜索结果的持久化缓存
+//! - `emulator`: 模拟器生命周期管理（启动、等待开机、关闭）
+//! - `hot_reload`: 资源热重载
+//! - `node_debug`: 单节点调试（识别/动作）
+//! - `pipeline`: Pipeline override 校验
+//! - `task_plan`: 任务执行计划（失败停止、重试、重复、收尾任务）
+//! - `orchestrator`: 多实例编排（分组顺序执行、组内并行）
 //! - `state`: 状态查询命令
 //! - `file_ops`: 文件操作命令
 //! - `update`: 更新安装相关命令
@@ -14,15 +24,25 @@
 //! - `system`: 系统相关命令
 //! - `tray`: 托盘相关命令
 
+pub mod error;
 pub mod types;
 pub mod utils;
 
+pub mod adb;
+pub mod controller_probe;
+pub mod discovery_cache;
 pub mod download;
+pub mod emulator;
 pub mod file_ops;
+pub mod hot_reload;
 pub mod maa_agent;
 pub mod maa_core;
+pub mod node_debug;
+pub mod orchestrator;
+pub mod pipeline;
 pub mod state;
 pub mod system;
+pub mod task_plan;
 pub mod tray;
 pub mod update;
 
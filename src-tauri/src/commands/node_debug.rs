@@ -0,0 +1,256 @@
+//! 单节点调试
+//!
+//! 对当前帧（控制器缓存截图或指定图片文件）单独执行某个节点的识别，
+//! 或单独执行某个节点的动作，用于"测试此节点"而无需修改任务列表
+
+use log::info;
+use std::sync::Arc;
+use std::time::Instant;
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use serde_json::Value;
+use tauri::State;
+
+use maa_framework::buffer::ImageBuffer;
+use maa_framework::common::Rect;
+use maa_framework::controller::Controller;
+use maa_framework::resource::Resource;
+use maa_framework::tasker::Tasker;
+
+use super::error::MxuError;
+use super::maa_core::create_tasker;
+use super::types::{ActionDebugResult, MaaState, RecognitionDebugResult};
+use super::utils::normalize_path;
+
+/// 获取调试所需的资源、控制器和 tasker（tasker 不存在时创建）
+fn acquire_debug_handles(
+    app: &tauri::AppHandle,
+    state: &MaaState,
+    instance_id: &str,
+) -> Result<(Resource, Controller, Tasker), MxuError> {
+    let mut instances = state.instances.lock()?;
+    let instance = instances
+        .get_mut(instance_id)
+        .ok_or_else(|| MxuError::InstanceNotFound {
+            instance_id: instance_id.to_string(),
+        })?;
+
+    let resource = instance
+        .resource
+        .as_ref()
+        .filter(|r| r.loaded())
+        .ok_or_else(|| MxuError::ResourceNotLoaded {
+            instance_id: instance_id.to_string(),
+        })?;
+    let controller =
+        instance
+            .controller
+            .as_ref()
+            .ok_or_else(|| MxuError::ControllerNotConnected {
+                instance_id: instance_id.to_string(),
+            })?;
+
+    if instance.tasker.is_none() {
+        instance.tasker = Some(create_tasker(
+            app,
+            state,
+            instance_id,
+            resource,
+            controller,
+        )?);
+    }
+    let tasker = instance.tasker.as_ref().unwrap();
+
+    if !tasker.inited() {
+        return Err(MxuError::TaskerNotInitialized {
+            instance_id: instance_id.to_string(),
+        });
+    }
+    if tasker.running() {
+        return Err(MxuError::TaskerBusy {
+            instance_id: instance_id.to_string(),
+        });
+    }
+
+    Ok((resource.clone(), controller.clone(), tasker.clone()))
+}
+
+/// 读取节点合并后的定义，返回指定字段（recognition / action）的类型和参数
+fn node_algorithm(
+    resource: &Resource,
+    node_name: &str,
+    field: &str,
+) -> Result<(String, Value), MxuError> {
+    let raw = resource
+        .get_node_data(node_name)
+        .map_err(|e| MxuError::maa("get_node_data", e))?
+        .ok_or_else(|| {
+            MxuError::invalid_param("node_name", format!("node '{}' not found", node_name))
+        })?;
+    let node: Value = serde_json::from_str(&raw)
+        .map_err(|e| MxuError::maa("get_node_data", format!("invalid node JSON: {}", e)))?;
+
+    match node.get(field) {
+        Some(Value::Object(obj)) => Ok((
+            obj.get("type")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_string(),
+            obj.get("param")
+                .cloned()
+                .unwrap_or(Value::Object(Default::default())),
+        )),
+        Some(Value::String(t)) => Ok((t.clone(), Value::Object(Default::default()))),
+        _ => Err(MxuError::invalid_param(
+            "node_name",
+            format!("node '{}' has no {}", node_name, field),
+        )),
+    }
+}
+
+/// 将图像缓冲区编码为 data URL
+fn image_to_data_url(image: &ImageBuffer) -> Option<String> {
+    let data = image.to_vec()?;
+    if data.is_empty() {
+        return None;
+    }
+    Some(format!("data:image/png;base64,{}", STANDARD.encode(&data)))
+}
+
+fn rect_to_array(rect: &Rect) -> [i32; 4] {
+    [rect.x, rect.y, rect.width, rect.height]
+}
+
+/// 对单个节点执行识别
+/// image_path 为空时使用控制器最近一次截图（cached_image）
+#[tauri::command]
+pub async fn maa_debug_recognition(
+    app: tauri::AppHandle,
+    state: State<'_, Arc<MaaState>>,
+    instance_id: String,
+    node_name: String,
+    image_path: Option<String>,
+) -> Result<RecognitionDebugResult, MxuError> {
+    info!(
+        "maa_debug_recognition called, instance_id: {}, node: {}, image: {:?}",
+        instance_id, node_name, image_path
+    );
+
+    let state_arc = state.inner().clone();
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let (resource, controller, tasker) = acquire_debug_handles(&app, &state_arc, &instance_id)?;
+        let (algorithm, param) = node_algorithm(&resource, &node_name, "recognition")?;
+
+        let image = match image_path.as_deref().filter(|p| !p.is_empty()) {
+            Some(path) => {
+                let path = normalize_path(path);
+                let bytes =
+                    std::fs::read(&path).map_err(|e| MxuError::io("read image", &path, e))?;
+                ImageBuffer::from_encoded(&bytes).map_err(|e| MxuError::maa("decode_image", e))?
+            }
+            None => controller
+                .cached_image()
+                .map_err(|e| MxuError::maa("cached_image", e))?,
+        };
+        if image.is_empty() {
+            return Err(MxuError::maa(
+                "cached_image",
+                "No image data available, take a screenshot first",
+            ));
+        }
+
+        let started = Instant::now();
+        let job = tasker
+            .post_recognition(&algorithm, &param.to_string(), &image)
+            .map_err(|e| MxuError::maa("post_recognition", e))?;
+        tasker.wait(job.id);
+        let elapsed_ms = started.elapsed().as_millis() as u64;
+
+        let detail = tasker
+            .get_recognition_detail(job.id)
+            .map_err(|e| MxuError::maa("get_recognition_detail", e))?
+            .ok_or_else(|| MxuError::maa("get_recognition_detail", "No recognition detail"))?;
+        let detail_json: Value = serde_json::from_str(&detail.detail).unwrap_or(Value::Null);
+        let score = detail_json
+            .get("best")
+            .and_then(|best| best.get("score"))
+            .and_then(|v| v.as_f64());
+
+        info!(
+            "[debug] Recognition {} ({}) hit: {}, box: {:?}, {}ms",
+            node_name, algorithm, detail.hit, detail.box_, elapsed_ms
+        );
+
+        Ok(RecognitionDebugResult {
+            node: node_name,
+            algorithm,
+            hit: detail.hit,
+            hit_box: detail.box_.as_ref().map(rect_to_array),
+            score,
+            detail: detail_json,
+            draws: detail.draws.iter().filter_map(image_to_data_url).collect(),
+            elapsed_ms,
+        })
+    })
+    .await?
+}
+
+/// 对单个节点执行动作
+/// target_box 为动作目标框 [x, y, width, height]，未提供时为全零框（由动作参数自行决定目标）
+#[tauri::command]
+pub async fn maa_debug_action(
+    app: tauri::AppHandle,
+    state: State<'_, Arc<MaaState>>,
+    instance_id: String,
+    node_name: String,
+    target_box: Option<[i32; 4]>,
+) -> Result<ActionDebugResult, MxuError> {
+    info!(
+        "maa_debug_action called, instance_id: {}, node: {}, box: {:?}",
+        instance_id, node_name, target_box
+    );
+
+    let state_arc = state.inner().clone();
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let (resource, _controller, tasker) =
+            acquire_debug_handles(&app, &state_arc, &instance_id)?;
+        let (action, param) = node_algorithm(&resource, &node_name, "action")?;
+
+        let [x, y, width, height] = target_box.unwrap_or_default();
+        let rect = Rect {
+            x,
+            y,
+            width,
+            height,
+        };
+
+        let started = Instant::now();
+        let job = tasker
+            .post_action(&action, &param.to_string(), &rect, "{}")
+            .map_err(|e| MxuError::maa("post_action", e))?;
+        tasker.wait(job.id);
+        let elapsed_ms = started.elapsed().as_millis() as u64;
+
+        let detail = tasker
+            .get_action_detail(job.id)
+            .map_err(|e| MxuError::maa("get_action_detail", e))?
+            .ok_or_else(|| MxuError::maa("get_action_detail", "No action detail"))?;
+
+        info!(
+            "[debug] Action {} ({}) success: {}, {}ms",
+            node_name, action, detail.success, elapsed_ms
+        );
+
+        Ok(ActionDebugResult {
+            node: node_name,
+            action,
+            success: detail.success,
+            target_box: rect_to_array(&detail.box_),
+            detail: serde_json::from_str(&detail.detail).unwrap_or(Value::Null),
+            elapsed_ms,
+        })
+    })
+    .await?
+}
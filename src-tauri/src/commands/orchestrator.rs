@@ -0,0 +1,446 @@
+//! 多实例编排
+//!
+//! 按分组顺序运行多个实例的任务列表：组内实例并行执行，整组结束后才启动下一组。
+//! 每个实例的任务列表通过执行计划（task_plan）运行，进度与结果合并为编排事件发送
+
+use log::{info, warn};
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::Instant;
+
+use futures_util::future::join_all;
+use tauri::{AppHandle, Emitter, State};
+
+use maa_framework::tasker::Tasker;
+
+use super::error::MxuError;
+use super::maa_agent::{acquire_task_handles, start_agents, stop_agents};
+use super::pipeline::ensure_valid_override;
+use super::task_plan;
+use super::types::{
+    MaaState, OrchestrationEntry, OrchestrationFinishedEvent, OrchestrationInstanceResult,
+    OrchestrationPlan, OrchestrationProgressEvent, TaskStatus,
+};
+
+/// 编排 ID 计数器
+static NEXT_RUN_ID: AtomicU64 = AtomicU64::new(1);
+
+/// 正在运行的编排：run_id -> 句柄
+static ORCHESTRATIONS: LazyLock<Mutex<HashMap<String, Arc<OrchestrationHandle>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// 正在运行的编排句柄，用于取消
+struct OrchestrationHandle {
+    cancelled: AtomicBool,
+    /// 计划中涉及的所有实例
+    instance_ids: HashSet<String>,
+}
+
+/// 单次编排的运行上下文
+struct Orchestration {
+    app: AppHandle,
+    state: Arc<MaaState>,
+    run_id: String,
+    handle: Arc<OrchestrationHandle>,
+    total: usize,
+    completed: AtomicUsize,
+}
+
+impl Orchestration {
+    fn cancelled(&self) -> bool {
+        self.handle.cancelled.load(Ordering::SeqCst)
+    }
+
+    fn emit_progress(
+        &self,
+        group_index: usize,
+        instance_id: &str,
+        status: TaskStatus,
+        error: Option<String>,
+    ) {
+        let event = OrchestrationProgressEvent {
+            run_id: self.run_id.clone(),
+            group_index,
+            instance_id: instance_id.to_string(),
+            status,
+            completed: self.completed.load(Ordering::SeqCst),
+            total: self.total,
+            error,
+        };
+        if let Err(e) = self.app.emit("maa-orchestration-progress", event) {
+            warn!(
+                "[orchestrator] Failed to emit maa-orchestration-progress: {}",
+                e
+            );
+        }
+    }
+
+    /// 记录实例结束并发送进度
+    fn finish_instance(&self, result: &OrchestrationInstanceResult) {
+        self.completed.fetch_add(1, Ordering::SeqCst);
+        self.emit_progress(
+            result.group_index,
+            &result.instance_id,
+            result.status.clone(),
+            result.error.clone(),
+        );
+    }
+
+    fn skipped(
+        &self,
+        group_index: usize,
+        entry: &OrchestrationEntry,
+    ) -> OrchestrationInstanceResult {
+        let result = OrchestrationInstanceResult {
+            group_index,
+            instance_id: entry.instance_id.clone(),
+            status: TaskStatus::Skipped,
+            error: None,
+            results: Vec::new(),
+            elapsed_ms: 0,
+        };
+        self.finish_instance(&result);
+        result
+    }
+
+    /// 准备实例：接管控制器、获取 tasker、校验任务并启动 Agent
+    async fn prepare(&self, entry: &OrchestrationEntry) -> Result<Tasker, MxuError> {
+        if let Some(source_id) = &entry.controller_from {
+            take_over_controller(&self.state, &entry.instance_id, source_id)?;
+        }
+
+        let (resource, controller, tasker) =
+            acquire_task_handles(&self.app, &self.state, &entry.instance_id)?;
+        if tasker.running() {
+            return Err(MxuError::TaskerBusy {
+                instance_id: entry.instance_id.clone(),
+            });
+        }
+        for task in &entry.tasks {
+            ensure_valid_override(&resource, Some(&task.entry), &task.pipeline_override)?;
+        }
+
+        start_agents(
+            &self.app,
+            &self.state,
+            &entry.instance_id,
+            entry.agent_configs.clone(),
+            &entry.cwd,
+            entry.tcp_compat_mode,
+            &resource,
+            &controller,
+            &tasker,
+        )
+        .await?;
+
+        let mut instances = self.state.instances.lock()?;
+        if let Some(instance) = instances.get_mut(&entry.instance_id) {
+            instance.task_ids.clear();
+        }
+        Ok(tasker)
+    }
+
+    /// 运行单个实例的任务列表直到结束
+    async fn run_entry(
+        &self,
+        group_index: usize,
+        entry: &OrchestrationEntry,
+    ) -> OrchestrationInstanceResult {
+        if self.cancelled() {
+            return self.skipped(group_index, entry);
+        }
+
+        let started = Instant::now();
+        let mut result = OrchestrationInstanceResult {
+            group_index,
+            instance_id: entry.instance_id.clone(),
+            status: TaskStatus::Failed,
+            error: None,
+            results: Vec::new(),
+            elapsed_ms: 0,
+        };
+
+        match self.prepare(entry).await {
+            // 准备期间被取消：不再提交任务
+            Ok(_) if self.cancelled() => {
+                let _ = stop_agents(&self.state, &entry.instance_id);
+                result.status = TaskStatus::Skipped;
+            }
+            Ok(tasker) => {
+                info!(
+                    "[orchestrator] {} starting {} with {} task(s)",
+                    self.run_id,
+                    entry.instance_id,
+                    entry.tasks.len()
+                );
+                self.emit_progress(group_index, &entry.instance_id, TaskStatus::Running, None);
+
+                let app = self.app.clone();
+                let state = self.state.clone();
+                let instance_id = entry.instance_id.clone();
+                let tasks = entry.tasks.clone();
+                let cancel = task_plan::plan_cancel_flag(&state, &instance_id);
+                // 检查取消后到此处之间发生的取消同样交给计划执行器处理
+                if self.cancelled() {
+                    cancel.store(true, Ordering::SeqCst);
+                }
+                let plan = tauri::async_runtime::spawn_blocking(move || {
+                    task_plan::run_plan(app, state, instance_id, tasker, tasks, cancel)
+                })
+                .await;
+
+                if let Err(e) = stop_agents(&self.state, &entry.instance_id) {
+                    warn!(
+                        "[orchestrator] Failed to stop agents of {}: {}",
+                        entry.instance_id, e
+                    );
+                }
+
+                match plan {
+                    Ok(plan) => {
+                        if plan.success {
+                            result.status = TaskStatus::Succeeded;
+                        }
+                        result.results = plan.results;
+                    }
+                    Err(e) => result.error = Some(MxuError::from(e).to_string()),
+                }
+            }
+            Err(e) => {
+                warn!(
+                    "[orchestrator] Failed to start {}: {}",
+                    entry.instance_id, e
+                );
+                result.error = Some(e.to_string());
+            }
+        }
+
+        result.elapsed_ms = started.elapsed().as_millis() as u64;
+        self.finish_instance(&result);
+        result
+    }
+
+    /// 按分组依次执行整个编排
+    async fn run(self, plan: OrchestrationPlan) {
+        let started = Instant::now();
+        let mut results = Vec::with_capacity(self.total);
+        let mut failed = false;
+
+        for (group_index, group) in plan.groups.iter().enumerate() {
+            if self.cancelled() || (failed && plan.stop_on_failure) {
+                for entry in &group.instances {
+                    results.push(self.skipped(group_index, entry));
+                }
+                continue;
+            }
+
+            info!(
+                "[orchestrator] {} running group {} ({} instance(s))",
+                self.run_id,
+                group_index,
+                group.instances.len()
+            );
+            let group_results = join_all(
+                group
+                    .instances
+                    .iter()
+                    .map(|entry| self.run_entry(group_index, entry)),
+            )
+            .await;
+
+            failed |= group_results
+                .iter()
+                .any(|r| !matches!(r.status, TaskStatus::Succeeded));
+            results.extend(group_results);
+        }
+
+        let cancelled = self.cancelled();
+        let success = !cancelled
+            && results
+                .iter()
+                .all(|r| matches!(r.status, TaskStatus::Succeeded));
+        info!(
+            "[orchestrator] {} finished, success: {}, cancelled: {}",
+            self.run_id, success, cancelled
+        );
+
+        if let Ok(mut running) = ORCHESTRATIONS.lock() {
+            running.remove(&self.run_id);
+        }
+
+        let event = OrchestrationFinishedEvent {
+            run_id: self.run_id.clone(),
+            success,
+            cancelled,
+            results,
+            elapsed_ms: started.elapsed().as_millis() as u64,
+        };
+        if let Err(e) = self.app.emit("maa-orchestration-finished", event) {
+            warn!(
+                "[orchestrator] Failed to emit maa-orchestration-finished: {}",
+                e
+            );
+        }
+    }
+}
+
+/// 让实例使用另一个实例已连接的控制器
+/// 已有的 tasker 绑定的是旧控制器，丢弃后由 acquire_task_handles 重新创建
+fn take_over_controller(
+    state: &MaaState,
+    instance_id: &str,
+    source_id: &str,
+) -> Result<(), MxuError> {
+    let mut instances = state.instances.lock()?;
+    let controller = instances
+        .get(source_id)
+        .ok_or_else(|| MxuError::InstanceNotFound {
+            instance_id: source_id.to_string(),
+        })?
+        .controller
+        .clone()
+        .ok_or_else(|| MxuError::ControllerNotConnected {
+            instance_id: source_id.to_string(),
+        })?;
+
+    let instance = instances
+        .get_mut(instance_id)
+        .ok_or_else(|| MxuError::InstanceNotFound {
+            instance_id: instance_id.to_string(),
+        })?;
+    if instance.tasker.as_ref().is_some_and(|t| t.running()) {
+        return Err(MxuError::TaskerBusy {
+            instance_id: instance_id.to_string(),
+        });
+    }
+    info!(
+        "[orchestrator] {} takes over controller of {}",
+        instance_id, source_id
+    );
+    instance.controller = Some(controller);
+    instance.tasker = None;
+    state.unregister_tasker(instance_id);
+    Ok(())
+}
+
+/// 校验编排计划，返回涉及的所有实例
+fn validate_plan(plan: &OrchestrationPlan) -> Result<HashSet<String>, MxuError> {
+    if plan.groups.iter().all(|g| g.instances.is_empty()) {
+        return Err(MxuError::invalid_param("plan", "plan has no instances"));
+    }
+
+    let mut all_ids = HashSet::new();
+    for (group_index, group) in plan.groups.iter().enumerate() {
+        let mut group_ids = HashSet::new();
+        for entry in &group.instances {
+            if !group_ids.insert(entry.instance_id.as_str()) {
+                return Err(MxuError::invalid_param(
+                    "plan",
+                    format!(
+                        "instance {} appears twice in group {}",
+                        entry.instance_id, group_index
+                    ),
+                ));
+            }
+        }
+        // 并行的实例不能共用同一个控制器
+        for entry in &group.instances {
+            if let Some(source_id) = &entry.controller_from {
+                if source_id != &entry.instance_id && group_ids.contains(source_id.as_str()) {
+                    return Err(MxuError::invalid_param(
+                        "plan",
+                        format!(
+                            "instance {} cannot take over the controller of {} in the same group",
+                            entry.instance_id, source_id
+                        ),
+                    ));
+                }
+            }
+        }
+        all_ids.extend(group_ids.into_iter().map(str::to_string));
+    }
+    Ok(all_ids)
+}
+
+/// 启动多实例编排，立即返回 run_id
+/// 进度与结果通过 maa-orchestration-progress / maa-orchestration-finished 事件通知
+#[tauri::command]
+pub fn maa_run_orchestration(
+    app: AppHandle,
+    state: State<'_, Arc<MaaState>>,
+    plan: OrchestrationPlan,
+) -> Result<String, MxuError> {
+    let instance_ids = validate_plan(&plan)?;
+
+    let run_id = format!("orch-{}", NEXT_RUN_ID.fetch_add(1, Ordering::SeqCst));
+    let handle = Arc::new(OrchestrationHandle {
+        cancelled: AtomicBool::new(false),
+        instance_ids,
+    });
+    {
+        let mut running = ORCHESTRATIONS.lock()?;
+        // 同一实例不能同时属于两个编排
+        if let Some(busy) = running
+            .values()
+            .flat_map(|h| h.instance_ids.intersection(&handle.instance_ids))
+            .next()
+        {
+            return Err(MxuError::TaskerBusy {
+                instance_id: busy.clone(),
+            });
+        }
+        running.insert(run_id.clone(), handle.clone());
+    }
+
+    let total = plan.groups.iter().map(|g| g.instances.len()).sum();
+    info!(
+        "[orchestrator] Starting {}: {} group(s), {} instance(s)",
+        run_id,
+        plan.groups.len(),
+        total
+    );
+    let orchestration = Orchestration {
+        app,
+        state: state.inner().clone(),
+        run_id: run_id.clone(),
+        handle,
+        total,
+        completed: AtomicUsize::new(0),
+    };
+    tauri::async_runtime::spawn(orchestration.run(plan));
+    Ok(run_id)
+}
+
+/// 取消编排：停止正在运行的实例并跳过尚未开始的实例
+/// 编排存在时返回 true
+#[tauri::command]
+pub fn maa_cancel_orchestration(
+    state: State<'_, Arc<MaaState>>,
+    run_id: String,
+) -> Result<bool, MxuError> {
+    let Some(handle) = ORCHESTRATIONS.lock()?.get(&run_id).cloned() else {
+        return Ok(false);
+    };
+    info!("[orchestrator] Cancelling {}", run_id);
+    handle.cancelled.store(true, Ordering::SeqCst);
+
+    let instances = state.instances.lock()?;
+    for instance_id in &handle.instance_ids {
+        let Some(instance) = instances.get(instance_id) else {
+            continue;
+        };
+        // 计划执行器在任务间隙同样会看到取消，不再提交后续任务
+        instance.plan_cancel.store(true, Ordering::SeqCst);
+        let Some(tasker) = instance.tasker.as_ref() else {
+            continue;
+        };
+        if tasker.running() {
+            if let Err(e) = tasker.post_stop() {
+                warn!("[orchestrator] post_stop failed for {}: {}", instance_id, e);
+            }
+            crate::mxu_actions::kill_launched_processes(instance_id);
+        }
+    }
+    Ok(true)
+}
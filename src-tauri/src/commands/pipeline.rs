@@ -0,0 +1,531 @@
+//! Pipeline override 校验
+//!
+//! 在提交任务前解析 pipeline_override，检查节点引用和字段名，
+//! 避免选项拼写错误只能在任务失败后通过回调发现
+
+use log::{debug, warn};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use serde_json::{Map, Value};
+use tauri::State;
+
+use maa_framework::resource::Resource;
+
+use super::error::MxuError;
+use super::types::{DiagnosticSeverity, MaaState, PipelineDiagnostic, PipelineValidationReport};
+
+/// 节点通用字段
+const NODE_FIELDS: &[&str] = &[
+    "recognition",
+    "action",
+    "next",
+    "on_error",
+    "interrupt",
+    "is_sub",
+    "anchor",
+    "inverse",
+    "enabled",
+    "max_hit",
+    "rate_limit",
+    "timeout",
+    "pre_delay",
+    "post_delay",
+    "pre_wait_freezes",
+    "post_wait_freezes",
+    "repeat",
+    "repeat_delay",
+    "repeat_wait_freezes",
+    "focus",
+    "attach",
+    "doc",
+];
+
+/// 识别算法参数字段（v1 平铺写法或 v2 `recognition.param` 内）
+const RECOGNITION_FIELDS: &[&str] = &[
+    "roi",
+    "roi_offset",
+    "template",
+    "threshold",
+    "order_by",
+    "index",
+    "method",
+    "green_mask",
+    "count",
+    "detector",
+    "ratio",
+    "lower",
+    "upper",
+    "connected",
+    "expected",
+    "replace",
+    "only_rec",
+    "model",
+    "labels",
+    "color_filter",
+    "all_of",
+    "any_of",
+    "box_index",
+    "sub_name",
+    "custom_recognition",
+    "custom_recognition_param",
+];
+
+/// 动作参数字段（v1 平铺写法或 v2 `action.param` 内）
+const ACTION_FIELDS: &[&str] = &[
+    "target",
+    "target_offset",
+    "begin",
+    "begin_offset",
+    "end",
+    "end_offset",
+    "duration",
+    "end_hold",
+    "only_hover",
+    "swipes",
+    "contact",
+    "pressure",
+    "dx",
+    "dy",
+    "key",
+    "input_text",
+    "package",
+    "exec",
+    "args",
+    "detach",
+    "cmd",
+    "custom_action",
+    "custom_action_param",
+];
+
+/// 识别算法类型
+const RECOGNITION_TYPES: &[&str] = &[
+    "DirectHit",
+    "TemplateMatch",
+    "FeatureMatch",
+    "ColorMatch",
+    "OCR",
+    "NeuralNetworkClassify",
+    "NeuralNetworkDetect",
+    "And",
+    "Or",
+    "Custom",
+];
+
+/// 动作类型
+const ACTION_TYPES: &[&str] = &[
+    "DoNothing",
+    "Click",
+    "LongPress",
+    "Swipe",
+    "MultiSwipe",
+    "TouchDown",
+    "TouchMove",
+    "TouchUp",
+    "ClickKey",
+    "LongPressKey",
+    "KeyDown",
+    "KeyUp",
+    "InputText",
+    "StartApp",
+    "StopApp",
+    "StopTask",
+    "Scroll",
+    "Command",
+    "Shell",
+    "Custom",
+];
+
+/// 引用其他节点的字段
+const REFERENCE_FIELDS: &[&str] = &["next", "on_error", "interrupt"];
+
+/// 诊断收集器
+struct Diagnostics(Vec<PipelineDiagnostic>);
+
+impl Diagnostics {
+    fn push(
+        &mut self,
+        severity: DiagnosticSeverity,
+        node: &str,
+        field: Option<&str>,
+        message: String,
+    ) {
+        self.0.push(PipelineDiagnostic {
+            severity,
+            node: Some(node.to_string()),
+            field: field.map(|f| f.to_string()),
+            message,
+            line: None,
+            column: None,
+        });
+    }
+
+    fn error(&mut self, node: &str, field: Option<&str>, message: String) {
+        self.push(DiagnosticSeverity::Error, node, field, message);
+    }
+
+    fn warning(&mut self, node: &str, field: Option<&str>, message: String) {
+        self.push(DiagnosticSeverity::Warning, node, field, message);
+    }
+}
+
+/// 获取资源中已加载的节点名集合
+pub fn resource_node_names(resource: &Resource) -> Result<HashSet<String>, MxuError> {
+    let nodes = resource
+        .node_list()
+        .map_err(|e| MxuError::maa("node_list", e))?;
+    Ok(nodes.into_iter().collect())
+}
+
+/// 校验 pipeline_override
+///
+/// - `known_nodes`: 资源中已存在的节点名
+/// - `entry`: 任务入口（提供时检查入口节点是否存在）
+pub fn validate_pipeline_override(
+    known_nodes: &HashSet<String>,
+    pipeline_override: &str,
+    entry: Option<&str>,
+) -> PipelineValidationReport {
+    let mut diags = Diagnostics(Vec::new());
+
+    let trimmed = pipeline_override.trim();
+    let value = if trimmed.is_empty() {
+        Value::Object(Map::new())
+    } else {
+        match serde_json::from_str::<Value>(trimmed) {
+            Ok(v) => v,
+            Err(e) => {
+                diags.0.push(PipelineDiagnostic {
+                    severity: DiagnosticSeverity::Error,
+                    node: None,
+                    field: None,
+                    message: format!("Invalid JSON: {}", e),
+                    line: Some(e.line()),
+                    column: Some(e.column()),
+                });
+                return finish(diags);
+            }
+        }
+    };
+
+    // MaaFramework 支持对象或对象数组（按顺序依次覆盖）
+    let layers: Vec<&Map<String, Value>> = match &value {
+        Value::Object(obj) => vec![obj],
+        Value::Array(items) => {
+            let mut layers = Vec::new();
+            for (i, item) in items.iter().enumerate() {
+                match item {
+                    Value::Object(obj) => layers.push(obj),
+                    _ => diags.0.push(PipelineDiagnostic {
+                        severity: DiagnosticSeverity::Error,
+                        node: None,
+                        field: None,
+                        message: format!("Override array item #{} is not an object", i),
+                        line: None,
+                        column: None,
+                    }),
+                }
+            }
+            layers
+        }
+        _ => {
+            diags.0.push(PipelineDiagnostic {
+                severity: DiagnosticSeverity::Error,
+                node: None,
+                field: None,
+                message: "Pipeline override must be an object or an array of objects".to_string(),
+                line: None,
+                column: None,
+            });
+            return finish(diags);
+        }
+    };
+
+    // override 中定义的节点也可以被引用
+    let override_nodes: HashSet<&str> = layers
+        .iter()
+        .flat_map(|layer| layer.keys().map(|k| k.as_str()))
+        .collect();
+    let node_exists = |name: &str| known_nodes.contains(name) || override_nodes.contains(name);
+
+    if let Some(entry) = entry {
+        if !node_exists(entry) {
+            diags.error(
+                entry,
+                None,
+                format!("Entry node '{}' not found in resource or override", entry),
+            );
+        }
+    }
+
+    for layer in &layers {
+        for (node_name, node) in layer.iter() {
+            let Value::Object(fields) = node else {
+                diags.error(
+                    node_name,
+                    None,
+                    "Node definition must be an object".to_string(),
+                );
+                continue;
+            };
+
+            if !known_nodes.contains(node_name) {
+                debug!(
+                    "[pipeline] Override defines node not in resource: {}",
+                    node_name
+                );
+            }
+
+            for (field, field_value) in fields.iter() {
+                let field = field.as_str();
+
+                if REFERENCE_FIELDS.contains(&field) {
+                    for target in referenced_nodes(field_value) {
+                        if !node_exists(&target) {
+                            diags.error(
+                                node_name,
+                                Some(field),
+                                format!("Referenced node '{}' not found", target),
+                            );
+                        }
+                    }
+                    if !matches!(
+                        field_value,
+                        Value::String(_) | Value::Array(_) | Value::Object(_)
+                    ) {
+                        diags.error(
+                            node_name,
+                            Some(field),
+                            "Expected a node name or a list of node names".to_string(),
+                        );
+                    }
+                    continue;
+                }
+
+                match field {
+                    "recognition" => check_typed_field(
+                        &mut diags,
+                        node_name,
+                        field,
+                        field_value,
+                        RECOGNITION_TYPES,
+                        RECOGNITION_FIELDS,
+                    ),
+                    "action" => check_typed_field(
+                        &mut diags,
+                        node_name,
+                        field,
+                        field_value,
+                        ACTION_TYPES,
+                        ACTION_FIELDS,
+                    ),
+                    _ => {
+                        if !NODE_FIELDS.contains(&field)
+                            && !RECOGNITION_FIELDS.contains(&field)
+                            && !ACTION_FIELDS.contains(&field)
+                        {
+                            diags.warning(
+                                node_name,
+                                Some(field),
+                                unknown_field_message(
+                                    field,
+                                    NODE_FIELDS
+                                        .iter()
+                                        .chain(RECOGNITION_FIELDS)
+                                        .chain(ACTION_FIELDS),
+                                ),
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    finish(diags)
+}
+
+/// 提交任务前校验 pipeline_override，存在 Error 级别诊断时返回错误
+pub fn ensure_valid_override(
+    resource: &Resource,
+    entry: Option<&str>,
+    pipeline_override: &str,
+) -> Result<(), MxuError> {
+    let known_nodes = resource_node_names(resource)?;
+    let report = validate_pipeline_override(&known_nodes, pipeline_override, entry);
+
+    for d in &report.diagnostics {
+        if d.severity == DiagnosticSeverity::Warning {
+            warn!(
+                "[pipeline] {}.{}: {}",
+                d.node.as_deref().unwrap_or("-"),
+                d.field.as_deref().unwrap_or("-"),
+                d.message
+            );
+        }
+    }
+
+    if report.valid {
+        Ok(())
+    } else {
+        Err(MxuError::InvalidPipelineOverride {
+            entry: entry.unwrap_or_default().to_string(),
+            diagnostics: report
+                .diagnostics
+                .into_iter()
+                .filter(|d| d.severity == DiagnosticSeverity::Error)
+                .collect(),
+        })
+    }
+}
+
+fn finish(diags: Diagnostics) -> PipelineValidationReport {
+    let valid = !diags
+        .0
+        .iter()
+        .any(|d| d.severity == DiagnosticSeverity::Error);
+    PipelineValidationReport {
+        valid,
+        diagnostics: diags.0,
+    }
+}
+
+/// 解析 next / on_error 中引用的节点名（忽略 Anchor 引用）
+fn referenced_nodes(value: &Value) -> Vec<String> {
+    fn one(item: &Value, out: &mut Vec<String>) {
+        match item {
+            Value::String(s) => {
+                if s.starts_with("[Anchor]") {
+                    return;
+                }
+                let name = s.strip_prefix("[JumpBack]").unwrap_or(s);
+                out.push(name.to_string());
+            }
+            Value::Object(obj) => {
+                if obj.get("anchor").and_then(|v| v.as_bool()) == Some(true) {
+                    return;
+                }
+                if let Some(name) = obj.get("name").and_then(|v| v.as_str()) {
+                    out.push(name.to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut out = Vec::new();
+    match value {
+        Value::Array(items) => items.iter().for_each(|item| one(item, &mut out)),
+        other => one(other, &mut out),
+    }
+    out
+}
+
+/// 校验 recognition / action 字段
+/// 支持 v1 写法（字符串类型名）和 v2 写法（`{ "type": ..., "param": {...} }`）
+fn check_typed_field(
+    diags: &mut Diagnostics,
+    node: &str,
+    field: &str,
+    value: &Value,
+    types: &[&str],
+    params: &[&str],
+) {
+    let (type_name, param) = match value {
+        Value::String(s) => (Some(s.as_str()), None),
+        Value::Object(obj) => (obj.get("type").and_then(|v| v.as_str()), obj.get("param")),
+        _ => {
+            diags.error(
+                node,
+                Some(field),
+                "Expected a type name or an object with 'type'".to_string(),
+            );
+            return;
+        }
+    };
+
+    // 类型表只是当前已知类型的快照：旧别名或新版 MaaFramework 增加的类型不应阻止任务提交
+    if let Some(t) = type_name {
+        if !types.contains(&t) {
+            diags.warning(node, Some(field), format!("Unknown {} type '{}'", field, t));
+        }
+    }
+
+    if let Some(Value::Object(param)) = param {
+        for key in param.keys() {
+            if !params.contains(&key.as_str()) {
+                diags.warning(node, Some(field), unknown_field_message(key, params.iter()));
+            }
+        }
+    }
+}
+
+/// 生成未知字段提示，附带最接近的已知字段名
+fn unknown_field_message<'a>(field: &str, candidates: impl Iterator<Item = &'a &'a str>) -> String {
+    let suggestion = candidates
+        .map(|c| (edit_distance(field, c), *c))
+        .filter(|(d, _)| *d <= 2)
+        .min_by_key(|(d, _)| *d)
+        .map(|(_, c)| c);
+
+    match suggestion {
+        Some(c) => format!("Unknown field '{}', did you mean '{}'?", field, c),
+        None => format!("Unknown field '{}'", field),
+    }
+}
+
+/// Levenshtein 编辑距离
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b_chars: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b_chars.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut cur = vec![i + 1; b_chars.len() + 1];
+        for (j, cb) in b_chars.iter().enumerate() {
+            let cost = if ca == *cb { 0 } else { 1 };
+            cur[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(cur[j] + 1);
+        }
+        prev = cur;
+    }
+
+    prev[b_chars.len()]
+}
+
+// ============================================================================
+// Tauri 命令
+// ============================================================================
+
+/// 校验 pipeline_override（不提交任务）
+/// entry 可选，提供时同时检查入口节点是否存在
+#[tauri::command]
+pub fn maa_validate_pipeline_override(
+    state: State<Arc<MaaState>>,
+    instance_id: String,
+    pipeline_override: String,
+    entry: Option<String>,
+) -> Result<PipelineValidationReport, MxuError> {
+    debug!(
+        "maa_validate_pipeline_override called, instance_id: {}, entry: {:?}",
+        instance_id, entry
+    );
+
+    let instances = state.instances.lock()?;
+    let instance = instances
+        .get(&instance_id)
+        .ok_or_else(|| MxuError::InstanceNotFound {
+            instance_id: instance_id.clone(),
+        })?;
+    let resource = instance
+        .resource
+        .as_ref()
+        .ok_or_else(|| MxuError::ResourceNotLoaded {
+            instance_id: instance_id.clone(),
+        })?;
+
+    let known_nodes = resource_node_names(resource)?;
+    Ok(validate_pipeline_override(
+        &known_nodes,
+        &pipeline_override,
+        entry.as_deref(),
+    ))
+}
@@ -8,6 +8,7 @@ use std::sync::Arc;
 
 use tauri::State;
 
+use super::error::MxuError;
 use super::types::{AdbDevice, AllInstanceStates, InstanceState, MaaState, Win32Window};
 
 /// 获取单个实例的运行时状态
@@ -15,16 +16,18 @@ use super::types::{AdbDevice, AllInstanceStates, InstanceState, MaaState, Win32W
 pub fn maa_get_instance_state(
     state: State<Arc<MaaState>>,
     instance_id: String,
-) -> Result<InstanceState, String> {
+) -> Result<InstanceState, MxuError> {
     debug!(
         "maa_get_instance_state called, instance_id: {}",
         instance_id
     );
 
-    let mut instances = state.instances.lock().map_err(|e| e.to_string())?;
+    let mut instances = state.instances.lock()?;
     let instance = instances
         .get_mut(&instance_id)
-        .ok_or("Instance not found")?;
+        .ok_or_else(|| MxuError::InstanceNotFound {
+            instance_id: instance_id.clone(),
+        })?;
 
     // 通过 Maa API 查询真实状态
     let is_running = instance.tasker.as_ref().is_some_and(|t| t.running());
@@ -45,15 +48,12 @@ pub fn maa_get_instance_state(
 
 /// 获取所有实例的状态快照（用于前端启动时恢复状态）
 #[tauri::command]
-pub fn maa_get_all_states(state: State<Arc<MaaState>>) -> Result<AllInstanceStates, String> {
+pub fn maa_get_all_states(state: State<Arc<MaaState>>) -> Result<AllInstanceStates, MxuError> {
     debug!("maa_get_all_states called");
 
-    let mut instances = state.instances.lock().map_err(|e| e.to_string())?;
-    let cached_adb = state.cached_adb_devices.lock().map_err(|e| e.to_string())?;
-    let cached_win32 = state
-        .cached_win32_windows
-        .lock()
-        .map_err(|e| e.to_string())?;
+    let mut instances = state.instances.lock()?;
+    let cached_adb = state.cached_adb_devices.lock()?;
+    let cached_win32 = state.cached_win32_windows.lock()?;
 
     let mut instance_states = HashMap::new();
 
@@ -82,14 +82,16 @@ pub fn maa_get_all_states(state: State<Arc<MaaState>>) -> Result<AllInstanceStat
         instances: instance_states,
         cached_adb_devices: cached_adb.clone(),
         cached_win32_windows: cached_win32.clone(),
+        cached_adb_updated_at: *state.cached_adb_updated_at.lock()?,
+        cached_win32_updated_at: *state.cached_win32_updated_at.lock()?,
     })
 }
 
 /// 获取缓存的 ADB 设备列表
 #[tauri::command]
-pub fn maa_get_cached_adb_devices(state: State<Arc<MaaState>>) -> Result<Vec<AdbDevice>, String> {
+pub fn maa_get_cached_adb_devices(state: State<Arc<MaaState>>) -> Result<Vec<AdbDevice>, MxuError> {
     debug!("maa_get_cached_adb_devices called");
-    let cached = state.cached_adb_devices.lock().map_err(|e| e.to_string())?;
+    let cached = state.cached_adb_devices.lock()?;
     Ok(cached.clone())
 }
 
@@ -97,11 +99,8 @@ pub fn maa_get_cached_adb_devices(state: State<Arc<MaaState>>) -> Result<Vec<Adb
 #[tauri::command]
 pub fn maa_get_cached_win32_windows(
     state: State<Arc<MaaState>>,
-) -> Result<Vec<Win32Window>, String> {
+) -> Result<Vec<Win32Window>, MxuError> {
     debug!("maa_get_cached_win32_windows called");
-    let cached = state
-        .cached_win32_windows
-        .lock()
-        .map_err(|e| e.to_string())?;
+    let cached = state.cached_win32_windows.lock()?;
     Ok(cached.clone())
 }
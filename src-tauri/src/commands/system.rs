@@ -5,7 +5,8 @@
 use log::info;
 use std::sync::atomic::{AtomicBool, Ordering};
 
-use super::types::SystemInfo;
+use super::error::MxuError;
+use super::types::{PluginActionInfo, SystemInfo};
 use super::utils::get_maafw_dir;
 
 /// 标记是否检测到可能缺少 VC++ 运行库
@@ -65,7 +66,7 @@ pub fn is_elevated() -> bool {
 
 /// 以管理员权限重启应用
 #[tauri::command]
-pub fn restart_as_admin(app_handle: tauri::AppHandle) -> Result<(), String> {
+pub fn restart_as_admin(app_handle: tauri::AppHandle) -> Result<(), MxuError> {
     #[cfg(windows)]
     {
         use std::ffi::OsStr;
@@ -75,7 +76,7 @@ pub fn restart_as_admin(app_handle: tauri::AppHandle) -> Result<(), String> {
         use windows::Win32::UI::Shell::ShellExecuteW;
         use windows::Win32::UI::WindowsAndMessaging::SW_SHOWNORMAL;
 
-        let exe_path = std::env::current_exe().map_err(|e| format!("获取程序路径失败: {}", e))?;
+        let exe_path = std::env::current_exe().map_err(|e| MxuError::io("get exe path", "", e))?;
 
         let exe_path_str = exe_path.to_string_lossy().to_string();
 
@@ -106,10 +107,10 @@ pub fn restart_as_admin(app_handle: tauri::AppHandle) -> Result<(), String> {
                 app_handle.exit(0);
                 Ok(())
             } else {
-                Err(format!(
-                    "以管理员身份启动失败: 错误码 {}",
+                Err(MxuError::internal(format!(
+                    "ShellExecuteW runas failed with code {}",
                     result.0 as usize
-                ))
+                )))
             }
         }
     }
@@ -117,24 +118,39 @@ pub fn restart_as_admin(app_handle: tauri::AppHandle) -> Result<(), String> {
     #[cfg(not(windows))]
     {
         let _ = app_handle;
-        Err("此功能仅在 Windows 上可用".to_string())
+        Err(MxuError::Unsupported {
+            feature: "restart_as_admin".to_string(),
+        })
     }
 }
 
 /// 设置全局选项 - 保存调试图像
 #[tauri::command]
-pub fn maa_set_save_draw(enabled: bool) -> Result<bool, String> {
+pub fn maa_set_save_draw(enabled: bool) -> Result<bool, MxuError> {
     maa_framework::set_save_draw(enabled)
         .map(|_| {
             info!("保存调试图像: {}", if enabled { "启用" } else { "禁用" });
             true
         })
-        .map_err(|e| format!("设置保存调试图像失败: {}", e))
+        .map_err(|e| MxuError::maa("set_save_draw", e))
+}
+
+/// 取消正在进行的 MXU_POWER 倒计时
+/// 存在倒计时时返回 true
+#[tauri::command]
+pub fn cancel_power_countdown() -> bool {
+    crate::mxu_actions::cancel_power_countdown()
+}
+
+/// 列出 plugins 目录中插件提供的 custom actions
+#[tauri::command]
+pub fn list_plugin_actions() -> Vec<PluginActionInfo> {
+    crate::mxu_plugins::list_plugin_actions()
 }
 
 /// 打开文件（使用系统默认程序）
 #[tauri::command]
-pub async fn open_file(file_path: String) -> Result<(), String> {
+pub async fn open_file(file_path: String) -> Result<(), MxuError> {
     info!("open_file: {}", file_path);
 
     #[cfg(windows)]
@@ -144,7 +160,7 @@ pub async fn open_file(file_path: String) -> Result<(), String> {
         Command::new("cmd")
             .args(["/c", "start", "", &file_path])
             .spawn()
-            .map_err(|e| format!("Failed to open file: {}", e))?;
+            .map_err(|e| MxuError::io("open file", &file_path, e))?;
     }
 
     #[cfg(target_os = "macos")]
@@ -153,7 +169,7 @@ pub async fn open_file(file_path: String) -> Result<(), String> {
         Command::new("open")
             .arg(&file_path)
             .spawn()
-            .map_err(|e| format!("Failed to open file: {}", e))?;
+            .map_err(|e| MxuError::io("open file", &file_path, e))?;
     }
 
     #[cfg(target_os = "linux")]
@@ -162,7 +178,7 @@ pub async fn open_file(file_path: String) -> Result<(), String> {
         Command::new("xdg-open")
             .arg(&file_path)
             .spawn()
-            .map_err(|e| format!("Failed to open file: {}", e))?;
+            .map_err(|e| MxuError::io("open file", &file_path, e))?;
     }
 
     Ok(())
@@ -170,7 +186,7 @@ pub async fn open_file(file_path: String) -> Result<(), String> {
 
 /// 运行程序并等待其退出
 #[tauri::command]
-pub async fn run_and_wait(file_path: String) -> Result<i32, String> {
+pub async fn run_and_wait(file_path: String) -> Result<i32, MxuError> {
     info!("run_and_wait: {}", file_path);
 
     #[cfg(windows)]
@@ -178,7 +194,7 @@ pub async fn run_and_wait(file_path: String) -> Result<i32, String> {
         use std::process::Command;
         let status = Command::new(&file_path)
             .status()
-            .map_err(|e| format!("Failed to run file: {}", e))?;
+            .map_err(|e| MxuError::io("run file", &file_path, e))?;
 
         let exit_code = status.code().unwrap_or(-1);
         info!("run_and_wait finished with exit code: {}", exit_code);
@@ -188,7 +204,9 @@ pub async fn run_and_wait(file_path: String) -> Result<i32, String> {
     #[cfg(not(windows))]
     {
         let _ = file_path;
-        Err("run_and_wait is only supported on Windows".to_string())
+        Err(MxuError::Unsupported {
+            feature: "run_and_wait".to_string(),
+        })
     }
 }
 
@@ -203,7 +221,7 @@ pub async fn run_action(
     args: String,
     cwd: Option<String>,
     wait_for_exit: bool,
-) -> Result<i32, String> {
+) -> Result<i32, MxuError> {
     use std::process::Command;
 
     info!(
@@ -241,7 +259,7 @@ pub async fn run_action(
         // 等待进程退出
         let status = cmd
             .status()
-            .map_err(|e| format!("Failed to run action: {} - {}", program, e))?;
+            .map_err(|e| MxuError::io("run action", &program, e))?;
 
         let exit_code = status.code().unwrap_or(-1);
         info!("run_action finished with exit code: {}", exit_code);
@@ -249,7 +267,7 @@ pub async fn run_action(
     } else {
         // 不等待，启动后立即返回
         cmd.spawn()
-            .map_err(|e| format!("Failed to spawn action: {} - {}", program, e))?;
+            .map_err(|e| MxuError::io("spawn action", &program, e))?;
 
         info!("run_action spawned (not waiting)");
         Ok(0) // 不等待时返回 0
@@ -258,12 +276,14 @@ pub async fn run_action(
 
 /// 重新尝试加载 MaaFramework 库
 #[tauri::command]
-pub async fn retry_load_maa_library() -> Result<String, String> {
+pub async fn retry_load_maa_library() -> Result<String, MxuError> {
     info!("retry_load_maa_library");
 
     let maafw_dir = get_maafw_dir()?;
     if !maafw_dir.exists() {
-        return Err("MaaFramework directory not found".to_string());
+        return Err(MxuError::LibraryNotFound {
+            path: maafw_dir.display().to_string(),
+        });
     }
 
     // Load library
@@ -274,7 +294,10 @@ pub async fn retry_load_maa_library() -> Result<String, String> {
     #[cfg(target_os = "linux")]
     let dll_path = maafw_dir.join("libMaaFramework.so");
 
-    maa_framework::load_library(&dll_path).map_err(|e| e.to_string())?;
+    maa_framework::load_library(&dll_path).map_err(|e| MxuError::LibraryLoadFailed {
+        path: dll_path.display().to_string(),
+        detail: e.to_string(),
+    })?;
 
     let version = maa_framework::maa_version().to_string();
     info!("MaaFramework loaded successfully, version: {}", version);
@@ -0,0 +1,494 @@
+//! 任务执行计划
+//!
+//! 按任务策略逐个提交任务：失败停止、失败重试、前一任务成功时跳过、
+//! 重复执行直到成功或达到次数、单任务超时，以及无论如何都会执行的收尾任务。
+//! 任务完成通过 tasker sink 的 Tasker.Task.Succeeded / Failed 消息得知
+
+use log::{debug, info, warn};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
+
+use tauri::{AppHandle, Emitter};
+
+use maa_framework::tasker::Tasker;
+use maa_framework::MaaStatus;
+
+use super::types::{
+    FailurePolicy, MaaState, PlanFinishedEvent, PlanStepEvent, PlanStepResult, TaskConfig,
+    TaskStatus,
+};
+
+/// 等待任务完成时的检查间隔
+const WAIT_SLICE: Duration = Duration::from_millis(200);
+
+/// tasker 已不在运行但仍未收到完成消息时，改为直接查询任务状态的等待时间
+const SINK_GRACE: Duration = Duration::from_secs(2);
+
+/// 正在运行的计划数量，为 0 时不记录任务结果
+static ACTIVE_PLANS: AtomicUsize = AtomicUsize::new(0);
+
+/// sink 收到的任务结果：task_id -> 是否成功
+static TASK_RESULTS: Mutex<Option<HashMap<i64, bool>>> = Mutex::new(None);
+static TASK_RESULTS_CV: Condvar = Condvar::new();
+
+/// tasker sink 回调入口，记录任务完成结果供计划执行器使用
+pub(super) fn on_tasker_event(message: &str, details: &str) {
+    let succeeded = match message {
+        "Tasker.Task.Succeeded" => true,
+        "Tasker.Task.Failed" => false,
+        _ => return,
+    };
+    if ACTIVE_PLANS.load(Ordering::SeqCst) == 0 {
+        return;
+    }
+    let Some(task_id) = serde_json::from_str::<serde_json::Value>(details)
+        .ok()
+        .and_then(|v| v.get("task_id").and_then(|id| id.as_i64()))
+    else {
+        return;
+    };
+
+    if let Ok(mut results) = TASK_RESULTS.lock() {
+        results
+            .get_or_insert_with(HashMap::new)
+            .insert(task_id, succeeded);
+        TASK_RESULTS_CV.notify_all();
+    }
+}
+
+/// 按 tasker 回调维护运行中的任务数（供 MaaState::other_running_instances 查询）
+pub(super) fn track_running_tasks(running_tasks: &AtomicUsize, message: &str) {
+    match message {
+        "Tasker.Task.Starting" => {
+            running_tasks.fetch_add(1, Ordering::SeqCst);
+        }
+        "Tasker.Task.Succeeded" | "Tasker.Task.Failed" => {
+            let _ = running_tasks.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| {
+                Some(n.saturating_sub(1))
+            });
+        }
+        _ => {}
+    }
+}
+
+/// 任务列表是否用到了执行计划策略（未用到时按原方式一次性提交）
+pub(super) fn needs_plan(tasks: &[TaskConfig]) -> bool {
+    tasks.iter().any(|t| t.has_policy())
+}
+
+/// 计划执行期间的计数守卫，结束时清理残留结果
+struct ActivePlanGuard;
+
+impl ActivePlanGuard {
+    fn new() -> Self {
+        ACTIVE_PLANS.fetch_add(1, Ordering::SeqCst);
+        Self
+    }
+}
+
+impl Drop for ActivePlanGuard {
+    fn drop(&mut self) {
+        if ACTIVE_PLANS.fetch_sub(1, Ordering::SeqCst) == 1 {
+            if let Ok(mut results) = TASK_RESULTS.lock() {
+                *results = None;
+            }
+        }
+    }
+}
+
+/// 单次任务执行结果
+enum RunOutcome {
+    Succeeded(i64),
+    Failed(Option<i64>),
+    /// 超过 timeout_secs 被停止
+    TimedOut(i64),
+    /// 用户停止了任务
+    Stopped(Option<i64>),
+}
+
+/// 等待任务的结果
+enum WaitResult {
+    Succeeded,
+    Failed,
+    TimedOut,
+}
+
+/// 任务计划执行器
+struct PlanRunner {
+    app: AppHandle,
+    state: Arc<MaaState>,
+    instance_id: String,
+    tasker: Tasker,
+    /// 停止请求标志，读取后清除，以便收尾任务执行期间再次停止
+    cancel: Arc<AtomicBool>,
+    /// 用户是否已请求停止
+    stopped: bool,
+    /// 当前停止是否由任务超时触发（不视为用户停止）
+    stopping_for_timeout: bool,
+}
+
+impl PlanRunner {
+    fn emit_step(&self, event: PlanStepEvent) {
+        if let Err(e) = self.app.emit("maa-plan-step", event) {
+            warn!("[task_plan] Failed to emit maa-plan-step: {}", e);
+        }
+    }
+
+    /// 记录新提交的 task_id，用于刷新后恢复状态
+    fn remember_task_id(&self, task_id: i64) {
+        if let Ok(mut instances) = self.state.instances.lock() {
+            if let Some(instance) = instances.get_mut(&self.instance_id) {
+                instance.task_ids.push(task_id);
+            }
+        }
+    }
+
+    /// 检查用户是否已请求停止
+    /// 只认停止标志而非 tasker.stopping()，后者在任务因停止而结束后即恢复，无法区分停止与失败
+    fn check_stopping(&mut self) -> bool {
+        if self.cancel.swap(false, Ordering::SeqCst) && !self.stopped {
+            info!("[task_plan] Stop requested for {}", self.instance_id);
+            self.stopped = true;
+        }
+        self.stopped
+    }
+
+    /// 可被停止打断的等待
+    fn sleep(&mut self, duration: Duration) -> bool {
+        let deadline = Instant::now() + duration;
+        while Instant::now() < deadline {
+            if self.check_stopping() {
+                return false;
+            }
+            std::thread::sleep(WAIT_SLICE.min(deadline.saturating_duration_since(Instant::now())));
+        }
+        true
+    }
+
+    /// 提交任务并等待 sink 报告完成
+    fn run_once(&mut self, task: &TaskConfig) -> RunOutcome {
+        info!(
+            "[task_plan] post_task: entry={}, override={}",
+            task.entry, task.pipeline_override
+        );
+        let task_id = match self.tasker.post_task(&task.entry, &task.pipeline_override) {
+            Ok(job) => job.id,
+            Err(e) => {
+                warn!("[task_plan] Failed to post task {}: {}", task.entry, e);
+                return RunOutcome::Failed(None);
+            }
+        };
+        self.remember_task_id(task_id);
+
+        let timeout = task.timeout_secs.map(Duration::from_secs);
+        let result = self.wait_task(task_id, timeout);
+        if let WaitResult::TimedOut = result {
+            // 等待停止流程结束后再继续下一个任务
+            self.wait_until_idle();
+            self.stopping_for_timeout = false;
+            return RunOutcome::TimedOut(task_id);
+        }
+        // 停止会让当前任务以失败结束，此时不计入失败策略
+        if self.check_stopping() {
+            return RunOutcome::Stopped(Some(task_id));
+        }
+        match result {
+            WaitResult::Succeeded => RunOutcome::Succeeded(task_id),
+            _ => RunOutcome::Failed(Some(task_id)),
+        }
+    }
+
+    /// 等待任务完成；超过 timeout 时调用 post_stop 并在任务结束后返回 TimedOut
+    fn wait_task(&mut self, task_id: i64, timeout: Option<Duration>) -> WaitResult {
+        let started = Instant::now();
+        let mut idle_since: Option<Instant> = None;
+        let finished = |succeeded: bool, timed_out: bool| match (succeeded, timed_out) {
+            (_, true) => WaitResult::TimedOut,
+            (true, false) => WaitResult::Succeeded,
+            (false, false) => WaitResult::Failed,
+        };
+
+        loop {
+            {
+                let Ok(mut results) = TASK_RESULTS.lock() else {
+                    return WaitResult::Failed;
+                };
+                if let Some(succeeded) = results.as_mut().and_then(|r| r.remove(&task_id)) {
+                    return finished(succeeded, self.stopping_for_timeout);
+                }
+                let _ = TASK_RESULTS_CV.wait_timeout(results, WAIT_SLICE);
+            }
+            self.check_stopping();
+
+            if !self.stopped
+                && !self.stopping_for_timeout
+                && timeout.is_some_and(|t| started.elapsed() >= t)
+            {
+                warn!(
+                    "[task_plan] Task {} exceeded timeout of {:?}, stopping",
+                    task_id,
+                    timeout.unwrap_or_default()
+                );
+                self.stopping_for_timeout = true;
+                if let Err(e) = self.tasker.post_stop() {
+                    warn!("[task_plan] post_stop failed: {}", e);
+                }
+                crate::mxu_actions::kill_launched_processes(&self.instance_id);
+            }
+
+            // sink 消息丢失（如 tasker 被销毁）时，回退到直接查询任务状态
+            if self.tasker.running() {
+                idle_since = None;
+            } else if idle_since.get_or_insert_with(Instant::now).elapsed() >= SINK_GRACE {
+                let status = self
+                    .tasker
+                    .get_task_detail(task_id)
+                    .ok()
+                    .flatten()
+                    .map(|d| d.status);
+                debug!(
+                    "[task_plan] No sink result for task {}, status: {:?}",
+                    task_id, status
+                );
+                return finished(
+                    status == Some(MaaStatus::SUCCEEDED),
+                    self.stopping_for_timeout,
+                );
+            }
+        }
+    }
+
+    /// 按策略执行单个任务（含重试与重复）
+    fn run_step(&mut self, index: usize, task: &TaskConfig) -> PlanStepResult {
+        let started = Instant::now();
+        let retries = task.retry;
+        let (iterations, until_success) = match &task.repeat {
+            Some(repeat) => (repeat.times.max(1), repeat.until_success),
+            None => (1, false),
+        };
+
+        let mut task_ids = Vec::new();
+        let mut status = TaskStatus::Failed;
+        // 最后一次尝试的状态（区分失败与超时）
+        let mut last_status = TaskStatus::Failed;
+
+        'iterations: for iteration in 0..iterations {
+            let mut iteration_ok = false;
+            for attempt in 0..=retries {
+                self.emit_step(PlanStepEvent {
+                    instance_id: self.instance_id.clone(),
+                    index,
+                    entry: task.entry.clone(),
+                    task_id: None,
+                    iteration,
+                    attempt,
+                    status: TaskStatus::Running,
+                });
+
+                let outcome = self.run_once(task);
+                let (task_id, step_status) = match outcome {
+                    RunOutcome::Succeeded(id) => (Some(id), TaskStatus::Succeeded),
+                    RunOutcome::Failed(id) => (id, TaskStatus::Failed),
+                    RunOutcome::TimedOut(id) => (Some(id), TaskStatus::TimedOut),
+                    RunOutcome::Stopped(id) => (id, TaskStatus::Failed),
+                };
+                task_ids.extend(task_id);
+                self.emit_step(PlanStepEvent {
+                    instance_id: self.instance_id.clone(),
+                    index,
+                    entry: task.entry.clone(),
+                    task_id,
+                    iteration,
+                    attempt,
+                    status: step_status.clone(),
+                });
+
+                match outcome {
+                    RunOutcome::Succeeded(_) => {
+                        iteration_ok = true;
+                        break;
+                    }
+                    RunOutcome::Stopped(_) => break 'iterations,
+                    RunOutcome::Failed(_) | RunOutcome::TimedOut(_) if attempt < retries => {
+                        info!(
+                            "[task_plan] {} failed, retrying in {}ms ({}/{})",
+                            task.entry,
+                            task.retry_delay_ms,
+                            attempt + 1,
+                            retries
+                        );
+                        if !self.sleep(Duration::from_millis(task.retry_delay_ms)) {
+                            break 'iterations;
+                        }
+                    }
+                    RunOutcome::Failed(_) | RunOutcome::TimedOut(_) => {}
+                }
+                last_status = step_status;
+            }
+
+            status = if iteration_ok {
+                TaskStatus::Succeeded
+            } else {
+                last_status.clone()
+            };
+            // 重复直到成功：成功即结束；固定次数：失败即结束
+            if iteration_ok == until_success {
+                break;
+            }
+        }
+
+        PlanStepResult {
+            index,
+            entry: task.entry.clone(),
+            status,
+            task_ids,
+            elapsed_ms: started.elapsed().as_millis() as u64,
+        }
+    }
+
+    fn skipped(&self, index: usize, task: &TaskConfig) -> PlanStepResult {
+        self.emit_step(PlanStepEvent {
+            instance_id: self.instance_id.clone(),
+            index,
+            entry: task.entry.clone(),
+            task_id: None,
+            iteration: 0,
+            attempt: 0,
+            status: TaskStatus::Skipped,
+        });
+        PlanStepResult {
+            index,
+            entry: task.entry.clone(),
+            status: TaskStatus::Skipped,
+            task_ids: Vec::new(),
+            elapsed_ms: 0,
+        }
+    }
+
+    /// 执行整个计划，返回同时通过 maa-plan-finished 发送的结果
+    fn run(mut self, tasks: Vec<TaskConfig>) -> PlanFinishedEvent {
+        let _guard = ActivePlanGuard::new();
+        info!(
+            "[task_plan] Running plan for {} with {} task(s)",
+            self.instance_id,
+            tasks.len()
+        );
+
+        let mut results: Vec<PlanStepResult> = Vec::with_capacity(tasks.len());
+        // 因失败策略或用户停止而中止后，只执行 always_run 任务
+        let mut aborted = false;
+        let mut previous_succeeded = false;
+
+        for (index, task) in tasks.iter().enumerate() {
+            if self.check_stopping() {
+                aborted = true;
+            }
+            if aborted && !task.always_run {
+                results.push(self.skipped(index, task));
+                continue;
+            }
+            if task.skip_if_previous_succeeded && previous_succeeded && !aborted {
+                info!(
+                    "[task_plan] Skipping {}: previous task succeeded",
+                    task.entry
+                );
+                results.push(self.skipped(index, task));
+                continue;
+            }
+
+            // 收尾任务不受之前的停止请求影响
+            if aborted && self.stopped {
+                self.stopped = false;
+                self.wait_until_idle();
+            }
+
+            let result = self.run_step(index, task);
+            previous_succeeded = matches!(result.status, TaskStatus::Succeeded);
+            if !previous_succeeded && task.on_failure == FailurePolicy::Stop {
+                info!("[task_plan] {} failed, stopping plan", task.entry);
+                aborted = true;
+            }
+            if self.stopped {
+                aborted = true;
+            }
+            results.push(result);
+        }
+
+        let success = !aborted
+            && results
+                .iter()
+                .all(|r| !matches!(r.status, TaskStatus::Failed | TaskStatus::TimedOut));
+        info!(
+            "[task_plan] Plan for {} finished, success: {}",
+            self.instance_id, success
+        );
+        let event = PlanFinishedEvent {
+            instance_id: self.instance_id.clone(),
+            success,
+            results,
+        };
+        if let Err(e) = self.app.emit("maa-plan-finished", &event) {
+            warn!("[task_plan] Failed to emit maa-plan-finished: {}", e);
+        }
+        event
+    }
+
+    /// 等待停止流程完成（tasker 不再处于 stopping / running）
+    fn wait_until_idle(&self) {
+        let deadline = Instant::now() + Duration::from_secs(30);
+        while (self.tasker.stopping() || self.tasker.running()) && Instant::now() < deadline {
+            std::thread::sleep(WAIT_SLICE);
+        }
+    }
+}
+
+/// 在当前线程按计划执行任务列表，直到全部任务结束
+pub(super) fn run_plan(
+    app: AppHandle,
+    state: Arc<MaaState>,
+    instance_id: String,
+    tasker: Tasker,
+    tasks: Vec<TaskConfig>,
+    cancel: Arc<AtomicBool>,
+) -> PlanFinishedEvent {
+    let runner = PlanRunner {
+        app,
+        state,
+        instance_id,
+        tasker,
+        cancel,
+        stopped: false,
+        stopping_for_timeout: false,
+    };
+    runner.run(tasks)
+}
+
+/// 在后台线程按计划执行任务列表
+pub(super) fn spawn_plan(
+    app: AppHandle,
+    state: Arc<MaaState>,
+    instance_id: String,
+    tasker: Tasker,
+    tasks: Vec<TaskConfig>,
+    cancel: Arc<AtomicBool>,
+) {
+    std::thread::spawn(move || run_plan(app, state, instance_id, tasker, tasks, cancel));
+}
+
+/// 获取实例的计划停止标志，并清除计划开始前遗留的停止请求
+pub(super) fn plan_cancel_flag(state: &MaaState, instance_id: &str) -> Arc<AtomicBool> {
+    let cancel = state
+        .instances
+        .lock()
+        .ok()
+        .and_then(|instances| {
+            instances
+                .get(instance_id)
+                .map(|instance| instance.plan_cancel.clone())
+        })
+        .unwrap_or_default();
+    cancel.store(false, Ordering::SeqCst);
+    cancel
+}
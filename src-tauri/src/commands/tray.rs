@@ -1,5 +1,6 @@
 //! 托盘相关命令
 
+use super::error::MxuError;
 use crate::tray;
 
 /// 设置关闭时是否最小化到托盘
@@ -17,12 +18,12 @@ pub fn get_minimize_to_tray() -> bool {
 
 /// 更新托盘图标
 #[tauri::command]
-pub fn update_tray_icon(icon_path: String) -> Result<(), String> {
+pub fn update_tray_icon(icon_path: String) -> Result<(), MxuError> {
     tray::update_tray_icon(&icon_path)
 }
 
 /// 更新托盘 tooltip
 #[tauri::command]
-pub fn update_tray_tooltip(tooltip: String) -> Result<(), String> {
+pub fn update_tray_tooltip(tooltip: String) -> Result<(), MxuError> {
     tray::update_tray_tooltip(&tooltip)
 }
@@ -5,7 +5,8 @@
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::process::Child;
-use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::Instant;
 
 use serde::{Deserialize, Serialize};
@@ -20,7 +21,7 @@ use maa_framework::tasker::Tasker;
 // ============================================================================
 
 /// ADB 设备信息
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct AdbDevice {
     pub name: String,
     pub adb_path: String,
@@ -30,6 +31,12 @@ pub struct AdbDevice {
     #[serde(with = "u64_as_string")]
     pub input_methods: u64,
     pub config: String,
+    /// 设备稳定标识（ro.serialno / AVD 名称等），端口变化后仍可据此识别同一设备
+    #[serde(default)]
+    pub identity: Option<String>,
+    /// 通过 maa_probe_adb_address 手动添加的时间（Unix 毫秒），扫描发现的设备为 None
+    #[serde(default)]
+    pub manual_added_at: Option<i64>,
 }
 
 /// 将 u64 序列化/反序列化为字符串，避免 JavaScript 精度丢失
@@ -58,6 +65,15 @@ pub struct Win32Window {
     pub handle: u64,
     pub class_name: String,
     pub window_name: String,
+    /// 所属进程 ID
+    #[serde(default)]
+    pub pid: Option<u32>,
+    /// 所属进程名（可执行文件名）
+    #[serde(default)]
+    pub process_name: Option<String>,
+    /// 所属进程的可执行文件路径（无权限读取时为 None）
+    #[serde(default)]
+    pub exe_path: Option<String>,
 }
 
 /// 控制器类型
@@ -89,6 +105,49 @@ pub enum ControllerConfig {
         #[serde(default)]
         uuid: Option<String>,
     },
+    /// 离线回放（从文件夹读取截图，记录输入）
+    Replay {
+        image_dir: String,
+        /// 回放脚本路径（JSON），为空时按文件名顺序播放
+        #[serde(default)]
+        script: Option<String>,
+        /// 按顺序播放到末尾后是否循环
+        #[serde(default)]
+        loop_images: bool,
+    },
+}
+
+/// 单个 ADB 截图 / 输入方式的探测结果
+#[derive(Debug, Clone, Serialize)]
+pub struct ControllerMethodProbe {
+    /// 方式位值（u64 作为字符串传递）
+    pub method: String,
+    pub name: String,
+    /// 仅启用该方式时能否连接
+    pub connected: bool,
+    pub attempts: u32,
+    pub successes: u32,
+    /// 全黑截图次数（输入方式恒为 0）
+    pub black_frames: u32,
+    pub avg_latency_ms: Option<u64>,
+    pub min_latency_ms: Option<u64>,
+    /// 全部成功且无黑屏
+    pub usable: bool,
+    /// 最近一次失败原因
+    pub error: Option<String>,
+}
+
+/// ADB 控制方式探测报告（各列表已按可用性和速度排序）
+#[derive(Debug, Clone, Serialize)]
+pub struct ControllerProbeReport {
+    pub address: String,
+    pub screencap: Vec<ControllerMethodProbe>,
+    pub input: Vec<ControllerMethodProbe>,
+    /// 最快的可用截图方式，可直接填入 ControllerConfig::Adb.screencap_methods
+    pub best_screencap_methods: Option<String>,
+    /// 最快的可用输入方式，可直接填入 ControllerConfig::Adb.input_methods
+    pub best_input_methods: Option<String>,
+    pub elapsed_ms: u64,
 }
 
 /// 连接状态
@@ -107,6 +166,10 @@ pub enum TaskStatus {
     Running,
     Succeeded,
     Failed,
+    /// 执行计划中被跳过
+    Skipped,
+    /// 超过 timeout_secs 被停止
+    TimedOut,
 }
 
 /// 实例运行时状态（用于前端查询）
@@ -130,6 +193,18 @@ pub struct AllInstanceStates {
     pub instances: HashMap<String, InstanceState>,
     pub cached_adb_devices: Vec<AdbDevice>,
     pub cached_win32_windows: Vec<Win32Window>,
+    /// ADB 设备缓存更新时间（Unix 毫秒，从未搜索过时为 None）
+    pub cached_adb_updated_at: Option<i64>,
+    /// Win32 窗口缓存更新时间（Unix 毫秒）
+    pub cached_win32_updated_at: Option<i64>,
+}
+
+/// 后台刷新发现 ADB 设备列表变化事件（maa-adb-devices-changed）
+#[derive(Debug, Clone, Serialize)]
+pub struct AdbDevicesChangedEvent {
+    pub devices: Vec<AdbDevice>,
+    /// 更新时间（Unix 毫秒）
+    pub updated_at: i64,
 }
 
 /// 实例运行时状态（持有 MaaFramework 对象句柄）
@@ -142,14 +217,26 @@ pub struct InstanceRuntime {
     pub agent_children: Vec<Child>,
     /// 当前运行的任务 ID 列表（用于刷新后恢复状态）
     pub task_ids: Vec<i64>,
+    /// 执行计划的停止请求，由 maa_stop_task 设置、计划执行器读取后清除
+    pub plan_cancel: Arc<AtomicBool>,
     /// 是否正在停止任务（用于防重复 stop）
     pub stop_in_progress: bool,
     /// stop 请求的起始时间（用于节流/重试）
     pub stop_started_at: Option<Instant>,
+    /// 已提交的资源包路径（按加载顺序，供热重载使用）
+    pub bundle_paths: Vec<String>,
+    /// 热重载监视线程的停止标志（None 表示未开启）
+    pub hot_reload_stop: Option<Arc<AtomicBool>>,
+    /// 回放控制器收到的输入记录（仅 Replay 控制器）
+    pub replay_inputs: Option<Arc<Mutex<Vec<ReplayInputRecord>>>>,
 }
 
 impl Drop for InstanceRuntime {
     fn drop(&mut self) {
+        // 停止热重载监视线程
+        if let Some(stop) = self.hot_reload_stop.take() {
+            stop.store(true, Ordering::SeqCst);
+        }
         // 断开并销毁所有 agent
         for client in &self.agent_clients {
             let _ = client.disconnect();
@@ -172,9 +259,69 @@ pub struct MaaState {
     pub cached_adb_devices: Mutex<Vec<AdbDevice>>,
     /// 缓存的 Win32 窗口列表（全局共享）
     pub cached_win32_windows: Mutex<Vec<Win32Window>>,
+    /// ADB 设备缓存更新时间（Unix 毫秒，随缓存持久化到数据目录）
+    pub cached_adb_updated_at: Mutex<Option<i64>>,
+    /// Win32 窗口缓存更新时间（Unix 毫秒）
+    pub cached_win32_updated_at: Mutex<Option<i64>>,
+    /// tasker 所属实例（key 为 tasker 原始指针）
+    /// 与 instances 分开加锁：MaaFramework 动作回调中只能查询此表，
+    /// instances 锁可能正被等待该任务结束的销毁操作持有
+    pub tasker_owners: Mutex<HashMap<usize, TaskerOwner>>,
+}
+
+/// tasker 所属实例及其运行中的任务数（由 tasker 回调维护）
+pub struct TaskerOwner {
+    pub instance_id: String,
+    pub running_tasks: Arc<AtomicUsize>,
 }
 
 impl MaaState {
+    /// 登记实例新建的 tasker（替换该实例之前的登记），返回其运行任务计数
+    pub fn register_tasker(&self, instance_id: &str, tasker: &Tasker) -> Arc<AtomicUsize> {
+        let running_tasks = Arc::new(AtomicUsize::new(0));
+        if let Ok(mut owners) = self.tasker_owners.lock() {
+            owners.retain(|_, owner| owner.instance_id != instance_id);
+            owners.insert(
+                tasker.raw() as usize,
+                TaskerOwner {
+                    instance_id: instance_id.to_string(),
+                    running_tasks: running_tasks.clone(),
+                },
+            );
+        }
+        running_tasks
+    }
+
+    /// 移除实例的 tasker 登记
+    pub fn unregister_tasker(&self, instance_id: &str) {
+        if let Ok(mut owners) = self.tasker_owners.lock() {
+            owners.retain(|_, owner| owner.instance_id != instance_id);
+        }
+    }
+
+    /// 查找 tasker 所属的实例 ID
+    pub fn tasker_instance(&self, tasker: &Tasker) -> Option<String> {
+        let owners = self.tasker_owners.lock().ok()?;
+        owners
+            .get(&(tasker.raw() as usize))
+            .map(|owner| owner.instance_id.clone())
+    }
+
+    /// 列出除指定 tasker 外仍有任务在运行的实例
+    pub fn other_running_instances(&self, current: &Tasker) -> Vec<String> {
+        let Ok(owners) = self.tasker_owners.lock() else {
+            return Vec::new();
+        };
+        let current = current.raw() as usize;
+        owners
+            .iter()
+            .filter(|(key, owner)| {
+                **key != current && owner.running_tasks.load(Ordering::SeqCst) > 0
+            })
+            .map(|(_, owner)| owner.instance_id.clone())
+            .collect()
+    }
+
     /// 清理所有实例的 agent 子进程
     pub fn cleanup_all_agent_children(&self) {
         if let Ok(mut instances) = self.instances.lock() {
@@ -203,6 +350,110 @@ pub struct MaaCallbackEvent {
     pub details: String,
 }
 
+/// 单个资源包的加载结果
+#[derive(Debug, Clone, Serialize)]
+pub struct BundleLoadEntry {
+    /// 请求的原始路径
+    pub path: String,
+    /// 提交成功时的加载请求 ID
+    pub job_id: Option<i64>,
+    /// 提交失败原因
+    pub error: Option<String>,
+    /// 资源包中缺失的常规子目录（pipeline / image），仅作提示
+    pub missing_dirs: Vec<String>,
+    /// 加载完成状态（仅在等待加载完成时填充）
+    pub status: Option<TaskStatus>,
+    /// 加载耗时（毫秒，仅在等待加载完成时填充）
+    pub elapsed_ms: Option<u64>,
+}
+
+/// 资源加载报告
+#[derive(Debug, Clone, Serialize)]
+pub struct ResourceLoadReport {
+    /// 与请求路径一一对应
+    pub bundles: Vec<BundleLoadEntry>,
+}
+
+/// 单个资源包加载完成事件（maa-resource-bundle）
+#[derive(Debug, Clone, Serialize)]
+pub struct ResourceBundleEvent {
+    pub instance_id: String,
+    pub path: String,
+    pub job_id: i64,
+    pub status: TaskStatus,
+    pub elapsed_ms: u64,
+}
+
+/// 资源文件错误（热重载时的 JSON 解析错误等）
+#[derive(Debug, Clone, Serialize)]
+pub struct ResourceFileError {
+    pub file: String,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    pub message: String,
+}
+
+/// 资源热重载事件（maa-resource-reloaded）
+#[derive(Debug, Clone, Serialize)]
+pub struct ResourceReloadEvent {
+    pub instance_id: String,
+    pub added: Vec<String>,
+    pub modified: Vec<String>,
+    pub removed: Vec<String>,
+    /// 是否已替换为新资源
+    pub reloaded: bool,
+    /// 未重载的原因（JSON 解析错误或加载失败）
+    pub errors: Vec<ResourceFileError>,
+    pub elapsed_ms: u64,
+}
+
+/// 单节点识别调试结果
+#[derive(Debug, Clone, Serialize)]
+pub struct RecognitionDebugResult {
+    pub node: String,
+    /// 识别算法（如 TemplateMatch、OCR）
+    pub algorithm: String,
+    pub hit: bool,
+    /// 命中框 [x, y, width, height]
+    #[serde(rename = "box")]
+    pub hit_box: Option<[i32; 4]>,
+    /// 最佳结果得分（算法不提供时为 None）
+    pub score: Option<f64>,
+    /// 算法原始详情
+    pub detail: serde_json::Value,
+    /// 调试绘制图（base64 data URL）
+    pub draws: Vec<String>,
+    pub elapsed_ms: u64,
+}
+
+/// 单节点动作调试结果
+#[derive(Debug, Clone, Serialize)]
+pub struct ActionDebugResult {
+    pub node: String,
+    /// 动作类型（如 Click、Swipe）
+    pub action: String,
+    pub success: bool,
+    /// 动作目标框 [x, y, width, height]
+    #[serde(rename = "box")]
+    pub target_box: [i32; 4],
+    pub detail: serde_json::Value,
+    pub elapsed_ms: u64,
+}
+
+/// 回放控制器收到的输入
+#[derive(Debug, Clone, Serialize)]
+pub struct ReplayInputRecord {
+    /// 序号（从 0 开始）
+    pub seq: u64,
+    /// 输入类型（click、swipe、input_text 等）
+    pub kind: String,
+    pub params: serde_json::Value,
+    /// 收到输入时的画面文件名
+    pub frame: Option<String>,
+    /// 距控制器创建的毫秒数
+    pub elapsed_ms: u64,
+}
+
 /// Agent 配置
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AgentConfig {
@@ -214,10 +465,272 @@ pub struct AgentConfig {
 }
 
 /// 任务配置
+/// 除 entry / pipeline_override 外均为执行计划策略，全部缺省时任务按顺序一次性提交
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TaskConfig {
     pub entry: String,
     pub pipeline_override: String,
+    /// 失败后是否继续执行后续任务
+    #[serde(default)]
+    pub on_failure: FailurePolicy,
+    /// 失败后重试次数
+    #[serde(default)]
+    pub retry: u32,
+    /// 重试间隔（毫秒）
+    #[serde(default)]
+    pub retry_delay_ms: u64,
+    /// 前一个任务成功时跳过本任务（如"主方案失败时才执行备用方案"）
+    #[serde(default)]
+    pub skip_if_previous_succeeded: bool,
+    /// 重复执行
+    #[serde(default)]
+    pub repeat: Option<RepeatPolicy>,
+    /// 收尾任务：计划因失败或停止中止时仍然执行（如关闭游戏）
+    #[serde(default)]
+    pub always_run: bool,
+    /// 超时时间（秒），超时后停止任务并标记为 TimedOut，按 on_failure 决定是否继续
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
+}
+
+impl TaskConfig {
+    /// 是否设置了任何执行计划策略
+    pub fn has_policy(&self) -> bool {
+        self.on_failure != FailurePolicy::Continue
+            || self.retry > 0
+            || self.skip_if_previous_succeeded
+            || self.repeat.is_some()
+            || self.always_run
+            || self.timeout_secs.is_some()
+    }
+}
+
+/// 任务失败后的处理方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FailurePolicy {
+    /// 继续执行后续任务
+    #[default]
+    Continue,
+    /// 停止计划（仅执行 always_run 任务）
+    Stop,
+}
+
+/// 任务重复策略
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepeatPolicy {
+    /// 最多执行次数
+    pub times: u32,
+    /// 为 true 时成功即停止（重复直到成功），否则失败即停止（固定执行 times 次）
+    #[serde(default)]
+    pub until_success: bool,
+}
+
+/// 执行计划单步状态事件（maa-plan-step）
+#[derive(Debug, Clone, Serialize)]
+pub struct PlanStepEvent {
+    pub instance_id: String,
+    /// 任务在列表中的下标
+    pub index: usize,
+    pub entry: String,
+    /// 提交后的 task_id（Running 事件中为 None）
+    pub task_id: Option<i64>,
+    /// 第几次重复（从 0 开始）
+    pub iteration: u32,
+    /// 第几次尝试（从 0 开始，大于 0 表示重试）
+    pub attempt: u32,
+    pub status: TaskStatus,
+}
+
+/// 执行计划中单个任务的最终结果
+#[derive(Debug, Clone, Serialize)]
+pub struct PlanStepResult {
+    pub index: usize,
+    pub entry: String,
+    pub status: TaskStatus,
+    /// 所有尝试对应的 task_id
+    pub task_ids: Vec<i64>,
+    pub elapsed_ms: u64,
+}
+
+/// 执行计划完成事件（maa-plan-finished）
+#[derive(Debug, Clone, Serialize)]
+pub struct PlanFinishedEvent {
+    pub instance_id: String,
+    /// 计划未中止且没有失败的任务
+    pub success: bool,
+    pub results: Vec<PlanStepResult>,
+}
+
+/// maa_start_tasks 的返回值
+#[derive(Debug, Clone, Serialize)]
+pub struct StartTasksResult {
+    /// 已提交的 task_id（按任务顺序）；plan 为 true 时为空
+    pub task_ids: Vec<i64>,
+    /// 是否由执行计划逐个提交，此时 task_id 通过 maa-plan-step 事件通知
+    pub plan: bool,
+}
+
+/// 模拟器类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EmulatorKind {
+    /// MuMu 模拟器 12（MuMuManager）
+    Mumu,
+    /// 雷电模拟器 9（ldconsole）
+    Ldplayer,
+    /// BlueStacks 5（HD-Player）
+    Bluestacks,
+    /// Android SDK 模拟器（emulator -avd）
+    Avd,
+}
+
+/// 模拟器配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmulatorConfig {
+    pub kind: EmulatorKind,
+    /// 安装目录（AVD 为 Android SDK 目录，缺省时使用 ANDROID_HOME / ANDROID_SDK_ROOT）
+    #[serde(default)]
+    pub install_dir: Option<String>,
+    /// 多开实例序号
+    #[serde(default)]
+    pub index: u32,
+    /// BlueStacks 实例名 / AVD 名称
+    #[serde(default)]
+    pub instance_name: Option<String>,
+    /// adb 路径，缺省时使用模拟器自带的 adb
+    #[serde(default)]
+    pub adb_path: Option<String>,
+    /// adb 地址，缺省时按实例序号推算
+    #[serde(default)]
+    pub address: Option<String>,
+    /// 等待开机的超时时间（秒），默认 120
+    #[serde(default)]
+    pub boot_timeout_secs: Option<u64>,
+    /// 作为 maa_start_tasks 后置步骤时，任务结束后关闭模拟器
+    #[serde(default)]
+    pub close_after_tasks: bool,
+}
+
+/// 模拟器状态
+#[derive(Debug, Clone, Serialize)]
+pub struct EmulatorStatus {
+    pub address: String,
+    pub adb_path: String,
+    /// sys.boot_completed 为 1
+    pub booted: bool,
+    /// 本次调用启动了模拟器（之前未开机）
+    pub launched: bool,
+    pub elapsed_ms: u64,
+}
+
+/// 多实例编排计划：分组按顺序执行，组内实例并行执行
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrchestrationPlan {
+    pub groups: Vec<OrchestrationGroup>,
+    /// 有实例失败时跳过后续分组
+    #[serde(default)]
+    pub stop_on_failure: bool,
+}
+
+/// 编排分组，组内实例同时启动，全部结束后才进入下一组
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrchestrationGroup {
+    pub instances: Vec<OrchestrationEntry>,
+}
+
+/// 编排中单个实例的任务列表（参数与 maa_start_tasks 一致）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrchestrationEntry {
+    pub instance_id: String,
+    pub tasks: Vec<TaskConfig>,
+    #[serde(default)]
+    pub agent_configs: Option<Vec<AgentConfig>>,
+    #[serde(default)]
+    pub cwd: String,
+    #[serde(default)]
+    pub tcp_compat_mode: bool,
+    /// 接管指定实例的控制器（多个账号共用同一模拟器时，后续实例无需再次连接）
+    #[serde(default)]
+    pub controller_from: Option<String>,
+}
+
+/// 编排进度事件（maa-orchestration-progress）
+#[derive(Debug, Clone, Serialize)]
+pub struct OrchestrationProgressEvent {
+    pub run_id: String,
+    pub group_index: usize,
+    pub instance_id: String,
+    pub status: TaskStatus,
+    /// 已结束的实例数
+    pub completed: usize,
+    /// 计划中的实例总数
+    pub total: usize,
+    pub error: Option<String>,
+}
+
+/// 编排中单个实例的最终结果
+#[derive(Debug, Clone, Serialize)]
+pub struct OrchestrationInstanceResult {
+    pub group_index: usize,
+    pub instance_id: String,
+    pub status: TaskStatus,
+    /// 启动失败时的错误信息
+    pub error: Option<String>,
+    /// 各任务的执行结果
+    pub results: Vec<PlanStepResult>,
+    pub elapsed_ms: u64,
+}
+
+/// 编排完成事件（maa-orchestration-finished）
+#[derive(Debug, Clone, Serialize)]
+pub struct OrchestrationFinishedEvent {
+    pub run_id: String,
+    /// 所有实例均成功
+    pub success: bool,
+    /// 编排被用户取消
+    pub cancelled: bool,
+    pub results: Vec<OrchestrationInstanceResult>,
+    pub elapsed_ms: u64,
+}
+
+/// Pipeline 诊断级别
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DiagnosticSeverity {
+    /// 错误：提交后必然导致任务失败，提交前拦截
+    Error,
+    /// 警告：可能是拼写错误，但不阻止提交
+    Warning,
+}
+
+/// Pipeline override 诊断信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PipelineDiagnostic {
+    pub severity: DiagnosticSeverity,
+    /// 所属节点名（JSON 解析错误时为空）
+    pub node: Option<String>,
+    /// 相关字段名
+    pub field: Option<String>,
+    pub message: String,
+    /// JSON 解析错误所在行（1 起始）
+    pub line: Option<usize>,
+    /// JSON 解析错误所在列（1 起始）
+    pub column: Option<usize>,
+}
+
+/// Pipeline override 校验结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PipelineValidationReport {
+    /// 是否不含 Error 级别诊断
+    pub valid: bool,
+    pub diagnostics: Vec<PipelineDiagnostic>,
+}
+
+/// 资源中已注册的自定义识别/动作
+#[derive(Debug, Clone, Serialize)]
+pub struct ResourceCustomList {
+    pub actions: Vec<String>,
+    pub recognitions: Vec<String>,
 }
 
 /// 版本检查结果
@@ -271,3 +784,18 @@ pub struct SystemInfo {
     pub arch: String,
     pub tauri_version: String,
 }
+
+/// 插件提供的 custom action 信息
+#[derive(Debug, Clone, Serialize)]
+pub struct PluginActionInfo {
+    /// 插件名称
+    pub plugin: String,
+    pub version: Option<String>,
+    /// 插件类型：library / command
+    pub kind: String,
+    /// custom action 名称
+    pub name: String,
+    pub description: Option<String>,
+    /// 参数 JSON Schema
+    pub param_schema: Option<serde_json::Value>,
+}
@@ -4,12 +4,13 @@
 
 use log::{info, warn};
 
+use super::error::MxuError;
 use super::file_ops::get_exe_dir;
 use super::types::ChangesJson;
 
 /// 解压压缩文件到指定目录，支持 zip 和 tar.gz/tgz 格式
 #[tauri::command]
-pub fn extract_zip(zip_path: String, dest_dir: String) -> Result<(), String> {
+pub fn extract_zip(zip_path: String, dest_dir: String) -> Result<(), MxuError> {
     info!("extract_zip called: {} -> {}", zip_path, dest_dir);
 
     let path_lower = zip_path.to_lowercase();
@@ -23,20 +24,22 @@ pub fn extract_zip(zip_path: String, dest_dir: String) -> Result<(), String> {
 }
 
 /// 解压 ZIP 文件
-fn extract_zip_file(zip_path: &str, dest_dir: &str) -> Result<(), String> {
-    let file = std::fs::File::open(zip_path)
-        .map_err(|e| format!("无法打开 ZIP 文件 [{}]: {}", zip_path, e))?;
+fn extract_zip_file(zip_path: &str, dest_dir: &str) -> Result<(), MxuError> {
+    let file = std::fs::File::open(zip_path).map_err(|e| MxuError::io("open file", zip_path, e))?;
 
-    let mut archive =
-        zip::ZipArchive::new(file).map_err(|e| format!("无法解析 ZIP 文件: {}", e))?;
+    let mut archive = zip::ZipArchive::new(file).map_err(|e| MxuError::ExtractFailed {
+        path: zip_path.to_string(),
+        detail: e.to_string(),
+    })?;
 
     // 确保目标目录存在
-    std::fs::create_dir_all(dest_dir).map_err(|e| format!("无法创建目录 [{}]: {}", dest_dir, e))?;
+    std::fs::create_dir_all(dest_dir).map_err(|e| MxuError::io("create dir", dest_dir, e))?;
 
     for i in 0..archive.len() {
-        let mut file = archive
-            .by_index(i)
-            .map_err(|e| format!("无法读取 ZIP 条目 {}: {}", i, e))?;
+        let mut file = archive.by_index(i).map_err(|e| MxuError::ExtractFailed {
+            path: zip_path.to_string(),
+            detail: format!("entry {}: {}", i, e),
+        })?;
 
         let outpath = match file.enclosed_name() {
             Some(path) => std::path::Path::new(dest_dir).join(path),
@@ -46,19 +49,18 @@ fn extract_zip_file(zip_path: &str, dest_dir: &str) -> Result<(), String> {
         if file.name().ends_with('/') {
             // 目录
             std::fs::create_dir_all(&outpath)
-                .map_err(|e| format!("无法创建目录 [{}]: {}", outpath.display(), e))?;
+                .map_err(|e| MxuError::io("create dir", &outpath, e))?;
         } else {
             // 文件
             if let Some(p) = outpath.parent() {
                 if !p.exists() {
-                    std::fs::create_dir_all(p)
-                        .map_err(|e| format!("无法创建父目录 [{}]: {}", p.display(), e))?;
+                    std::fs::create_dir_all(p).map_err(|e| MxuError::io("create dir", p, e))?;
                 }
             }
             let mut outfile = std::fs::File::create(&outpath)
-                .map_err(|e| format!("无法创建文件 [{}]: {}", outpath.display(), e))?;
+                .map_err(|e| MxuError::io("create file", &outpath, e))?;
             std::io::copy(&mut file, &mut outfile)
-                .map_err(|e| format!("无法写入文件 [{}]: {}", outpath.display(), e))?;
+                .map_err(|e| MxuError::io("write file", &outpath, e))?;
         }
     }
 
@@ -67,22 +69,24 @@ fn extract_zip_file(zip_path: &str, dest_dir: &str) -> Result<(), String> {
 }
 
 /// 解压 tar.gz/tgz 文件
-fn extract_tar_gz(tar_path: &str, dest_dir: &str) -> Result<(), String> {
+fn extract_tar_gz(tar_path: &str, dest_dir: &str) -> Result<(), MxuError> {
     use flate2::read::GzDecoder;
     use tar::Archive;
 
-    let file = std::fs::File::open(tar_path)
-        .map_err(|e| format!("无法打开 tar.gz 文件 [{}]: {}", tar_path, e))?;
+    let file = std::fs::File::open(tar_path).map_err(|e| MxuError::io("open file", tar_path, e))?;
 
     let gz = GzDecoder::new(file);
     let mut archive = Archive::new(gz);
 
     // 确保目标目录存在
-    std::fs::create_dir_all(dest_dir).map_err(|e| format!("无法创建目录 [{}]: {}", dest_dir, e))?;
+    std::fs::create_dir_all(dest_dir).map_err(|e| MxuError::io("create dir", dest_dir, e))?;
 
     archive
         .unpack(dest_dir)
-        .map_err(|e| format!("解压 tar.gz 失败: {}", e))?;
+        .map_err(|e| MxuError::ExtractFailed {
+            path: tar_path.to_string(),
+            detail: e.to_string(),
+        })?;
 
     info!("extract_tar_gz success");
     Ok(())
@@ -90,7 +94,7 @@ fn extract_tar_gz(tar_path: &str, dest_dir: &str) -> Result<(), String> {
 
 /// 检查解压目录中是否存在 changes.json（增量包标识）
 #[tauri::command]
-pub fn check_changes_json(extract_dir: String) -> Result<Option<ChangesJson>, String> {
+pub fn check_changes_json(extract_dir: String) -> Result<Option<ChangesJson>, MxuError> {
     let changes_path = std::path::Path::new(&extract_dir).join("changes.json");
 
     if !changes_path.exists() {
@@ -98,10 +102,10 @@ pub fn check_changes_json(extract_dir: String) -> Result<Option<ChangesJson>, St
     }
 
     let content = std::fs::read_to_string(&changes_path)
-        .map_err(|e| format!("无法读取 changes.json: {}", e))?;
+        .map_err(|e| MxuError::io("read file", &changes_path, e))?;
 
     let changes: ChangesJson =
-        serde_json::from_str(&content).map_err(|e| format!("无法解析 changes.json: {}", e))?;
+        serde_json::from_str(&content).map_err(|e| MxuError::io("parse file", &changes_path, e))?;
 
     Ok(Some(changes))
 }
@@ -142,13 +146,13 @@ pub fn cleanup_dir_contents(dir: &std::path::Path) -> (usize, usize) {
 /// 将文件或目录移动到程序目录下的 cache/old 文件夹，处理重名冲突
 /// 供前端调用，统一文件移动逻辑
 #[tauri::command]
-pub fn move_file_to_old(file_path: String) -> Result<(), String> {
+pub fn move_file_to_old(file_path: String) -> Result<(), MxuError> {
     let path = std::path::Path::new(&file_path);
     move_to_old_folder(path)
 }
 
 /// 将文件或目录移动到程序目录下的 cache/old 文件夹，处理重名冲突（内部函数）
-pub fn move_to_old_folder(source: &std::path::Path) -> Result<(), String> {
+pub fn move_to_old_folder(source: &std::path::Path) -> Result<(), MxuError> {
     if !source.exists() {
         return Ok(());
     }
@@ -173,12 +177,11 @@ pub fn move_to_old_folder(source: &std::path::Path) -> Result<(), String> {
     }
 
     // 确保目录存在（刚删掉的话需要重新创建）
-    std::fs::create_dir_all(&old_dir)
-        .map_err(|e| format!("无法创建 old 目录 [{}]: {}", old_dir.display(), e))?;
+    std::fs::create_dir_all(&old_dir).map_err(|e| MxuError::io("create dir", &old_dir, e))?;
 
-    let file_name = source
-        .file_name()
-        .ok_or_else(|| format!("无法获取文件名: {}", source.display()))?;
+    let file_name = source.file_name().ok_or_else(|| MxuError::InvalidPath {
+        path: source.display().to_string(),
+    })?;
 
     let mut dest = old_dir.join(file_name);
 
@@ -197,11 +200,10 @@ pub fn move_to_old_folder(source: &std::path::Path) -> Result<(), String> {
 
     // 执行移动（重命名）
     std::fs::rename(source, &dest).map_err(|e| {
-        format!(
-            "无法移动 [{}] -> [{}]: {}",
-            source.display(),
-            dest.display(),
-            e
+        MxuError::io(
+            "move file",
+            source,
+            format!("-> [{}]: {}", dest.display(), e),
         )
     })?;
 
@@ -216,13 +218,13 @@ pub fn apply_incremental_update(
     extract_dir: String,
     target_dir: String,
     deleted_files: Vec<String>,
-) -> Result<(), String> {
+) -> Result<(), MxuError> {
     info!("apply_incremental_update called");
     info!("extract_dir: {}, target_dir: {}", extract_dir, target_dir);
     info!("deleted_files: {:?}", deleted_files);
 
     let target_path = std::path::Path::new(&target_dir);
-    let mut move_errors: Vec<String> = Vec::new();
+    let mut move_errors: Vec<MxuError> = Vec::new();
 
     // 1. 尝试将 deleted 中列出的文件移动到 old 文件夹（失败不阻断）
     for file in &deleted_files {
@@ -252,17 +254,17 @@ pub fn apply_incremental_update(
 /// 应用全量更新：将与新包根目录同名的文件夹/文件移动到 old 文件夹，然后复制新文件
 /// 即使移动旧文件失败，也会继续复制新文件，确保程序可用
 #[tauri::command]
-pub fn apply_full_update(extract_dir: String, target_dir: String) -> Result<(), String> {
+pub fn apply_full_update(extract_dir: String, target_dir: String) -> Result<(), MxuError> {
     info!("apply_full_update called");
     info!("extract_dir: {}, target_dir: {}", extract_dir, target_dir);
 
     let extract_path = std::path::Path::new(&extract_dir);
     let target_path = std::path::Path::new(&target_dir);
-    let mut move_errors: Vec<String> = Vec::new();
+    let mut move_errors: Vec<MxuError> = Vec::new();
 
     // 1. 获取解压目录中的根级条目
     let entries: Vec<_> = std::fs::read_dir(extract_path)
-        .map_err(|e| format!("无法读取解压目录: {}", e))?
+        .map_err(|e| MxuError::io("read dir", extract_path, e))?
         .filter_map(|e| e.ok())
         .collect();
 
@@ -300,7 +302,7 @@ pub fn apply_full_update(extract_dir: String, target_dir: String) -> Result<(),
 
 /// 复制单个文件，先尝试将目标文件移动到 old 目录再复制
 /// 如果移动失败，直接尝试覆盖（确保新文件能被复制）
-fn copy_file_with_move_old(src: &std::path::Path, dst: &std::path::Path) -> Result<(), String> {
+fn copy_file_with_move_old(src: &std::path::Path, dst: &std::path::Path) -> Result<(), MxuError> {
     // 如果目标文件存在，先尝试移动到 old 目录
     if dst.exists() {
         if let Err(e) = move_to_old_folder(dst) {
@@ -313,30 +315,22 @@ fn copy_file_with_move_old(src: &std::path::Path, dst: &std::path::Path) -> Resu
     }
 
     // 复制新文件
-    std::fs::copy(src, dst).map_err(|e| {
-        format!(
-            "无法复制文件 [{}] -> [{}]: {}",
-            src.display(),
-            dst.display(),
-            e
-        )
-    })?;
+    std::fs::copy(src, dst)
+        .map_err(|e| MxuError::io("copy file", src, format!("-> [{}]: {}", dst.display(), e)))?;
 
     Ok(())
 }
 
 /// 递归复制目录内容（不包含根目录本身）
-fn copy_dir_contents(src: &str, dst: &str, skip_files: Option<&[&str]>) -> Result<(), String> {
+fn copy_dir_contents(src: &str, dst: &str, skip_files: Option<&[&str]>) -> Result<(), MxuError> {
     let src_path = std::path::Path::new(src);
     let dst_path = std::path::Path::new(dst);
 
     // 确保目标目录存在
-    std::fs::create_dir_all(dst_path).map_err(|e| format!("无法创建目录 [{}]: {}", dst, e))?;
+    std::fs::create_dir_all(dst_path).map_err(|e| MxuError::io("create dir", dst, e))?;
 
-    for entry in
-        std::fs::read_dir(src_path).map_err(|e| format!("无法读取目录 [{}]: {}", src, e))?
-    {
-        let entry = entry.map_err(|e| format!("无法读取目录条目: {}", e))?;
+    for entry in std::fs::read_dir(src_path).map_err(|e| MxuError::io("read dir", src, e))? {
+        let entry = entry.map_err(|e| MxuError::io("read dir", src_path, e))?;
         let file_name = entry.file_name();
         let file_name_str = file_name.to_string_lossy();
 
@@ -361,13 +355,11 @@ fn copy_dir_contents(src: &str, dst: &str, skip_files: Option<&[&str]>) -> Resul
 }
 
 /// 递归复制整个目录
-fn copy_dir_recursive(src: &std::path::Path, dst: &std::path::Path) -> Result<(), String> {
-    std::fs::create_dir_all(dst).map_err(|e| format!("无法创建目录 [{}]: {}", dst.display(), e))?;
+fn copy_dir_recursive(src: &std::path::Path, dst: &std::path::Path) -> Result<(), MxuError> {
+    std::fs::create_dir_all(dst).map_err(|e| MxuError::io("create dir", dst, e))?;
 
-    for entry in
-        std::fs::read_dir(src).map_err(|e| format!("无法读取目录 [{}]: {}", src.display(), e))?
-    {
-        let entry = entry.map_err(|e| format!("无法读取目录条目: {}", e))?;
+    for entry in std::fs::read_dir(src).map_err(|e| MxuError::io("read dir", src, e))? {
+        let entry = entry.map_err(|e| MxuError::io("read dir", src, e))?;
         let src_item = entry.path();
         let dst_item = dst.join(entry.file_name());
 
@@ -383,13 +375,12 @@ fn copy_dir_recursive(src: &std::path::Path, dst: &std::path::Path) -> Result<()
 
 /// 清理临时解压目录
 #[tauri::command]
-pub fn cleanup_extract_dir(extract_dir: String) -> Result<(), String> {
+pub fn cleanup_extract_dir(extract_dir: String) -> Result<(), MxuError> {
     info!("cleanup_extract_dir: {}", extract_dir);
 
     let path = std::path::Path::new(&extract_dir);
     if path.exists() {
-        std::fs::remove_dir_all(path)
-            .map_err(|e| format!("无法清理目录 [{}]: {}", extract_dir, e))?;
+        std::fs::remove_dir_all(path).map_err(|e| MxuError::io("remove dir", &extract_dir, e))?;
     }
 
     Ok(())
@@ -402,7 +393,7 @@ pub fn fallback_update(
     extract_dir: String,
     target_dir: String,
     new_version: String,
-) -> Result<String, String> {
+) -> Result<String, MxuError> {
     info!(
         "fallback_update called: extract_dir={}, target_dir={}, new_version={}",
         extract_dir, target_dir, new_version
@@ -425,7 +416,8 @@ pub fn fallback_update(
     info!("创建兜底目录: {}", final_fallback_dir.display());
 
     // 创建兜底目录
-    std::fs::create_dir_all(&final_fallback_dir).map_err(|e| format!("无法创建兜底目录: {}", e))?;
+    std::fs::create_dir_all(&final_fallback_dir)
+        .map_err(|e| MxuError::io("create dir", &final_fallback_dir, e))?;
 
     // 复制解压的新文件到兜底目录
     copy_dir_contents(
@@ -1,5 +1,8 @@
 pub mod commands;
 mod mxu_actions;
+mod mxu_plugins;
+mod mxu_recognitions;
+mod replay_controller;
 mod tray;
 
 use commands::MaaState;
@@ -44,8 +47,13 @@ pub fn run() {
         .setup(|app| {
             // 创建 MaaState 并注册为 Tauri 管理状态
             let maa_state = Arc::new(MaaState::default());
+            // 加载上次保存的设备 / 窗口搜索结果，前端启动后可直接使用
+            commands::discovery_cache::load(&maa_state);
             app.manage(maa_state);
 
+            // MXU 内置动作通过 AppHandle 发送进度事件
+            mxu_actions::init_app_handle(app.handle().clone());
+
             // Windows 下移除系统标题栏（使用自定义标题栏）
             // macOS/Linux 保留完整的原生标题栏
             #[cfg(target_os = "windows")]
@@ -87,7 +95,13 @@ pub fn run() {
                     let dll_path = maafw_dir.join("libMaaFramework.so");
 
                     match maa_framework::load_library(&dll_path) {
-                        Ok(()) => log::info!("MaaFramework loaded from {:?}", dll_path),
+                        Ok(()) => {
+                            log::info!("MaaFramework loaded from {:?}", dll_path);
+                            // 后台重新扫描 ADB 设备，更新启动时加载的缓存
+                            commands::discovery_cache::spawn_background_refresh(
+                                app.handle().clone(),
+                            );
+                        }
                         Err(e) => {
                             log::error!("Failed to load MaaFramework: {}", e);
                             // 检查是否是 DLL 存在但加载失败的情况（可能是运行库缺失）
@@ -120,14 +134,24 @@ pub fn run() {
             commands::maa_core::maa_get_version,
             commands::maa_core::maa_check_version,
             commands::maa_core::maa_find_adb_devices,
+            commands::adb::maa_probe_adb_address,
+            commands::controller_probe::maa_probe_adb_controller,
             commands::maa_core::maa_find_win32_windows,
+            commands::maa_core::maa_wait_for_win32_window,
             commands::maa_core::maa_create_instance,
             commands::maa_core::maa_destroy_instance,
             commands::maa_core::maa_connect_controller,
             commands::maa_core::maa_get_connection_status,
+            commands::maa_core::maa_get_replay_inputs,
             commands::maa_core::maa_load_resource,
             commands::maa_core::maa_is_resource_loaded,
             commands::maa_core::maa_destroy_resource,
+            commands::maa_core::maa_get_node_list,
+            commands::maa_core::maa_get_node_data,
+            commands::maa_core::maa_get_custom_list,
+            commands::maa_core::maa_get_resource_hash,
+            commands::hot_reload::maa_set_hot_reload,
+            commands::hot_reload::maa_is_hot_reload_enabled,
             commands::maa_core::maa_run_task,
             commands::maa_core::maa_get_task_status,
             commands::maa_core::maa_stop_task,
@@ -135,9 +159,21 @@ pub fn run() {
             commands::maa_core::maa_is_running,
             commands::maa_core::maa_post_screencap,
             commands::maa_core::maa_get_cached_image,
+            // Pipeline 校验命令
+            commands::pipeline::maa_validate_pipeline_override,
+            // 单节点调试命令
+            commands::node_debug::maa_debug_recognition,
+            commands::node_debug::maa_debug_action,
             // Agent 命令
             commands::maa_agent::maa_start_tasks,
             commands::maa_agent::maa_stop_agent,
+            // 模拟器命令
+            commands::emulator::maa_start_emulator,
+            commands::emulator::maa_stop_emulator,
+            commands::emulator::maa_get_emulator_status,
+            // 多实例编排命令
+            commands::orchestrator::maa_run_orchestration,
+            commands::orchestrator::maa_cancel_orchestration,
             // 文件操作命令
             commands::file_ops::read_local_file,
             commands::file_ops::read_local_file_base64,
@@ -164,11 +200,14 @@ pub fn run() {
             // 下载命令
             commands::download::download_file,
             commands::download::cancel_download,
+            commands::download::set_download_proxy,
             // 系统相关命令
             commands::system::is_elevated,
             commands::system::is_autostart,
             commands::system::restart_as_admin,
             commands::system::maa_set_save_draw,
+            commands::system::cancel_power_countdown,
+            commands::system::list_plugin_actions,
             commands::system::open_file,
             commands::system::run_and_wait,
             commands::system::run_action,
@@ -190,10 +229,11 @@ pub fn run() {
                         api.prevent_close();
                     }
                 }
-                // 窗口销毁时清理所有 agent 子进程
+                // 窗口销毁时清理所有 agent 子进程及 MXU_LAUNCH 登记的进程
                 tauri::WindowEvent::Destroyed => {
                     if let Some(state) = window.try_state::<Arc<MaaState>>() {
                         state.cleanup_all_agent_children();
+                        mxu_actions::kill_all_launched_processes();
                     }
                 }
                 _ => {}
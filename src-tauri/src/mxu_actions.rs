@@ -2,10 +2,97 @@
 //!
 //! 提供 MXU 特有的自定义动作实现，如 MXU_SLEEP 等
 
-use chrono::TimeZone;
 use log::{info, warn};
 use maa_framework::custom::FnAction;
 use maa_framework::resource::Resource;
+use tauri::Emitter;
+
+use crate::commands::error::MxuError;
+
+// ============================================================================
+// 公共辅助：可取消等待与进度事件
+// ============================================================================
+
+/// 可取消等待的检查间隔
+const WAIT_SLICE: std::time::Duration = std::time::Duration::from_millis(200);
+
+/// 用于发送进度事件的 AppHandle（应用启动时设置）
+static APP_HANDLE: std::sync::OnceLock<tauri::AppHandle> = std::sync::OnceLock::new();
+
+/// 设置 AppHandle，之后长时间运行的动作会发送 mxu-action-progress 事件
+pub fn init_app_handle(app: tauri::AppHandle) {
+    let _ = APP_HANDLE.set(app);
+}
+
+/// 长时间运行动作的倒计时进度事件（mxu-action-progress）
+#[derive(Clone, serde::Serialize)]
+struct ActionProgressEvent<'a> {
+    action: &'a str,
+    task_id: i64,
+    node: &'a str,
+    total_secs: u64,
+    remaining_secs: u64,
+}
+
+/// 发送倒计时进度事件
+fn emit_action_progress(
+    ctx: &maa_framework::context::Context,
+    args: &maa_framework::custom::ActionArgs,
+    action: &str,
+    total_secs: u64,
+    remaining_secs: u64,
+) {
+    let Some(app) = APP_HANDLE.get() else {
+        return;
+    };
+    let event = ActionProgressEvent {
+        action,
+        task_id: ctx.task_id(),
+        node: args.node_name,
+        total_secs,
+        remaining_secs,
+    };
+    if let Err(e) = app.emit("mxu-action-progress", event) {
+        warn!("[MXU] Failed to emit mxu-action-progress: {}", e);
+    }
+}
+
+/// 任务是否已被请求停止
+fn is_stopping(ctx: &maa_framework::context::Context) -> bool {
+    ctx.tasker().stopping()
+}
+
+/// 分片等待指定时长，期间检查停止请求并每秒发送进度事件
+/// 等待完成返回 true，任务被停止返回 false
+fn sleep_cancellable(
+    ctx: &maa_framework::context::Context,
+    args: &maa_framework::custom::ActionArgs,
+    action: &str,
+    duration: std::time::Duration,
+) -> bool {
+    let total_secs = duration.as_secs();
+    let deadline = std::time::Instant::now() + duration;
+    let mut last_reported = None;
+
+    loop {
+        if is_stopping(ctx) {
+            info!("[{}] Task stopping, wait cancelled", action);
+            return false;
+        }
+
+        let remaining = deadline.saturating_duration_since(std::time::Instant::now());
+        let remaining_secs = remaining.as_secs_f64().ceil() as u64;
+        if last_reported != Some(remaining_secs) {
+            emit_action_progress(ctx, args, action, total_secs, remaining_secs);
+            last_reported = Some(remaining_secs);
+        }
+        if remaining.is_zero() {
+            return true;
+        }
+
+        std::thread::sleep(remaining.min(WAIT_SLICE));
+    }
+}
 
 // ============================================================================
 // MXU_SLEEP Custom Action
@@ -17,7 +104,7 @@ const MXU_SLEEP_ACTION: &str = "MXU_SLEEP_ACTION";
 /// MXU_SLEEP custom action 回调函数
 /// 从 custom_action_param 中读取 sleep_time（秒），执行等待操作
 fn mxu_sleep_action_fn(
-    _ctx: &maa_framework::context::Context,
+    ctx: &maa_framework::context::Context,
     args: &maa_framework::custom::ActionArgs,
 ) -> bool {
     let param_str = args.param;
@@ -37,8 +124,15 @@ fn mxu_sleep_action_fn(
 
     info!("[MXU_SLEEP] Sleeping for {} seconds...", sleep_seconds);
 
-    // 执行睡眠
-    std::thread::sleep(std::time::Duration::from_secs(sleep_seconds));
+    // 执行睡眠（可被停止任务打断）
+    if !sleep_cancellable(
+        ctx,
+        args,
+        "MXU_SLEEP",
+        std::time::Duration::from_secs(sleep_seconds),
+    ) {
+        return false;
+    }
 
     info!("[MXU_SLEEP] Sleep completed");
     true
@@ -51,11 +145,209 @@ fn mxu_sleep_action_fn(
 /// MXU_WAITUNTIL 动作名称常量
 const MXU_WAITUNTIL_ACTION: &str = "MXU_WAITUNTIL_ACTION";
 
+/// MXU_WAITUNTIL 默认最长等待时间（小时），超出则拒绝执行
+const WAITUNTIL_DEFAULT_MAX_HOURS: u64 = 8 * 24;
+
+/// MXU_WAITUNTIL 目标时区
+enum WaitZone {
+    Local,
+    Fixed(chrono::FixedOffset),
+    Named(chrono_tz::Tz),
+}
+
+/// 解析时区：local（默认）、UTC、UTC+8 / +08:00 / GMT-5 等固定偏移，或 IANA 时区名（如 Asia/Shanghai）
+fn parse_wait_zone(s: &str) -> Option<WaitZone> {
+    let s = s.trim();
+    if s.is_empty() || s.eq_ignore_ascii_case("local") {
+        return Some(WaitZone::Local);
+    }
+    if s.eq_ignore_ascii_case("utc") || s.eq_ignore_ascii_case("gmt") || s == "Z" {
+        return chrono::FixedOffset::east_opt(0).map(WaitZone::Fixed);
+    }
+
+    let offset = s
+        .strip_prefix("UTC")
+        .or_else(|| s.strip_prefix("utc"))
+        .or_else(|| s.strip_prefix("GMT"))
+        .or_else(|| s.strip_prefix("gmt"))
+        .unwrap_or(s);
+    if let Some(sign) = offset.chars().next().filter(|c| *c == '+' || *c == '-') {
+        let body = &offset[1..];
+        // 仅接受 ASCII 数字偏移，避免下方按字节切片时落在多字节字符中间
+        if !body.is_ascii() {
+            return None;
+        }
+        let (hours, minutes) = match body.split_once(':') {
+            Some((h, m)) => (h.parse::<i32>().ok()?, m.parse::<i32>().ok()?),
+            None if body.len() == 4 => (body[..2].parse().ok()?, body[2..].parse().ok()?),
+            None => (body.parse().ok()?, 0),
+        };
+        if !(0..=14).contains(&hours) || !(0..60).contains(&minutes) {
+            return None;
+        }
+        let secs = (hours * 3600 + minutes * 60) * if sign == '-' { -1 } else { 1 };
+        return chrono::FixedOffset::east_opt(secs).map(WaitZone::Fixed);
+    }
+
+    s.parse::<chrono_tz::Tz>().ok().map(WaitZone::Named)
+}
+
+/// 解析星期：Mon / Monday / 1（周一）… 7（周日）
+fn parse_weekday(s: &str) -> Option<chrono::Weekday> {
+    let s = s.trim();
+    match s.parse::<u32>() {
+        Ok(n @ 1..=7) => chrono::Weekday::try_from((n - 1) as u8).ok(),
+        Ok(_) => None,
+        Err(_) => s.parse::<chrono::Weekday>().ok(),
+    }
+}
+
+/// 解析 HH:MM 或 HH:MM:SS
+fn parse_clock(s: &str) -> Option<chrono::NaiveTime> {
+    let s = s.trim();
+    chrono::NaiveTime::parse_from_str(s, "%H:%M:%S")
+        .or_else(|_| chrono::NaiveTime::parse_from_str(s, "%H:%M"))
+        .ok()
+}
+
+/// 解析不带时区的日期时间
+fn parse_naive_datetime(s: &str) -> Option<chrono::NaiveDateTime> {
+    const FORMATS: &[&str] = &[
+        "%Y-%m-%dT%H:%M:%S",
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%dT%H:%M",
+        "%Y-%m-%d %H:%M",
+    ];
+    FORMATS
+        .iter()
+        .find_map(|f| chrono::NaiveDateTime::parse_from_str(s.trim(), f).ok())
+}
+
+/// 将本地日期时间按时区转换为 UTC（夏令时重叠取较早者，不存在的时间返回 None）
+fn zoned_to_utc<Tz: chrono::TimeZone>(
+    tz: &Tz,
+    naive: &chrono::NaiveDateTime,
+) -> Option<chrono::DateTime<chrono::Utc>> {
+    tz.from_local_datetime(naive)
+        .earliest()
+        .map(|dt| dt.with_timezone(&chrono::Utc))
+}
+
+/// 计算某时区下、晚于 now 的下一个匹配（可选星期 +）时间点
+fn next_occurrence<Tz: chrono::TimeZone>(
+    tz: &Tz,
+    now: chrono::DateTime<chrono::Utc>,
+    weekday: Option<chrono::Weekday>,
+    time: chrono::NaiveTime,
+) -> Option<chrono::DateTime<chrono::Utc>> {
+    use chrono::Datelike;
+
+    let today = now.with_timezone(tz).date_naive();
+    // 最多向后查找 8 天，覆盖"本周同一天但时间已过"的情况
+    (0..=8)
+        .filter_map(|offset| today.checked_add_days(chrono::Days::new(offset)))
+        .filter(|date| weekday.is_none_or(|w| date.weekday() == w))
+        .filter_map(|date| zoned_to_utc(tz, &date.and_time(time)))
+        .find(|target| *target > now)
+}
+
+/// 按时区解释目标
+fn resolve_in_zone(
+    zone: &WaitZone,
+    now: chrono::DateTime<chrono::Utc>,
+    target: WaitTarget,
+) -> Option<chrono::DateTime<chrono::Utc>> {
+    macro_rules! resolve {
+        ($tz:expr) => {
+            match target {
+                WaitTarget::DateTime(naive) => zoned_to_utc($tz, &naive),
+                WaitTarget::Recurring { weekday, time } => next_occurrence($tz, now, weekday, time),
+            }
+        };
+    }
+
+    match zone {
+        WaitZone::Local => resolve!(&chrono::Local),
+        WaitZone::Fixed(offset) => resolve!(offset),
+        WaitZone::Named(tz) => resolve!(tz),
+    }
+}
+
+/// MXU_WAITUNTIL 等待目标
+enum WaitTarget {
+    /// 指定日期时间
+    DateTime(chrono::NaiveDateTime),
+    /// 下一个匹配的（星期 +）时间
+    Recurring {
+        weekday: Option<chrono::Weekday>,
+        time: chrono::NaiveTime,
+    },
+}
+
+/// 解析 MXU_WAITUNTIL 参数，返回目标 UTC 时间
+fn parse_waituntil_target(
+    json: &serde_json::Value,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Result<chrono::DateTime<chrono::Utc>, String> {
+    let target_time = json
+        .get("target_time")
+        .and_then(|v| v.as_str())
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .ok_or("missing or empty 'target_time'")?;
+
+    // 带偏移的 ISO 8601 / RFC 3339 时间，直接使用其中的偏移
+    if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(target_time) {
+        return Ok(dt.with_timezone(&chrono::Utc));
+    }
+
+    let zone_str = json.get("timezone").and_then(|v| v.as_str()).unwrap_or("");
+    let zone =
+        parse_wait_zone(zone_str).ok_or_else(|| format!("invalid timezone '{}'", zone_str))?;
+
+    let target = if let Some(naive) = parse_naive_datetime(target_time) {
+        WaitTarget::DateTime(naive)
+    } else {
+        // "Mon 04:00" 或 "04:00"（星期也可通过 weekday 字段指定）
+        let (weekday_str, time_str) = match target_time.split_once(char::is_whitespace) {
+            Some((w, t)) => (Some(w), t),
+            None => (None, target_time),
+        };
+        let weekday_str = weekday_str.or_else(|| {
+            json.get("weekday")
+                .and_then(|v| v.as_str())
+                .filter(|s| !s.trim().is_empty())
+        });
+        let weekday = match weekday_str {
+            Some(w) => Some(parse_weekday(w).ok_or_else(|| format!("invalid weekday '{}'", w))?),
+            None => json
+                .get("weekday")
+                .and_then(|v| v.as_u64())
+                .map(|n| {
+                    parse_weekday(&n.to_string()).ok_or_else(|| format!("invalid weekday {}", n))
+                })
+                .transpose()?,
+        };
+        let time = parse_clock(time_str)
+            .ok_or_else(|| format!("invalid time '{}' (expected HH:MM)", time_str))?;
+        WaitTarget::Recurring { weekday, time }
+    };
+
+    resolve_in_zone(&zone, now, target)
+        .ok_or_else(|| format!("'{}' does not exist in the given timezone", target_time))
+}
+
 /// MXU_WAITUNTIL custom action 回调函数
-/// 从 custom_action_param 中读取 target_time（HH:MM 格式），等待到该时间点
-/// 仅支持 24 小时内：若目标时间已过则等待到次日该时间
+/// 从 custom_action_param 中读取 target_time，等待到该时间点。target_time 支持：
+/// - HH:MM / HH:MM:SS：下一个该时刻（已过则为次日）
+/// - Mon 04:00 / Monday 04:00：下一个该星期的该时刻（也可用 weekday 字段指定星期，1=周一）
+/// - 2026-01-01T04:00 / 2026-01-01 04:00：指定日期时间
+/// - 2026-01-01T04:00:00+08:00：带偏移的 ISO 8601 时间（忽略 timezone）
+///
+/// timezone 可为 local（默认）、UTC、UTC+8 / +08:00 等固定偏移，或 IANA 时区名（如 Asia/Shanghai）；
+/// 目标已过或超过 max_wait_hours（默认 8 天）时拒绝执行
 fn mxu_waituntil_action_fn(
-    _ctx: &maa_framework::context::Context,
+    ctx: &maa_framework::context::Context,
     args: &maa_framework::custom::ActionArgs,
 ) -> bool {
     let param_str = args.param;
@@ -66,71 +358,55 @@ fn mxu_waituntil_action_fn(
         return false;
     };
 
-    let Some(target_time) = json
-        .get("target_time")
-        .and_then(|v| v.as_str())
-        .filter(|s| !s.trim().is_empty())
-    else {
-        warn!("[MXU_WAITUNTIL] Missing or empty 'target_time' parameter");
-        return false;
-    };
-    let target_time = target_time.to_string();
-
-    // 解析 HH:MM 格式
-    let parts: Vec<&str> = target_time.split(':').collect();
-    if parts.len() < 2 {
-        warn!("[MXU_WAITUNTIL] Invalid time format: {}", target_time);
-        return false;
-    }
-
-    let target_hour: u32 = match parts[0].parse() {
-        Ok(h) if h < 24 => h,
-        _ => {
-            warn!("[MXU_WAITUNTIL] Invalid hour: {}", parts[0]);
-            return false;
-        }
-    };
-
-    let target_minute: u32 = match parts[1].parse() {
-        Ok(m) if m < 60 => m,
-        _ => {
-            warn!("[MXU_WAITUNTIL] Invalid minute: {}", parts[1]);
+    let now = chrono::Utc::now();
+    let target = match parse_waituntil_target(&json, now) {
+        Ok(target) => target,
+        Err(e) => {
+            warn!("[MXU_WAITUNTIL] Invalid target: {}", e);
             return false;
         }
     };
 
-    // 计算当前时间与目标时间的差值
-    let now = chrono::Local::now();
-    let today_target = now
-        .date_naive()
-        .and_hms_opt(target_hour, target_minute, 0)
-        .unwrap();
-    let today_target = match chrono::Local.from_local_datetime(&today_target).single() {
-        Some(dt) => dt,
-        None => {
-            warn!(
-                "[MXU_WAITUNTIL] Ambiguous or invalid local time for target {:02}:{:02}, falling back to current time",
-                target_hour, target_minute
-            );
-            now
-        }
-    };
+    if target <= now {
+        warn!(
+            "[MXU_WAITUNTIL] Target time {} is in the past",
+            target.to_rfc3339()
+        );
+        return false;
+    }
 
-    let wait_duration = if today_target > now {
-        today_target - now
-    } else {
-        // 目标时间已过，等到明天
-        let tomorrow_target = today_target + chrono::Duration::days(1);
-        tomorrow_target - now
-    };
+    let max_hours = json
+        .get("max_wait_hours")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(WAITUNTIL_DEFAULT_MAX_HOURS);
+    let wait_secs = (target - now).num_seconds().max(0) as u64;
+    if wait_secs > max_hours.saturating_mul(3600) {
+        warn!(
+            "[MXU_WAITUNTIL] Target time {} is too far in the future ({}h > {}h limit)",
+            target.to_rfc3339(),
+            wait_secs / 3600,
+            max_hours
+        );
+        return false;
+    }
 
-    let wait_secs = wait_duration.num_seconds().max(0) as u64;
     info!(
-        "[MXU_WAITUNTIL] Waiting until {}:{:02} ({}s from now)",
-        target_hour, target_minute, wait_secs
+        "[MXU_WAITUNTIL] Waiting until {} (local {}, {}s from now)",
+        target.to_rfc3339(),
+        target
+            .with_timezone(&chrono::Local)
+            .format("%Y-%m-%d %H:%M:%S"),
+        wait_secs
     );
 
-    std::thread::sleep(std::time::Duration::from_secs(wait_secs));
+    if !sleep_cancellable(
+        ctx,
+        args,
+        "MXU_WAITUNTIL",
+        std::time::Duration::from_secs(wait_secs),
+    ) {
+        return false;
+    }
 
     info!("[MXU_WAITUNTIL] Wait completed, target time reached");
     true
@@ -143,10 +419,22 @@ fn mxu_waituntil_action_fn(
 /// MXU_LAUNCH 动作名称常量
 const MXU_LAUNCH_ACTION: &str = "MXU_LAUNCH_ACTION";
 
+/// 等待窗口/进程出现的默认超时
+const LAUNCH_DEFAULT_READY_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(120);
+
 /// MXU_LAUNCH custom action 回调函数
-/// 从 custom_action_param 中读取 program, args, wait_for_exit，启动外部程序
+/// 从 custom_action_param 中读取以下参数，启动外部程序：
+/// - program, args: 程序路径和参数
+/// - cwd: 工作目录（默认为程序所在目录）
+/// - env: 额外环境变量（对象）
+/// - hide_window: 不显示控制台窗口（仅 Windows）
+/// - wait_for_exit: 是否等待进程退出
+/// - timeout_secs: 等待超时（秒），超时后结束进程并返回失败
+/// - expected_exit_codes: 视为成功的退出码列表（不提供时任何退出码都视为成功）
+/// - wait_for_window / wait_for_process: 不等待退出时，等待指定标题的窗口或指定进程出现
+/// - kill_on_stop: 停止任务时是否结束该进程（等待退出时默认 true，否则默认 false）
 fn mxu_launch_action_fn(
-    _ctx: &maa_framework::context::Context,
+    ctx: &maa_framework::context::Context,
     args: &maa_framework::custom::ActionArgs,
 ) -> bool {
     let param_str = args.param;
@@ -179,9 +467,34 @@ fn mxu_launch_action_fn(
         .and_then(|v| v.as_bool())
         .unwrap_or(false);
 
+    let timeout = json
+        .get("timeout_secs")
+        .and_then(|v| v.as_u64())
+        .filter(|s| *s > 0)
+        .map(std::time::Duration::from_secs);
+
+    let expected_exit_codes: Option<Vec<i64>> = json
+        .get("expected_exit_codes")
+        .and_then(|v| v.as_array())
+        .map(|codes| codes.iter().filter_map(|c| c.as_i64()).collect());
+
+    let wait_for_window = json
+        .get("wait_for_window")
+        .and_then(|v| v.as_str())
+        .filter(|s| !s.trim().is_empty());
+    let wait_for_process = json
+        .get("wait_for_process")
+        .and_then(|v| v.as_str())
+        .filter(|s| !s.trim().is_empty());
+
+    let kill_on_stop = json
+        .get("kill_on_stop")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(wait_for_exit);
+
     info!(
-        "[MXU_LAUNCH] Launching: program={}, args={}, wait_for_exit={}",
-        program, args_str, wait_for_exit
+        "[MXU_LAUNCH] Launching: program={}, args={}, wait_for_exit={}, timeout={:?}",
+        program, args_str, wait_for_exit, timeout
     );
 
     let args_vec: Vec<String> = if args_str.trim().is_empty() {
@@ -205,39 +518,253 @@ fn mxu_launch_action_fn(
         cmd.args(&args_vec);
     }
 
-    // 默认使用程序所在目录作为工作目录
-    if let Some(parent) = std::path::Path::new(&program).parent() {
-        if parent.exists() {
-            cmd.current_dir(parent);
+    // 工作目录：优先使用 cwd 参数，默认使用程序所在目录
+    match json
+        .get("cwd")
+        .and_then(|v| v.as_str())
+        .filter(|s| !s.trim().is_empty())
+    {
+        Some(cwd) => {
+            if !std::path::Path::new(cwd).is_dir() {
+                warn!("[MXU_LAUNCH] Working directory not found: {}", cwd);
+                return false;
+            }
+            cmd.current_dir(cwd);
+        }
+        None => {
+            if let Some(parent) = std::path::Path::new(&program).parent() {
+                if parent.exists() {
+                    cmd.current_dir(parent);
+                }
+            }
+        }
+    }
+
+    if let Some(env) = json.get("env").and_then(|v| v.as_object()) {
+        for (key, value) in env {
+            let value = match value {
+                serde_json::Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+            cmd.env(key, value);
+        }
+    }
+
+    #[cfg(windows)]
+    if json
+        .get("hide_window")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        cmd.creation_flags(CREATE_NO_WINDOW);
+    }
+
+    let child = match cmd.spawn() {
+        Ok(child) => child,
+        Err(e) => {
+            log::error!("[MXU_LAUNCH] Failed to spawn program: {}", e);
+            return false;
+        }
+    };
+    info!("[MXU_LAUNCH] Process spawned, pid: {}", child.id());
+
+    let child = std::sync::Arc::new(std::sync::Mutex::new(child));
+    if kill_on_stop {
+        match maa_state().and_then(|state| state.tasker_instance(&ctx.tasker())) {
+            Some(instance_id) => register_launched_process(&instance_id, child.clone()),
+            None => {
+                warn!("[MXU_LAUNCH] Cannot resolve instance, process will not be killed on stop")
+            }
         }
     }
 
     if wait_for_exit {
-        match cmd.status() {
-            Ok(status) => {
+        return wait_launched_exit(ctx, &child, timeout, expected_exit_codes.as_deref());
+    }
+
+    if wait_for_window.is_some() || wait_for_process.is_some() {
+        return wait_launched_ready(ctx, &child, wait_for_window, wait_for_process, timeout);
+    }
+
+    info!("[MXU_LAUNCH] Process spawned (not waiting)");
+    true
+}
+
+/// 轮询等待进程退出，并检查退出码
+fn wait_launched_exit(
+    ctx: &maa_framework::context::Context,
+    child: &std::sync::Mutex<std::process::Child>,
+    timeout: Option<std::time::Duration>,
+    expected_exit_codes: Option<&[i64]>,
+) -> bool {
+    let started = std::time::Instant::now();
+    loop {
+        let status = match child.lock() {
+            Ok(mut c) => c.try_wait(),
+            Err(_) => return false,
+        };
+        match status {
+            Ok(Some(status)) => {
                 let exit_code = status.code().unwrap_or(-1);
                 info!("[MXU_LAUNCH] Process exited with code: {}", exit_code);
-                true
+                return match expected_exit_codes {
+                    Some(codes) if !codes.contains(&(exit_code as i64)) => {
+                        warn!(
+                            "[MXU_LAUNCH] Unexpected exit code {}, expected one of {:?}",
+                            exit_code, codes
+                        );
+                        false
+                    }
+                    _ => true,
+                };
             }
+            Ok(None) => {}
             Err(e) => {
-                log::error!("[MXU_LAUNCH] Failed to run program: {}", e);
-                false
+                log::error!("[MXU_LAUNCH] Failed to wait for process: {}", e);
+                return false;
             }
         }
-    } else {
-        match cmd.spawn() {
-            Ok(_) => {
-                info!("[MXU_LAUNCH] Process spawned (not waiting)");
-                true
+
+        // 任务停止时不再等待（登记过的进程由 maa_stop_task 结束）
+        if is_stopping(ctx) {
+            info!("[MXU_LAUNCH] Task stopping, stop waiting for process exit");
+            return false;
+        }
+
+        if timeout.is_some_and(|t| started.elapsed() >= t) {
+            warn!("[MXU_LAUNCH] Timed out waiting for process exit, killing it");
+            if let Ok(mut c) = child.lock() {
+                let _ = c.kill();
+                let _ = c.wait();
             }
-            Err(e) => {
-                log::error!("[MXU_LAUNCH] Failed to spawn program: {}", e);
-                false
+            return false;
+        }
+        std::thread::sleep(WAIT_SLICE);
+    }
+}
+
+/// 轮询等待目标窗口或进程出现
+fn wait_launched_ready(
+    ctx: &maa_framework::context::Context,
+    child: &std::sync::Mutex<std::process::Child>,
+    window_title: Option<&str>,
+    process_name: Option<&str>,
+    timeout: Option<std::time::Duration>,
+) -> bool {
+    let timeout = timeout.unwrap_or(LAUNCH_DEFAULT_READY_TIMEOUT);
+    let started = std::time::Instant::now();
+    info!(
+        "[MXU_LAUNCH] Waiting for window={:?}, process={:?} (timeout {:?})",
+        window_title, process_name, timeout
+    );
+
+    loop {
+        let window_ready = window_title.is_none_or(|title| {
+            maa_framework::toolkit::Toolkit::find_desktop_windows()
+                .map(|windows| windows.iter().any(|w| w.window_name.contains(title)))
+                .unwrap_or(false)
+        });
+        let process_ready = process_name.is_none_or(crate::mxu_recognitions::is_process_running);
+        if window_ready && process_ready {
+            info!(
+                "[MXU_LAUNCH] Target ready after {}s",
+                started.elapsed().as_secs()
+            );
+            return true;
+        }
+
+        // 启动器本身以非零码退出视为失败（正常退出可能是交给了子进程）
+        if let Ok(mut c) = child.lock() {
+            if let Ok(Some(status)) = c.try_wait() {
+                if !status.success() {
+                    warn!(
+                        "[MXU_LAUNCH] Process exited with {:?} before target appeared",
+                        status.code()
+                    );
+                    return false;
+                }
+            }
+        }
+
+        if is_stopping(ctx) {
+            info!("[MXU_LAUNCH] Task stopping, stop waiting for target");
+            return false;
+        }
+
+        if started.elapsed() >= timeout {
+            warn!("[MXU_LAUNCH] Timed out waiting for target window/process");
+            return false;
+        }
+        std::thread::sleep(WAIT_SLICE);
+    }
+}
+
+/// MXU_LAUNCH 启动的进程（按所属实例登记，停止任务或销毁实例时结束）
+struct LaunchedProcess {
+    instance_id: String,
+    child: std::sync::Arc<std::sync::Mutex<std::process::Child>>,
+}
+
+static LAUNCHED_PROCESSES: std::sync::Mutex<Vec<LaunchedProcess>> =
+    std::sync::Mutex::new(Vec::new());
+
+/// 登记启动的进程，同时清理已退出的记录
+fn register_launched_process(
+    instance_id: &str,
+    child: std::sync::Arc<std::sync::Mutex<std::process::Child>>,
+) {
+    if let Ok(mut processes) = LAUNCHED_PROCESSES.lock() {
+        processes.retain(|p| {
+            p.child
+                .lock()
+                .map(|mut c| matches!(c.try_wait(), Ok(None)))
+                .unwrap_or(false)
+        });
+        processes.push(LaunchedProcess {
+            instance_id: instance_id.to_string(),
+            child,
+        });
+    }
+}
+
+fn kill_launched(processes: Vec<LaunchedProcess>) {
+    for p in processes {
+        if let Ok(mut c) = p.child.lock() {
+            if matches!(c.try_wait(), Ok(None)) {
+                info!("[MXU_LAUNCH] Killing launched process, pid: {}", c.id());
+                let _ = c.kill();
+                let _ = c.wait();
             }
         }
     }
 }
 
+/// 结束该实例的任务中由 MXU_LAUNCH 启动的进程（停止任务、销毁实例时调用）
+pub fn kill_launched_processes(instance_id: &str) {
+    let matched = match LAUNCHED_PROCESSES.lock() {
+        Ok(mut processes) => {
+            let (matched, rest) = processes
+                .drain(..)
+                .partition(|p| p.instance_id == instance_id);
+            *processes = rest;
+            matched
+        }
+        Err(_) => return,
+    };
+    kill_launched(matched);
+}
+
+/// 结束所有由 MXU_LAUNCH 启动并登记的进程（应用退出时调用）
+pub fn kill_all_launched_processes() {
+    let all = match LAUNCHED_PROCESSES.lock() {
+        Ok(mut processes) => processes.drain(..).collect(),
+        Err(_) => return,
+    };
+    kill_launched(all);
+}
+
 // ============================================================================
 // MXU_WEBHOOK Custom Action
 // ============================================================================
@@ -272,15 +799,9 @@ fn mxu_webhook_action_fn(
 
     info!("[MXU_WEBHOOK] Sending GET request to: {}", url);
 
-    let client = match reqwest::blocking::Client::builder()
-        .timeout(std::time::Duration::from_secs(10))
-        .build()
-    {
-        Ok(c) => c,
-        Err(e) => {
-            log::error!("[MXU_WEBHOOK] Failed to build HTTP client: {}", e);
-            return false;
-        }
+    let Some(client) = build_http_client("MXU_WEBHOOK", std::time::Duration::from_secs(10), None)
+    else {
+        return false;
     };
 
     match client.get(&url).send() {
@@ -301,48 +822,255 @@ fn mxu_webhook_action_fn(
     }
 }
 
+/// 构建阻塞 HTTP 客户端（MXU_WEBHOOK / MXU_HTTP_CHECK 共用）
+/// proxy 为代理地址，None 表示直连
+fn build_http_client(
+    tag: &str,
+    timeout: std::time::Duration,
+    proxy: Option<&str>,
+) -> Option<reqwest::blocking::Client> {
+    let mut builder = reqwest::blocking::Client::builder()
+        .user_agent(crate::commands::utils::build_user_agent())
+        .timeout(timeout);
+
+    if let Some(proxy) = proxy {
+        info!("[{}] Using proxy: {}", tag, proxy);
+        match reqwest::Proxy::all(proxy) {
+            Ok(p) => builder = builder.proxy(p),
+            Err(e) => {
+                log::error!("[{}] Invalid proxy {}: {}", tag, proxy, e);
+                return None;
+            }
+        }
+    }
+
+    match builder.build() {
+        Ok(c) => Some(c),
+        Err(e) => {
+            log::error!("[{}] Failed to build HTTP client: {}", tag, e);
+            None
+        }
+    }
+}
+
 // ============================================================================
-// MXU_NOTIFY Custom Action
+// MXU_HTTP_CHECK Custom Action
 // ============================================================================
 
-/// MXU_NOTIFY 动作名称常量
-const MXU_NOTIFY_ACTION: &str = "MXU_NOTIFY_ACTION";
-
-/// MXU_NOTIFY custom action 回调函数
-/// 从 custom_action_param 中读取 title, body，发送系统通知
-fn mxu_notify_action_fn(
+/// MXU_HTTP_CHECK 动作名称常量
+const MXU_HTTP_CHECK_ACTION: &str = "MXU_HTTP_CHECK_ACTION";
+
+/// MXU_HTTP_CHECK 默认超时（秒）
+const HTTP_CHECK_DEFAULT_TIMEOUT_SECS: u64 = 10;
+
+/// MXU_HTTP_CHECK custom action 回调函数
+/// 从 custom_action_param 中读取 url、method（默认 GET）、headers、body、timeout_secs、
+/// use_proxy（默认 true，使用下载代理），发送请求并按以下条件（全部满足）判断成功：
+/// - expect_status: 状态码或状态码数组，默认任意 2xx
+/// - json_path: 响应 JSON 中的路径（如 $.data.maintenance），配合 expect_value 比较，
+///   未提供 expect_value 时要求该值存在且不为 null / false
+/// - body_regex: 响应体需匹配的正则
+///
+/// invert 为 true 时结果取反（例如"维护中则失败"）
+fn mxu_http_check_action_fn(
     _ctx: &maa_framework::context::Context,
     args: &maa_framework::custom::ActionArgs,
 ) -> bool {
     let param_str = args.param;
-    info!("[MXU_NOTIFY] Received param: {}", param_str);
+    info!("[MXU_HTTP_CHECK] Received param: {}", param_str);
 
     let json: serde_json::Value = match serde_json::from_str(param_str) {
         Ok(v) => v,
         Err(e) => {
-            warn!("[MXU_NOTIFY] Failed to parse param JSON: {}", e);
+            warn!("[MXU_HTTP_CHECK] Failed to parse param JSON: {}", e);
             return false;
         }
     };
 
-    let title = json
-        .get("title")
+    let url = match json.get("url").and_then(|v| v.as_str()) {
+        Some(u) if !u.trim().is_empty() => u.trim().to_string(),
+        _ => {
+            warn!("[MXU_HTTP_CHECK] Missing or empty 'url' parameter");
+            return false;
+        }
+    };
+    let method = json
+        .get("method")
         .and_then(|v| v.as_str())
-        .unwrap_or("MXU")
-        .to_string();
-
-    let body = json
-        .get("body")
+        .unwrap_or("GET")
+        .to_uppercase();
+    let Ok(method) = reqwest::Method::from_bytes(method.as_bytes()) else {
+        warn!("[MXU_HTTP_CHECK] Invalid method: {}", method);
+        return false;
+    };
+    let body_regex = match json
+        .get("body_regex")
         .and_then(|v| v.as_str())
-        .unwrap_or("")
-        .to_string();
+        .map(regex::Regex::new)
+        .transpose()
+    {
+        Ok(re) => re,
+        Err(e) => {
+            warn!("[MXU_HTTP_CHECK] Invalid 'body_regex': {}", e);
+            return false;
+        }
+    };
 
-    info!(
-        "[MXU_NOTIFY] Sending notification: title={}, body={}",
-        title, body
+    let timeout = std::time::Duration::from_secs(
+        json.get("timeout_secs")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(HTTP_CHECK_DEFAULT_TIMEOUT_SECS),
     );
-
-    match notify_rust::Notification::new()
+    let proxy = json
+        .get("use_proxy")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(true)
+        .then(crate::commands::download::download_proxy)
+        .flatten();
+    let Some(client) = build_http_client("MXU_HTTP_CHECK", timeout, proxy.as_deref()) else {
+        return false;
+    };
+
+    let mut request = client.request(method.clone(), &url);
+    if let Some(headers) = json.get("headers").and_then(|v| v.as_object()) {
+        for (name, value) in headers {
+            if let Some(value) = value.as_str() {
+                request = request.header(name.as_str(), value);
+            }
+        }
+    }
+    match json.get("body") {
+        Some(serde_json::Value::String(body)) => request = request.body(body.clone()),
+        Some(serde_json::Value::Null) | None => {}
+        Some(body) => request = request.json(body),
+    }
+
+    info!("[MXU_HTTP_CHECK] {} {}", method, url);
+    let (status, body) = match request.send().and_then(|resp| {
+        let status = resp.status().as_u16();
+        resp.text().map(|body| (status, body))
+    }) {
+        Ok(result) => result,
+        Err(e) => {
+            warn!("[MXU_HTTP_CHECK] Request failed: {}", e);
+            return false;
+        }
+    };
+
+    let status_ok = match json.get("expect_status") {
+        Some(serde_json::Value::Array(codes)) => {
+            codes.iter().any(|c| c.as_u64() == Some(status as u64))
+        }
+        Some(code) if code.is_u64() => code.as_u64() == Some(status as u64),
+        _ => (200..300).contains(&status),
+    };
+
+    let json_ok = match json.get("json_path").and_then(|v| v.as_str()) {
+        Some(path) => {
+            let value = serde_json::from_str::<serde_json::Value>(&body)
+                .ok()
+                .and_then(|doc| json_path_lookup(&doc, path).cloned());
+            match (value, json.get("expect_value")) {
+                (Some(value), Some(expected)) => &value == expected,
+                (Some(value), None) => !matches!(
+                    value,
+                    serde_json::Value::Null | serde_json::Value::Bool(false)
+                ),
+                (None, _) => false,
+            }
+        }
+        None => true,
+    };
+
+    let regex_ok = body_regex.as_ref().is_none_or(|re| re.is_match(&body));
+
+    let matched = status_ok && json_ok && regex_ok;
+    let invert = json
+        .get("invert")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+
+    info!(
+        "[MXU_HTTP_CHECK] status {} (ok: {}), json_path ok: {}, regex ok: {}, invert: {}",
+        status, status_ok, json_ok, regex_ok, invert
+    );
+    matched != invert
+}
+
+/// 按简单 JSONPath（$.a.b[0]['c']）查找值
+fn json_path_lookup<'a>(doc: &'a serde_json::Value, path: &str) -> Option<&'a serde_json::Value> {
+    let mut rest = path.trim().strip_prefix('$').unwrap_or(path.trim());
+    let mut current = doc;
+
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix('.') {
+            let end = after.find(['.', '[']).unwrap_or(after.len());
+            let key = &after[..end];
+            if !key.is_empty() {
+                current = current.get(key)?;
+            }
+            rest = &after[end..];
+        } else if let Some(after) = rest.strip_prefix('[') {
+            let end = after.find(']')?;
+            let segment = after[..end].trim();
+            current = match segment.parse::<usize>() {
+                Ok(index) => current.get(index)?,
+                Err(_) => current.get(segment.trim_matches(|c| c == '\'' || c == '"'))?,
+            };
+            rest = &after[end + 1..];
+        } else {
+            // 省略了开头的 "$." 时按字段名处理
+            let end = rest.find(['.', '[']).unwrap_or(rest.len());
+            current = current.get(&rest[..end])?;
+            rest = &rest[end..];
+        }
+    }
+
+    Some(current)
+}
+
+// ============================================================================
+// MXU_NOTIFY Custom Action
+// ============================================================================
+
+/// MXU_NOTIFY 动作名称常量
+const MXU_NOTIFY_ACTION: &str = "MXU_NOTIFY_ACTION";
+
+/// MXU_NOTIFY custom action 回调函数
+/// 从 custom_action_param 中读取 title, body，发送系统通知
+fn mxu_notify_action_fn(
+    _ctx: &maa_framework::context::Context,
+    args: &maa_framework::custom::ActionArgs,
+) -> bool {
+    let param_str = args.param;
+    info!("[MXU_NOTIFY] Received param: {}", param_str);
+
+    let json: serde_json::Value = match serde_json::from_str(param_str) {
+        Ok(v) => v,
+        Err(e) => {
+            warn!("[MXU_NOTIFY] Failed to parse param JSON: {}", e);
+            return false;
+        }
+    };
+
+    let title = json
+        .get("title")
+        .and_then(|v| v.as_str())
+        .unwrap_or("MXU")
+        .to_string();
+
+    let body = json
+        .get("body")
+        .and_then(|v| v.as_str())
+        .unwrap_or("")
+        .to_string();
+
+    info!(
+        "[MXU_NOTIFY] Sending notification: title={}, body={}",
+        title, body
+    );
+
+    match notify_rust::Notification::new()
         .summary(&title)
         .body(&body)
         .show()
@@ -365,8 +1093,19 @@ fn mxu_notify_action_fn(
 /// MXU_KILLPROC 动作名称常量
 const MXU_KILLPROC_ACTION: &str = "MXU_KILLPROC_ACTION";
 
+/// MXU_KILLPROC 默认优雅结束超时（秒）
+const KILLPROC_DEFAULT_GRACEFUL_TIMEOUT_SECS: u64 = 10;
+
 /// MXU_KILLPROC custom action 回调函数
-/// 从 custom_action_param 中读取 kill_self, process_name，结束进程
+/// 从 custom_action_param 中读取 kill_self 及以下匹配条件（可组合，需同时满足），结束匹配的进程：
+/// - process_name: 进程名（不区分大小写）
+/// - exe_path: 可执行文件完整路径
+/// - window_title: 窗口标题正则（匹配拥有该窗口的进程）
+/// - pid_file: 记录 PID 的文件
+///
+/// graceful 为 true 时先请求进程正常退出，graceful_timeout_secs 后仍未退出再强制结束；
+/// dry_run 为 true 时只记录将要结束的进程。kill_self 通过应用正常退出流程关闭 MXU 自身，
+/// 未给出任何匹配条件时默认为 true，与匹配条件同时为 true 时报错
 fn mxu_killproc_action_fn(
     _ctx: &maa_framework::context::Context,
     args: &maa_framework::custom::ActionArgs,
@@ -382,94 +1121,410 @@ fn mxu_killproc_action_fn(
         }
     };
 
-    let kill_self = json
-        .get("kill_self")
+    let get_str = |key: &str| {
+        json.get(key)
+            .and_then(|v| v.as_str())
+            .map(|s| s.trim())
+            .filter(|s| !s.is_empty())
+    };
+    let dry_run = json
+        .get("dry_run")
         .and_then(|v| v.as_bool())
-        .unwrap_or(true);
+        .unwrap_or(false);
 
-    if kill_self {
-        info!("[MXU_KILLPROC] Killing self process");
-        // 获取当前可执行文件名
-        let exe_name = std::env::current_exe()
-            .ok()
-            .and_then(|p| p.file_name().map(|n| n.to_string_lossy().to_string()));
+    // 给出任一匹配条件时默认结束匹配的进程，否则默认结束 MXU 自身
+    let has_match_condition = ["process_name", "exe_path", "window_title", "pid_file"]
+        .iter()
+        .any(|key| get_str(key).is_some());
+    let kill_self = json.get("kill_self").and_then(|v| v.as_bool());
+    if kill_self == Some(true) && has_match_condition {
+        warn!("[MXU_KILLPROC] 'kill_self' cannot be combined with match conditions");
+        return false;
+    }
 
-        if let Some(name) = exe_name {
-            info!("[MXU_KILLPROC] Current exe: {}", name);
-            kill_process_by_name(&name)
-        } else {
-            warn!("[MXU_KILLPROC] Could not determine current exe name, using process::exit");
-            std::process::exit(0);
+    if kill_self.unwrap_or(!has_match_condition) {
+        if dry_run {
+            info!(
+                "[MXU_KILLPROC] Dry run: would exit MXU (pid {})",
+                std::process::id()
+            );
+            return true;
         }
-    } else {
-        let process_name = match json.get("process_name").and_then(|v| v.as_str()) {
-            Some(p) if !p.trim().is_empty() => p.to_string(),
-            _ => {
-                warn!("[MXU_KILLPROC] Missing or empty 'process_name' parameter");
+        return exit_self();
+    }
+
+    let process_name = get_str("process_name");
+    let exe_path = get_str("exe_path");
+    let pid_file = get_str("pid_file");
+    let window_title = match get_str("window_title").map(regex::Regex::new).transpose() {
+        Ok(re) => re,
+        Err(e) => {
+            warn!("[MXU_KILLPROC] Invalid 'window_title' regex: {}", e);
+            return false;
+        }
+    };
+
+    if process_name.is_none() && exe_path.is_none() && pid_file.is_none() && window_title.is_none()
+    {
+        warn!(
+            "[MXU_KILLPROC] No match condition (process_name / exe_path / window_title / pid_file)"
+        );
+        return false;
+    }
+
+    let mut targets = list_processes();
+    if let Some(name) = process_name {
+        targets.retain(|p| p.name.eq_ignore_ascii_case(name));
+    }
+    if let Some(path) = exe_path {
+        let expected = normalize_exe_path(std::path::Path::new(path));
+        targets.retain(|p| {
+            p.exe
+                .as_deref()
+                .is_some_and(|exe| normalize_exe_path(exe) == expected)
+        });
+    }
+    if let Some(pid_file) = pid_file {
+        let pid = match std::fs::read_to_string(pid_file)
+            .map_err(|e| e.to_string())
+            .and_then(|s| s.trim().parse::<u32>().map_err(|e| e.to_string()))
+        {
+            Ok(pid) => pid,
+            Err(e) => {
+                warn!("[MXU_KILLPROC] Cannot read PID from {}: {}", pid_file, e);
                 return false;
             }
         };
+        targets.retain(|p| p.pid == pid);
+    }
+    if let Some(re) = &window_title {
+        let pids: std::collections::HashSet<u32> = list_window_titles()
+            .into_iter()
+            .filter(|(_, title)| re.is_match(title))
+            .map(|(pid, _)| pid)
+            .collect();
+        targets.retain(|p| pids.contains(&p.pid));
+    }
+    // 不允许误伤自身（结束自身请使用 kill_self）
+    targets.retain(|p| p.pid != std::process::id());
 
-        info!("[MXU_KILLPROC] Killing process: {}", process_name);
-        kill_process_by_name(&process_name)
+    if targets.is_empty() {
+        info!("[MXU_KILLPROC] No matching process found");
+        return true;
     }
+
+    for p in &targets {
+        info!(
+            "[MXU_KILLPROC] {}{} (pid {}, exe {:?})",
+            if dry_run {
+                "Dry run: would kill "
+            } else {
+                "Killing "
+            },
+            p.name,
+            p.pid,
+            p.exe
+        );
+    }
+    if dry_run {
+        return true;
+    }
+
+    let graceful = json
+        .get("graceful")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    let timeout = std::time::Duration::from_secs(
+        json.get("graceful_timeout_secs")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(KILLPROC_DEFAULT_GRACEFUL_TIMEOUT_SECS),
+    );
+
+    let pids: Vec<u32> = targets.iter().map(|p| p.pid).collect();
+    terminate_processes(&pids, graceful, timeout)
 }
 
-/// 按名称结束进程
-fn kill_process_by_name(name: &str) -> bool {
-    use std::process::Command;
+/// 通过应用正常退出流程关闭 MXU（触发 agent 子进程清理与日志刷新）
+fn exit_self() -> bool {
+    info!("[MXU_KILLPROC] Exiting MXU");
+    log::logger().flush();
 
+    match APP_HANDLE.get() {
+        Some(app) => {
+            app.exit(0);
+            true
+        }
+        None => {
+            warn!("[MXU_KILLPROC] App handle not initialized, using process::exit");
+            log::logger().flush();
+            std::process::exit(0);
+        }
+    }
+}
+
+/// 进程信息
+pub(crate) struct ProcessInfo {
+    pub pid: u32,
+    /// 进程名（可执行文件名）
+    pub name: String,
+    /// 可执行文件完整路径（无权限读取时为 None）
+    pub exe: Option<std::path::PathBuf>,
+}
+
+/// 规范化可执行文件路径用于比较（Windows 下不区分大小写）
+pub(crate) fn normalize_exe_path(path: &std::path::Path) -> String {
+    let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    let s = path.to_string_lossy().to_string();
+    if cfg!(windows) {
+        s.trim_start_matches(r"\\?\").to_lowercase()
+    } else {
+        s
+    }
+}
+
+/// 列出系统中的所有进程
+pub(crate) fn list_processes() -> Vec<ProcessInfo> {
     #[cfg(windows)]
     {
-        match Command::new("taskkill").args(["/F", "/IM", name]).output() {
-            Ok(output) => {
-                let stdout = String::from_utf8_lossy(&output.stdout);
-                let stderr = String::from_utf8_lossy(&output.stderr);
-                if output.status.success() {
-                    info!("[MXU_KILLPROC] taskkill succeeded: {}", stdout.trim());
-                    true
-                } else {
-                    warn!(
-                        "[MXU_KILLPROC] taskkill failed: stdout={}, stderr={}",
-                        stdout.trim(),
-                        stderr.trim()
-                    );
-                    false
-                }
+        use windows::Win32::Foundation::CloseHandle;
+        use windows::Win32::System::Diagnostics::ToolHelp::{
+            CreateToolhelp32Snapshot, Process32FirstW, Process32NextW, PROCESSENTRY32W,
+            TH32CS_SNAPPROCESS,
+        };
+        use windows::Win32::System::Threading::{
+            OpenProcess, QueryFullProcessImageNameW, PROCESS_NAME_WIN32,
+            PROCESS_QUERY_LIMITED_INFORMATION,
+        };
+
+        let mut out = Vec::new();
+        unsafe {
+            let Ok(snapshot) = CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0) else {
+                warn!("[MXU] Failed to create process snapshot");
+                return out;
+            };
+            let mut entry = PROCESSENTRY32W {
+                dwSize: std::mem::size_of::<PROCESSENTRY32W>() as u32,
+                ..Default::default()
+            };
+            let mut ok = Process32FirstW(snapshot, &mut entry).is_ok();
+            while ok {
+                let len = entry
+                    .szExeFile
+                    .iter()
+                    .position(|c| *c == 0)
+                    .unwrap_or(entry.szExeFile.len());
+                let name = String::from_utf16_lossy(&entry.szExeFile[..len]);
+                let pid = entry.th32ProcessID;
+
+                let exe = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid)
+                    .ok()
+                    .and_then(|handle| {
+                        let mut buf = [0u16; 1024];
+                        let mut size = buf.len() as u32;
+                        let result = QueryFullProcessImageNameW(
+                            handle,
+                            PROCESS_NAME_WIN32,
+                            windows::core::PWSTR(buf.as_mut_ptr()),
+                            &mut size,
+                        );
+                        let _ = CloseHandle(handle);
+                        result.ok().map(|_| {
+                            std::path::PathBuf::from(String::from_utf16_lossy(
+                                &buf[..size as usize],
+                            ))
+                        })
+                    });
+
+                out.push(ProcessInfo { pid, name, exe });
+                ok = Process32NextW(snapshot, &mut entry).is_ok();
             }
-            Err(e) => {
-                log::error!("[MXU_KILLPROC] Failed to execute taskkill: {}", e);
-                false
+            let _ = CloseHandle(snapshot);
+        }
+        out
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        let Ok(entries) = std::fs::read_dir("/proc") else {
+            return Vec::new();
+        };
+        entries
+            .flatten()
+            .filter_map(|entry| {
+                let pid: u32 = entry.file_name().to_str()?.parse().ok()?;
+                let exe = std::fs::read_link(entry.path().join("exe")).ok();
+                let name = exe
+                    .as_ref()
+                    .and_then(|p| p.file_name())
+                    .map(|n| n.to_string_lossy().to_string())
+                    .or_else(|| {
+                        std::fs::read_to_string(entry.path().join("comm"))
+                            .ok()
+                            .map(|s| s.trim().to_string())
+                    })?;
+                Some(ProcessInfo { pid, name, exe })
+            })
+            .collect()
+    }
+
+    #[cfg(not(any(windows, target_os = "linux")))]
+    {
+        // macOS: ps 的 comm 列为可执行文件完整路径
+        let Ok(output) = std::process::Command::new("ps")
+            .args(["-axo", "pid=,comm="])
+            .output()
+        else {
+            return Vec::new();
+        };
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| {
+                let (pid, path) = line.trim().split_once(char::is_whitespace)?;
+                let path = std::path::PathBuf::from(path.trim());
+                Some(ProcessInfo {
+                    pid: pid.parse().ok()?,
+                    name: path.file_name()?.to_string_lossy().to_string(),
+                    exe: path.is_absolute().then_some(path),
+                })
+            })
+            .collect()
+    }
+}
+
+/// 列出所有顶层窗口的 (PID, 标题)
+fn list_window_titles() -> Vec<(u32, String)> {
+    #[cfg(windows)]
+    {
+        use windows::Win32::Foundation::{BOOL, HWND, LPARAM};
+        use windows::Win32::UI::WindowsAndMessaging::{
+            EnumWindows, GetWindowTextW, GetWindowThreadProcessId,
+        };
+
+        unsafe extern "system" fn collect(hwnd: HWND, lparam: LPARAM) -> BOOL {
+            let out = &mut *(lparam.0 as *mut Vec<(u32, String)>);
+            let mut buf = [0u16; 512];
+            let len = GetWindowTextW(hwnd, &mut buf);
+            if len > 0 {
+                let mut pid = 0u32;
+                GetWindowThreadProcessId(hwnd, Some(&mut pid));
+                out.push((pid, String::from_utf16_lossy(&buf[..len as usize])));
             }
+            BOOL(1)
         }
+
+        let mut out: Vec<(u32, String)> = Vec::new();
+        unsafe {
+            if let Err(e) = EnumWindows(Some(collect), LPARAM(&mut out as *mut _ as isize)) {
+                warn!("[MXU] EnumWindows failed: {}", e);
+            }
+        }
+        out
     }
 
     #[cfg(not(windows))]
     {
-        // macOS / Linux: 使用 killall，失败则 fallback 到 pkill
-        match Command::new("killall").arg(name).output() {
-            Ok(output) => {
-                if output.status.success() {
-                    info!("[MXU_KILLPROC] killall succeeded");
-                    true
-                } else {
-                    match Command::new("pkill").arg("-f").arg(name).output() {
-                        Ok(o) if o.status.success() => {
-                            info!("[MXU_KILLPROC] pkill succeeded");
-                            true
-                        }
-                        _ => {
-                            let stderr = String::from_utf8_lossy(&output.stderr);
-                            warn!("[MXU_KILLPROC] killall/pkill failed: {}", stderr.trim());
-                            false
-                        }
-                    }
-                }
-            }
+        // X11 下依赖 wmctrl：<窗口 ID> <桌面> <PID> <主机> <标题>
+        match std::process::Command::new("wmctrl").arg("-lp").output() {
+            Ok(output) => String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .filter_map(|line| {
+                    let fields: Vec<&str> = line.splitn(5, char::is_whitespace).collect();
+                    let pid = fields.get(2)?.trim().parse().ok()?;
+                    Some((pid, fields.get(4).unwrap_or(&"").trim().to_string()))
+                })
+                .collect(),
             Err(e) => {
-                log::error!("[MXU_KILLPROC] Failed to execute killall: {}", e);
-                false
+                warn!("[MXU] Window title matching unavailable (wmctrl): {}", e);
+                Vec::new()
+            }
+        }
+    }
+}
+
+/// 结束进程：graceful 时先请求正常退出并等待 timeout，仍存活的进程再强制结束
+fn terminate_processes(pids: &[u32], graceful: bool, timeout: std::time::Duration) -> bool {
+    let alive = |pids: &[u32]| -> Vec<u32> {
+        let running: std::collections::HashSet<u32> =
+            list_processes().into_iter().map(|p| p.pid).collect();
+        pids.iter()
+            .copied()
+            .filter(|pid| running.contains(pid))
+            .collect()
+    };
+
+    let mut remaining = pids.to_vec();
+    if graceful {
+        for pid in &remaining {
+            signal_process(*pid, false);
+        }
+        let deadline = std::time::Instant::now() + timeout;
+        while !remaining.is_empty() && std::time::Instant::now() < deadline {
+            std::thread::sleep(WAIT_SLICE);
+            remaining = alive(&remaining);
+        }
+        if remaining.is_empty() {
+            info!("[MXU_KILLPROC] All processes exited gracefully");
+            return true;
+        }
+        info!(
+            "[MXU_KILLPROC] {} process(es) still running after {:?}, forcing",
+            remaining.len(),
+            timeout
+        );
+    }
+
+    for pid in &remaining {
+        signal_process(*pid, true);
+    }
+    std::thread::sleep(WAIT_SLICE);
+    let survivors = alive(&remaining);
+    if survivors.is_empty() {
+        true
+    } else {
+        warn!("[MXU_KILLPROC] Failed to kill: {:?}", survivors);
+        false
+    }
+}
+
+/// 向进程发送结束请求（force 为 false 时请求正常退出）
+fn signal_process(pid: u32, force: bool) {
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+        let pid_str = pid.to_string();
+        let mut args = vec!["/PID", pid_str.as_str(), "/T"];
+        if force {
+            args.push("/F");
+        }
+        match std::process::Command::new("taskkill")
+            .args(&args)
+            .creation_flags(CREATE_NO_WINDOW)
+            .output()
+        {
+            Ok(output) if !output.status.success() => {
+                warn!(
+                    "[MXU_KILLPROC] taskkill {} failed: {}",
+                    pid,
+                    String::from_utf8_lossy(&output.stderr).trim()
+                );
             }
+            Ok(_) => {}
+            Err(e) => log::error!("[MXU_KILLPROC] Failed to execute taskkill: {}", e),
+        }
+    }
+
+    #[cfg(not(windows))]
+    {
+        let signal = if force { libc::SIGKILL } else { libc::SIGTERM };
+        if unsafe { libc::kill(pid as libc::pid_t, signal) } != 0 {
+            warn!(
+                "[MXU_KILLPROC] kill({}, {}) failed: {}",
+                pid,
+                signal,
+                std::io::Error::last_os_error()
+            );
         }
     }
 }
@@ -481,10 +1536,38 @@ fn kill_process_by_name(name: &str) -> bool {
 /// MXU_POWER 动作名称常量
 const MXU_POWER_ACTION: &str = "MXU_POWER_ACTION";
 
+/// MXU_POWER 默认倒计时（秒）
+const POWER_DEFAULT_COUNTDOWN_SECS: u64 = 30;
+
+/// 当前 MXU_POWER 倒计时的取消标志（同一时间只允许一个倒计时）
+static POWER_COUNTDOWN: std::sync::Mutex<Option<std::sync::Arc<std::sync::atomic::AtomicBool>>> =
+    std::sync::Mutex::new(None);
+
+/// 取消正在进行的 MXU_POWER 倒计时（托盘菜单 / 前端调用）
+/// 存在倒计时时返回 true
+pub fn cancel_power_countdown() -> bool {
+    let Ok(guard) = POWER_COUNTDOWN.lock() else {
+        return false;
+    };
+    match guard.as_ref() {
+        Some(flag) => {
+            flag.store(true, std::sync::atomic::Ordering::SeqCst);
+            info!("[MXU_POWER] Countdown cancelled by user");
+            true
+        }
+        None => false,
+    }
+}
+
 /// MXU_POWER custom action 回调函数
 /// 从 custom_action_param 中读取 power_action，执行关机/重启/息屏/睡眠操作
+/// - countdown_secs: 执行前倒计时（默认 30 秒），期间发送系统通知，可从托盘或界面取消
+/// - only_if_no_other_running: 仅当其他实例都没有任务在运行时执行
+/// - only_if_idle_minutes: 仅当系统已空闲（无键鼠输入）至少 N 分钟时执行，倒计时期间有输入则取消
+///
+/// 条件不满足或倒计时被取消时跳过电源操作并返回 true，不视为任务失败
 fn mxu_power_action_fn(
-    _ctx: &maa_framework::context::Context,
+    ctx: &maa_framework::context::Context,
     args: &maa_framework::custom::ActionArgs,
 ) -> bool {
     let param_str = args.param;
@@ -502,18 +1585,223 @@ fn mxu_power_action_fn(
         .get("power_action")
         .and_then(|v| v.as_str())
         .unwrap_or("shutdown");
+    let execute: fn() -> bool = match action {
+        "shutdown" => execute_power_shutdown,
+        "restart" => execute_power_restart,
+        "screenoff" => execute_power_screenoff,
+        "sleep" => execute_power_sleep,
+        _ => {
+            warn!("[MXU_POWER] Unknown power action: {}", action);
+            return false;
+        }
+    };
+
+    let countdown_secs = json
+        .get("countdown_secs")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(POWER_DEFAULT_COUNTDOWN_SECS);
+    let only_if_no_other_running = json
+        .get("only_if_no_other_running")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    let idle_minutes = json
+        .get("only_if_idle_minutes")
+        .and_then(|v| v.as_u64())
+        .filter(|m| *m > 0);
+
+    let check_conditions = || -> Result<(), String> {
+        if only_if_no_other_running {
+            let others = other_running_instances(&ctx.tasker());
+            if !others.is_empty() {
+                return Err(format!(
+                    "other instances are running: {}",
+                    others.join(", ")
+                ));
+            }
+        }
+        if let Some(minutes) = idle_minutes {
+            match system_idle_duration() {
+                Some(idle) if idle.as_secs() >= minutes * 60 => {}
+                Some(idle) => {
+                    return Err(format!(
+                        "system idle for {}s, less than {} minute(s)",
+                        idle.as_secs(),
+                        minutes
+                    ))
+                }
+                None => return Err("cannot determine system idle time".to_string()),
+            }
+        }
+        Ok(())
+    };
+
+    if let Err(reason) = check_conditions() {
+        info!("[MXU_POWER] Skipped {}: {}", action, reason);
+        return true;
+    }
+
+    if countdown_secs > 0 {
+        match power_countdown(ctx, args, action, countdown_secs, &check_conditions) {
+            PowerCountdown::Elapsed => {}
+            PowerCountdown::Stopped => return false,
+            PowerCountdown::Cancelled(reason) => {
+                info!("[MXU_POWER] Skipped {}: {}", action, reason);
+                return true;
+            }
+        }
+    }
 
     info!("[MXU_POWER] Executing power action: {}", action);
+    execute()
+}
 
-    match action {
-        "shutdown" => execute_power_shutdown(),
-        "restart" => execute_power_restart(),
-        "screenoff" => execute_power_screenoff(),
-        "sleep" => execute_power_sleep(),
-        _ => {
-            warn!("[MXU_POWER] Unknown power action: {}", action);
-            false
+/// 倒计时结果
+enum PowerCountdown {
+    /// 倒计时结束，继续执行
+    Elapsed,
+    /// 任务被停止
+    Stopped,
+    /// 用户取消或条件不再满足
+    Cancelled(String),
+}
+
+/// 执行倒计时：发送系统通知和进度事件，期间检查取消、停止和执行条件
+fn power_countdown(
+    ctx: &maa_framework::context::Context,
+    args: &maa_framework::custom::ActionArgs,
+    action: &str,
+    countdown_secs: u64,
+    check_conditions: &dyn Fn() -> Result<(), String>,
+) -> PowerCountdown {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    let cancelled = std::sync::Arc::new(AtomicBool::new(false));
+    if let Ok(mut guard) = POWER_COUNTDOWN.lock() {
+        *guard = Some(cancelled.clone());
+    }
+
+    info!(
+        "[MXU_POWER] {} in {}s unless cancelled",
+        action, countdown_secs
+    );
+    if let Err(e) = notify_rust::Notification::new()
+        .summary("MXU")
+        .body(&format!(
+            "{}s 后将执行 {}，可在托盘菜单或界面中取消",
+            countdown_secs, action
+        ))
+        .show()
+    {
+        warn!("[MXU_POWER] Failed to send countdown notification: {}", e);
+    }
+
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(countdown_secs);
+    let mut last_reported = None;
+    let result = loop {
+        if is_stopping(ctx) {
+            info!("[MXU_POWER] Task stopping, countdown cancelled");
+            break PowerCountdown::Stopped;
+        }
+        if cancelled.load(Ordering::SeqCst) {
+            break PowerCountdown::Cancelled("cancelled by user".to_string());
+        }
+
+        let remaining = deadline.saturating_duration_since(std::time::Instant::now());
+        let remaining_secs = remaining.as_secs_f64().ceil() as u64;
+        if last_reported != Some(remaining_secs) {
+            emit_action_progress(ctx, args, "MXU_POWER", countdown_secs, remaining_secs);
+            last_reported = Some(remaining_secs);
+            // 每秒复查一次条件（例如用户回到电脑前）
+            if let Err(reason) = check_conditions() {
+                break PowerCountdown::Cancelled(reason);
+            }
+        }
+        if remaining.is_zero() {
+            break PowerCountdown::Elapsed;
+        }
+
+        std::thread::sleep(remaining.min(WAIT_SLICE));
+    };
+
+    // 提前结束时补发剩余 0 秒，界面据此关闭倒计时提示
+    if !matches!(result, PowerCountdown::Elapsed) {
+        emit_action_progress(ctx, args, "MXU_POWER", countdown_secs, 0);
+    }
+    if let Ok(mut guard) = POWER_COUNTDOWN.lock() {
+        *guard = None;
+    }
+    result
+}
+
+/// 获取全局 MaaState（用于在 MaaFramework 回调中查询实例信息）
+fn maa_state() -> Option<std::sync::Arc<crate::commands::types::MaaState>> {
+    use tauri::Manager;
+
+    APP_HANDLE
+        .get()?
+        .try_state::<std::sync::Arc<crate::commands::types::MaaState>>()
+        .map(|state| state.inner().clone())
+}
+
+/// 列出除当前 tasker 外仍有任务在运行的实例
+/// 不能锁 instances：销毁 tasker 时会持锁等待运行中的任务（可能正是本动作）结束
+fn other_running_instances(current: &maa_framework::tasker::Tasker) -> Vec<String> {
+    maa_state()
+        .map(|state| state.other_running_instances(current))
+        .unwrap_or_default()
+}
+
+/// 获取系统空闲时长（距上次键鼠输入），无法获取时返回 None
+fn system_idle_duration() -> Option<std::time::Duration> {
+    #[cfg(windows)]
+    {
+        use windows::Win32::System::SystemInformation::GetTickCount;
+        use windows::Win32::UI::Input::KeyboardAndMouse::{GetLastInputInfo, LASTINPUTINFO};
+
+        let mut info = LASTINPUTINFO {
+            cbSize: std::mem::size_of::<LASTINPUTINFO>() as u32,
+            dwTime: 0,
+        };
+        unsafe {
+            if !GetLastInputInfo(&mut info).as_bool() {
+                return None;
+            }
+            let idle_ms = GetTickCount().wrapping_sub(info.dwTime);
+            Some(std::time::Duration::from_millis(idle_ms as u64))
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        // HIDIdleTime 单位为纳秒
+        let output = std::process::Command::new("ioreg")
+            .args(["-c", "IOHIDSystem", "-d", "4"])
+            .output()
+            .ok()?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let nanos: u64 = stdout
+            .lines()
+            .find(|line| line.contains("\"HIDIdleTime\""))?
+            .rsplit('=')
+            .next()?
+            .trim()
+            .parse()
+            .ok()?;
+        Some(std::time::Duration::from_nanos(nanos))
+    }
+
+    #[cfg(not(any(windows, target_os = "macos")))]
+    {
+        // X11 下依赖 xprintidle（单位为毫秒）
+        let output = std::process::Command::new("xprintidle").output().ok()?;
+        if !output.status.success() {
+            return None;
         }
+        let millis: u64 = String::from_utf8_lossy(&output.stdout)
+            .trim()
+            .parse()
+            .ok()?;
+        Some(std::time::Duration::from_millis(millis))
     }
 }
 
@@ -723,13 +2011,546 @@ fn execute_power_sleep() -> bool {
     }
 }
 
+// ============================================================================
+// MXU_SCREENSHOT Custom Action
+// ============================================================================
+
+/// MXU_SCREENSHOT 动作名称常量
+const MXU_SCREENSHOT_ACTION: &str = "MXU_SCREENSHOT_ACTION";
+
+/// 截图保存目录（相对于数据目录）
+const SCREENSHOT_DIR: &str = "screenshots";
+/// 默认文件名模板
+const SCREENSHOT_DEFAULT_TEMPLATE: &str = "{date}/{task}_{time}.png";
+/// 默认最多保留的截图数量
+const SCREENSHOT_DEFAULT_MAX_FILES: u64 = 500;
+/// 默认截图保留天数
+const SCREENSHOT_DEFAULT_MAX_DAYS: u64 = 30;
+
+/// MXU_SCREENSHOT custom action 回调函数
+/// 从 custom_action_param 中读取 filename（路径模板）、crop、task、max_files、max_days，
+/// 将当前画面（可裁剪到识别框）保存到数据目录下的 screenshots 文件夹
+///
+/// 模板变量：{instance}（当前 tasker 所属实例 ID）{task} {node} {date} {time} {datetime}
+fn mxu_screenshot_action_fn(
+    ctx: &maa_framework::context::Context,
+    args: &maa_framework::custom::ActionArgs,
+) -> bool {
+    let param_str = args.param;
+    info!("[MXU_SCREENSHOT] Received param: {}", param_str);
+
+    let json: serde_json::Value = match serde_json::from_str(param_str) {
+        Ok(v) => v,
+        Err(e) => {
+            warn!("[MXU_SCREENSHOT] Failed to parse param JSON: {}", e);
+            return false;
+        }
+    };
+
+    let get_str = |key: &str| {
+        json.get(key)
+            .and_then(|v| v.as_str())
+            .map(|s| s.trim())
+            .filter(|s| !s.is_empty())
+    };
+
+    // 模板变量
+    let now = chrono::Local::now();
+    let task = get_str("task").map(|s| s.to_string()).unwrap_or_else(|| {
+        ctx.tasker()
+            .get_task_detail(ctx.task_id())
+            .ok()
+            .flatten()
+            .map(|d| d.entry)
+            .unwrap_or_else(|| args.node_name.to_string())
+    });
+    // 按 tasker 查找所属实例，各实例截图分开保存
+    let instance = maa_state()
+        .and_then(|state| state.tasker_instance(&ctx.tasker()))
+        .unwrap_or_else(|| {
+            warn!("[MXU_SCREENSHOT] Cannot resolve instance of current tasker");
+            "unknown".to_string()
+        });
+    let template = get_str("filename").unwrap_or(SCREENSHOT_DEFAULT_TEMPLATE);
+    let rendered = template
+        .replace("{instance}", &instance)
+        .replace("{task}", &task)
+        .replace("{node}", args.node_name)
+        .replace("{date}", &now.format("%Y-%m-%d").to_string())
+        .replace("{time}", &now.format("%H-%M-%S").to_string())
+        .replace("{datetime}", &now.format("%Y%m%d_%H%M%S").to_string());
+
+    let Some(relative) = sanitize_relative_path(&rendered).and_then(with_png_extension) else {
+        warn!("[MXU_SCREENSHOT] Invalid filename template: {}", rendered);
+        return false;
+    };
+
+    let root = match crate::commands::utils::get_app_data_dir() {
+        Ok(dir) => dir.join(SCREENSHOT_DIR),
+        Err(e) => {
+            warn!("[MXU_SCREENSHOT] Failed to get data dir: {}", e);
+            return false;
+        }
+    };
+    let target = root.join(&relative);
+
+    // 获取当前画面
+    let data = match ctx.tasker().controller().cached_image() {
+        Ok(buffer) => buffer.to_vec().filter(|d| !d.is_empty()),
+        Err(e) => {
+            warn!("[MXU_SCREENSHOT] Failed to get cached image: {}", e);
+            return false;
+        }
+    };
+    let Some(data) = data else {
+        warn!("[MXU_SCREENSHOT] No image data available");
+        return false;
+    };
+
+    let mut image = match image::load_from_memory(&data) {
+        Ok(img) => img,
+        Err(e) => {
+            warn!("[MXU_SCREENSHOT] Failed to decode image: {}", e);
+            return false;
+        }
+    };
+
+    // 裁剪到识别框
+    let crop = json.get("crop").and_then(|v| v.as_bool()).unwrap_or(false);
+    let rect = &args.box_;
+    if crop && rect.width > 0 && rect.height > 0 {
+        let x = rect.x.max(0) as u32;
+        let y = rect.y.max(0) as u32;
+        let width = (rect.width as u32).min(image.width().saturating_sub(x));
+        let height = (rect.height as u32).min(image.height().saturating_sub(y));
+        if width > 0 && height > 0 {
+            image = image.crop_imm(x, y, width, height);
+        }
+    }
+
+    if let Some(parent) = target.parent() {
+        if let Err(e) = std::fs::create_dir_all(parent) {
+            warn!(
+                "[MXU_SCREENSHOT] Failed to create dir {}: {}",
+                parent.display(),
+                e
+            );
+            return false;
+        }
+    }
+    if let Err(e) = image.save_with_format(&target, image::ImageFormat::Png) {
+        warn!(
+            "[MXU_SCREENSHOT] Failed to save {}: {}",
+            target.display(),
+            e
+        );
+        return false;
+    }
+    info!("[MXU_SCREENSHOT] Saved screenshot to {}", target.display());
+
+    // 清理超出保留限制的旧截图
+    let max_files = json
+        .get("max_files")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(SCREENSHOT_DEFAULT_MAX_FILES);
+    let max_days = json
+        .get("max_days")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(SCREENSHOT_DEFAULT_MAX_DAYS);
+    cleanup_screenshots(&root, max_files, max_days);
+
+    true
+}
+
+/// 将模板渲染结果转换为安全的相对路径
+/// - 拒绝绝对路径和 `..` 片段，防止写出沙盒目录（截图目录 / 文件目录）
+/// - 替换文件名中的非法字符
+fn sanitize_relative_path(rendered: &str) -> Option<std::path::PathBuf> {
+    let mut path = std::path::PathBuf::new();
+    for part in rendered.split(['/', '\\']) {
+        let part = part.trim();
+        if part.is_empty() || part == "." {
+            continue;
+        }
+        if part.starts_with("..") || part.contains(':') {
+            return None;
+        }
+        let cleaned: String = part
+            .chars()
+            .map(|c| match c {
+                '<' | '>' | '"' | '|' | '?' | '*' => '_',
+                c if c.is_control() => '_',
+                c => c,
+            })
+            .collect();
+        path.push(cleaned);
+    }
+
+    if path.as_os_str().is_empty() {
+        return None;
+    }
+    Some(path)
+}
+
+/// 强制使用 .png 扩展名
+fn with_png_extension(mut path: std::path::PathBuf) -> Option<std::path::PathBuf> {
+    if path
+        .extension()
+        .is_none_or(|ext| !ext.eq_ignore_ascii_case("png"))
+    {
+        let mut name = path.file_name()?.to_os_string();
+        name.push(".png");
+        path.set_file_name(name);
+    }
+    Some(path)
+}
+
+/// 清理截图目录：删除超过 max_days 天的截图，并只保留最新的 max_files 张（0 表示不限制）
+fn cleanup_screenshots(root: &std::path::Path, max_files: u64, max_days: u64) {
+    fn collect(dir: &std::path::Path, out: &mut Vec<(std::path::PathBuf, std::time::SystemTime)>) {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                collect(&path, out);
+            } else if let Ok(modified) = entry.metadata().and_then(|m| m.modified()) {
+                out.push((path, modified));
+            }
+        }
+    }
+
+    let mut files = Vec::new();
+    collect(root, &mut files);
+    // 最新的在前
+    files.sort_by(|a, b| b.1.cmp(&a.1));
+
+    let now = std::time::SystemTime::now();
+    let max_age = std::time::Duration::from_secs(max_days * 24 * 60 * 60);
+    let mut removed = 0;
+    for (index, (path, modified)) in files.iter().enumerate() {
+        let too_old = max_days > 0 && now.duration_since(*modified).is_ok_and(|age| age > max_age);
+        let too_many = max_files > 0 && index as u64 >= max_files;
+        if (too_old || too_many) && std::fs::remove_file(path).is_ok() {
+            removed += 1;
+        }
+    }
+
+    if removed > 0 {
+        info!("[MXU_SCREENSHOT] Removed {} old screenshot(s)", removed);
+    }
+}
+
+// ============================================================================
+// MXU_FILE / MXU_CLIPBOARD Custom Actions
+// ============================================================================
+
+/// MXU_FILE 动作名称常量
+const MXU_FILE_ACTION: &str = "MXU_FILE_ACTION";
+
+/// MXU_CLIPBOARD 动作名称常量
+const MXU_CLIPBOARD_ACTION: &str = "MXU_CLIPBOARD_ACTION";
+
+/// MXU_FILE 沙盒目录（位于数据目录下）
+const FILE_SANDBOX_DIR: &str = "files";
+
+/// MXU_FILE 读取的最大文件大小
+const FILE_READ_MAX_BYTES: u64 = 1024 * 1024;
+
+/// 渲染写入内容中的变量：{node} {date} {time} {datetime}
+fn render_text_template(template: &str, args: &maa_framework::custom::ActionArgs) -> String {
+    let now = chrono::Local::now();
+    template
+        .replace("{node}", args.node_name)
+        .replace("{date}", &now.format("%Y-%m-%d").to_string())
+        .replace("{time}", &now.format("%H:%M:%S").to_string())
+        .replace("{datetime}", &now.format("%Y-%m-%d %H:%M:%S").to_string())
+}
+
+/// 递归替换 JSON 中字符串里的 {content}
+fn substitute_content(value: &serde_json::Value, content: &str) -> serde_json::Value {
+    match value {
+        serde_json::Value::String(s) => serde_json::Value::String(s.replace("{content}", content)),
+        serde_json::Value::Array(items) => items
+            .iter()
+            .map(|v| substitute_content(v, content))
+            .collect(),
+        serde_json::Value::Object(map) => map
+            .iter()
+            .map(|(k, v)| (k.clone(), substitute_content(v, content)))
+            .collect(),
+        other => other.clone(),
+    }
+}
+
+/// 处理读取结果（MXU_FILE / MXU_CLIPBOARD 共用）
+/// - expect_regex: 内容需匹配的正则，提供 capture 组时以第一个捕获组作为 {content}
+/// - override: 用 {content} 替换后覆盖到当前任务的 pipeline，供后续节点使用
+fn apply_read_result(
+    ctx: &maa_framework::context::Context,
+    tag: &str,
+    json: &serde_json::Value,
+    content: &str,
+) -> bool {
+    let mut content = content.trim_end_matches(['\r', '\n']).to_string();
+
+    if let Some(pattern) = json.get("expect_regex").and_then(|v| v.as_str()) {
+        let re = match regex::Regex::new(pattern) {
+            Ok(re) => re,
+            Err(e) => {
+                warn!("[{}] Invalid 'expect_regex': {}", tag, e);
+                return false;
+            }
+        };
+        let Some(caps) = re.captures(&content) else {
+            info!("[{}] Content does not match {}", tag, pattern);
+            return false;
+        };
+        if let Some(group) = caps.get(1) {
+            content = group.as_str().to_string();
+        }
+    }
+
+    if let Some(template) = json.get("override").filter(|v| v.is_object()) {
+        let pipeline_override = substitute_content(template, &content);
+        if let Err(e) = ctx
+            .tasker()
+            .override_pipeline(ctx.task_id(), &pipeline_override.to_string())
+        {
+            warn!("[{}] Failed to override pipeline: {}", tag, e);
+            return false;
+        }
+        info!("[{}] Pipeline overridden with read content", tag);
+    }
+
+    true
+}
+
+/// 解析 MXU_FILE 的目标路径，确保位于沙盒目录内
+fn resolve_sandbox_path(relative: &str) -> Result<std::path::PathBuf, String> {
+    let relative =
+        sanitize_relative_path(relative).ok_or_else(|| format!("invalid path '{}'", relative))?;
+    let root = crate::commands::utils::get_app_data_dir()
+        .map_err(|e| e.to_string())?
+        .join(FILE_SANDBOX_DIR);
+    std::fs::create_dir_all(&root).map_err(|e| e.to_string())?;
+
+    let target = root.join(&relative);
+    if let Some(parent) = target.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+
+    // 防止通过符号链接逃逸出沙盒目录
+    let canonical_root = root.canonicalize().map_err(|e| e.to_string())?;
+    let canonical_parent = target
+        .parent()
+        .unwrap_or(&root)
+        .canonicalize()
+        .map_err(|e| e.to_string())?;
+    let escaped = !canonical_parent.starts_with(&canonical_root)
+        || std::fs::symlink_metadata(&target).is_ok_and(|m| m.file_type().is_symlink());
+    if escaped {
+        return Err(format!("path escapes sandbox: {}", relative.display()));
+    }
+
+    Ok(target)
+}
+
+/// MXU_FILE custom action 回调函数
+/// 在数据目录的 files 子目录中读写文本文件，从 custom_action_param 中读取：
+/// - mode: write（覆盖）/ append（追加，默认）/ read
+/// - path: 相对于 files 目录的路径，禁止 `..` 和绝对路径
+/// - content: 写入内容，支持 {node} {date} {time} {datetime}
+/// - newline: append 时是否在末尾追加换行，默认 true
+///
+/// read 模式下文件不存在时失败，其余参数见 apply_read_result
+fn mxu_file_action_fn(
+    ctx: &maa_framework::context::Context,
+    args: &maa_framework::custom::ActionArgs,
+) -> bool {
+    use std::io::Write;
+
+    let param_str = args.param;
+    info!("[MXU_FILE] Received param: {}", param_str);
+
+    let json: serde_json::Value = match serde_json::from_str(param_str) {
+        Ok(v) => v,
+        Err(e) => {
+            warn!("[MXU_FILE] Failed to parse param JSON: {}", e);
+            return false;
+        }
+    };
+
+    let Some(path) = json
+        .get("path")
+        .and_then(|v| v.as_str())
+        .filter(|s| !s.trim().is_empty())
+    else {
+        warn!("[MXU_FILE] Missing or empty 'path' parameter");
+        return false;
+    };
+    let target = match resolve_sandbox_path(path) {
+        Ok(target) => target,
+        Err(e) => {
+            warn!("[MXU_FILE] {}", e);
+            return false;
+        }
+    };
+
+    let mode = json
+        .get("mode")
+        .and_then(|v| v.as_str())
+        .unwrap_or("append");
+    let content = render_text_template(
+        json.get("content").and_then(|v| v.as_str()).unwrap_or(""),
+        args,
+    );
+
+    let result = match mode {
+        "write" => std::fs::write(&target, content.as_bytes()),
+        "append" => {
+            let newline = json
+                .get("newline")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(true);
+            std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&target)
+                .and_then(|mut file| {
+                    file.write_all(content.as_bytes())?;
+                    if newline {
+                        file.write_all(b"\n")?;
+                    }
+                    Ok(())
+                })
+        }
+        "read" => {
+            let text = std::fs::metadata(&target).and_then(|meta| {
+                if meta.len() > FILE_READ_MAX_BYTES {
+                    return Err(std::io::Error::other(format!(
+                        "file too large ({} bytes)",
+                        meta.len()
+                    )));
+                }
+                std::fs::read_to_string(&target)
+            });
+            return match text {
+                Ok(text) => {
+                    info!(
+                        "[MXU_FILE] Read {} bytes from {}",
+                        text.len(),
+                        target.display()
+                    );
+                    apply_read_result(ctx, "MXU_FILE", &json, &text)
+                }
+                Err(e) => {
+                    info!("[MXU_FILE] Cannot read {}: {}", target.display(), e);
+                    false
+                }
+            };
+        }
+        _ => {
+            warn!("[MXU_FILE] Unknown mode: {}", mode);
+            return false;
+        }
+    };
+
+    match result {
+        Ok(()) => {
+            info!("[MXU_FILE] {} {}", mode, target.display());
+            true
+        }
+        Err(e) => {
+            warn!("[MXU_FILE] Failed to {} {}: {}", mode, target.display(), e);
+            false
+        }
+    }
+}
+
+/// 常驻的剪贴板句柄
+/// X11 / Wayland 下剪贴板内容由持有者进程提供，句柄销毁后写入的内容随之丢失，因此不能每次用完即释放
+static CLIPBOARD: std::sync::Mutex<Option<arboard::Clipboard>> = std::sync::Mutex::new(None);
+
+/// MXU_CLIPBOARD custom action 回调函数
+/// 从 custom_action_param 中读取 mode（write / read，默认 write）：
+/// - write: 将 text（支持 {node} {date} {time} {datetime}）写入剪贴板
+/// - read: 读取剪贴板文本，剪贴板为空时失败，其余参数见 apply_read_result
+fn mxu_clipboard_action_fn(
+    ctx: &maa_framework::context::Context,
+    args: &maa_framework::custom::ActionArgs,
+) -> bool {
+    let param_str = args.param;
+    info!("[MXU_CLIPBOARD] Received param: {}", param_str);
+
+    let json: serde_json::Value = match serde_json::from_str(param_str) {
+        Ok(v) => v,
+        Err(e) => {
+            warn!("[MXU_CLIPBOARD] Failed to parse param JSON: {}", e);
+            return false;
+        }
+    };
+
+    let Ok(mut guard) = CLIPBOARD.lock() else {
+        warn!("[MXU_CLIPBOARD] Clipboard lock poisoned");
+        return false;
+    };
+    if guard.is_none() {
+        match arboard::Clipboard::new() {
+            Ok(c) => *guard = Some(c),
+            Err(e) => {
+                warn!("[MXU_CLIPBOARD] Failed to open clipboard: {}", e);
+                return false;
+            }
+        }
+    }
+    let Some(clipboard) = guard.as_mut() else {
+        return false;
+    };
+
+    match json.get("mode").and_then(|v| v.as_str()).unwrap_or("write") {
+        "write" => {
+            let text = render_text_template(
+                json.get("text").and_then(|v| v.as_str()).unwrap_or(""),
+                args,
+            );
+            match clipboard.set_text(text) {
+                Ok(()) => {
+                    info!("[MXU_CLIPBOARD] Text written to clipboard");
+                    true
+                }
+                Err(e) => {
+                    warn!("[MXU_CLIPBOARD] Failed to write clipboard: {}", e);
+                    false
+                }
+            }
+        }
+        "read" => match clipboard.get_text() {
+            Ok(text) if !text.is_empty() => apply_read_result(ctx, "MXU_CLIPBOARD", &json, &text),
+            Ok(_) => {
+                info!("[MXU_CLIPBOARD] Clipboard is empty");
+                false
+            }
+            Err(e) => {
+                info!("[MXU_CLIPBOARD] No text in clipboard: {}", e);
+                false
+            }
+        },
+        mode => {
+            warn!("[MXU_CLIPBOARD] Unknown mode: {}", mode);
+            false
+        }
+    }
+}
+
 // ============================================================================
 // 注册入口
 // ============================================================================
 
 /// 为资源注册所有 MXU 内置 custom actions
 /// 在资源创建后调用此函数
-pub fn register_all_mxu_actions(resource: &Resource) -> Result<(), String> {
+pub fn register_all_mxu_actions(resource: &Resource) -> Result<(), MxuError> {
     let mut failed_count = 0;
 
     // 定义一个局部宏打印日志并统计失败
@@ -753,11 +2574,15 @@ pub fn register_all_mxu_actions(resource: &Resource) -> Result<(), String> {
     reg_action!(MXU_NOTIFY_ACTION, mxu_notify_action_fn);
     reg_action!(MXU_KILLPROC_ACTION, mxu_killproc_action_fn);
     reg_action!(MXU_POWER_ACTION, mxu_power_action_fn);
+    reg_action!(MXU_HTTP_CHECK_ACTION, mxu_http_check_action_fn);
+    reg_action!(MXU_SCREENSHOT_ACTION, mxu_screenshot_action_fn);
+    reg_action!(MXU_FILE_ACTION, mxu_file_action_fn);
+    reg_action!(MXU_CLIPBOARD_ACTION, mxu_clipboard_action_fn);
 
     if failed_count > 0 {
-        return Err(format!(
-            "Failed to register {} custom actions",
-            failed_count
+        return Err(MxuError::maa(
+            "register_custom_action",
+            format!("Failed to register {} custom actions", failed_count),
         ));
     }
 
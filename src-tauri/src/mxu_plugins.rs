@@ -0,0 +1,602 @@
+//! MXU 插件 Custom Actions
+//!
+//! 从 exe 目录下的 plugins 文件夹加载项目自带的宿主动作，无需修改 MXU 源码。
+//! 每个插件是一个子目录，包含 plugin.json 清单，支持两种实现方式：
+//! - library: 导出 C ABI 函数的动态库
+//! - command: 外部脚本/程序，参数通过 stdin 以 JSON 传入，退出码 0 表示成功
+//!
+//! ```json
+//! {
+//!   "name": "my-plugin",
+//!   "version": "1.0.0",
+//!   "type": "library",
+//!   "library": { "windows": "my_plugin.dll", "macos": "libmy_plugin.dylib", "linux": "libmy_plugin.so" },
+//!   "actions": [
+//!     { "name": "MY_ACTION", "symbol": "my_action", "description": "...", "param_schema": { ... } }
+//!   ]
+//! }
+//! ```
+//!
+//! 动态库导出函数签名：`int32_t my_action(const char* context_json, const char* param_json)`，
+//! 返回非 0 表示成功；可选导出 `uint32_t mxu_plugin_abi_version(void)`，需返回 PLUGIN_ABI_VERSION。
+//! 导出函数不得向外抛出异常或 panic（跨 FFI 边界展开无法捕获，会导致进程终止）。
+//!
+//! 动作名不能以 `MXU_` 开头，也不能与已注册的动作同名；库文件和 command 的相对路径必须位于插件目录内
+
+use log::{info, warn};
+use std::collections::{HashMap, HashSet};
+use std::ffi::{c_char, CString, OsStr};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use serde::Deserialize;
+
+use maa_framework::context::Context;
+use maa_framework::custom::{ActionArgs, FnAction};
+use maa_framework::resource::Resource;
+
+use crate::commands::error::MxuError;
+use crate::commands::types::PluginActionInfo;
+
+/// 插件目录名（位于 exe 目录下）
+const PLUGIN_DIR: &str = "plugins";
+
+/// 插件清单文件名
+const MANIFEST_FILE: &str = "plugin.json";
+
+/// 当前支持的动态库插件 ABI 版本
+const PLUGIN_ABI_VERSION: u32 = 1;
+
+/// command 插件默认超时（秒）
+const COMMAND_DEFAULT_TIMEOUT_SECS: u64 = 300;
+
+/// MXU 内置动作保留的名称前缀
+const RESERVED_ACTION_PREFIX: &str = "MXU_";
+
+/// 动态库插件的动作函数
+type PluginActionFn = unsafe extern "C" fn(*const c_char, *const c_char) -> i32;
+
+/// 已加载的动态库（加载后常驻，保证函数指针在进程生命周期内有效）
+static LOADED_LIBRARIES: Mutex<Vec<(PathBuf, Arc<libloading::Library>)>> = Mutex::new(Vec::new());
+
+/// 插件清单
+#[derive(Debug, Clone, Deserialize)]
+struct PluginManifest {
+    name: String,
+    #[serde(default)]
+    version: Option<String>,
+    #[serde(rename = "type")]
+    kind: PluginKind,
+    /// library 插件的动态库路径（相对插件目录），可按平台分别指定
+    #[serde(default)]
+    library: Option<LibraryPath>,
+    /// command 插件的程序及参数（相对路径以插件目录为准）
+    #[serde(default)]
+    command: Option<String>,
+    #[serde(default)]
+    args: Vec<String>,
+    #[serde(default)]
+    timeout_secs: Option<u64>,
+    actions: Vec<PluginActionManifest>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum PluginKind {
+    Library,
+    Command,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+enum LibraryPath {
+    Single(String),
+    PerOs {
+        #[serde(default)]
+        windows: Option<String>,
+        #[serde(default)]
+        macos: Option<String>,
+        #[serde(default)]
+        linux: Option<String>,
+    },
+}
+
+impl LibraryPath {
+    fn for_current_os(&self) -> Option<&str> {
+        match self {
+            LibraryPath::Single(path) => Some(path),
+            LibraryPath::PerOs {
+                windows,
+                macos,
+                linux,
+            } => {
+                if cfg!(windows) {
+                    windows.as_deref()
+                } else if cfg!(target_os = "macos") {
+                    macos.as_deref()
+                } else {
+                    linux.as_deref()
+                }
+            }
+        }
+    }
+}
+
+/// 清单中的单个动作
+#[derive(Debug, Clone, Deserialize)]
+struct PluginActionManifest {
+    /// 注册到资源的 custom action 名称
+    name: String,
+    /// library 插件导出的函数名，默认与 name 相同
+    #[serde(default)]
+    symbol: Option<String>,
+    #[serde(default)]
+    description: Option<String>,
+    /// 参数 JSON Schema（仅校验顶层 required 和 properties 的基本类型）
+    #[serde(default)]
+    param_schema: Option<serde_json::Value>,
+}
+
+/// 已解析的插件
+struct LoadedPlugin {
+    dir: PathBuf,
+    manifest: PluginManifest,
+}
+
+/// 插件目录
+fn plugin_root() -> Result<PathBuf, MxuError> {
+    Ok(crate::commands::utils::get_exe_directory()?.join(PLUGIN_DIR))
+}
+
+/// 扫描插件目录并解析所有清单（解析失败的插件跳过）
+fn discover_plugins() -> Vec<LoadedPlugin> {
+    let root = match plugin_root() {
+        Ok(root) => root,
+        Err(e) => {
+            warn!("[Plugin] Failed to resolve plugin dir: {}", e);
+            return Vec::new();
+        }
+    };
+    let Ok(entries) = std::fs::read_dir(&root) else {
+        return Vec::new();
+    };
+
+    let mut dirs: Vec<PathBuf> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.join(MANIFEST_FILE).is_file())
+        .collect();
+    dirs.sort();
+
+    dirs.into_iter()
+        .filter_map(|dir| {
+            let manifest_path = dir.join(MANIFEST_FILE);
+            let manifest = std::fs::read_to_string(&manifest_path)
+                .map_err(|e| e.to_string())
+                .and_then(|s| {
+                    serde_json::from_str::<PluginManifest>(&s).map_err(|e| e.to_string())
+                });
+            match manifest {
+                Ok(manifest) => Some(LoadedPlugin { dir, manifest }),
+                Err(e) => {
+                    warn!(
+                        "[Plugin] Invalid manifest {}: {}",
+                        manifest_path.display(),
+                        e
+                    );
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+/// 加载动态库（同一路径只加载一次）
+fn load_library(path: &Path) -> Result<Arc<libloading::Library>, String> {
+    let mut loaded = LOADED_LIBRARIES.lock().map_err(|e| e.to_string())?;
+    if let Some((_, lib)) = loaded.iter().find(|(p, _)| p == path) {
+        return Ok(lib.clone());
+    }
+
+    let lib = unsafe { libloading::Library::new(path) }.map_err(|e| e.to_string())?;
+    let version = unsafe {
+        lib.get::<unsafe extern "C" fn() -> u32>(b"mxu_plugin_abi_version")
+            .ok()
+            .map(|f| f())
+    };
+    if let Some(version) = version.filter(|v| *v != PLUGIN_ABI_VERSION) {
+        return Err(format!(
+            "unsupported ABI version {} (expected {})",
+            version, PLUGIN_ABI_VERSION
+        ));
+    }
+
+    let lib = Arc::new(lib);
+    loaded.push((path.to_path_buf(), lib.clone()));
+    Ok(lib)
+}
+
+/// 按 param_schema 做基本校验：required 字段存在、properties 的 type 匹配
+fn validate_param(schema: &serde_json::Value, param: &serde_json::Value) -> Result<(), String> {
+    let Some(obj) = param.as_object() else {
+        return Err("param must be a JSON object".to_string());
+    };
+
+    if let Some(required) = schema.get("required").and_then(|v| v.as_array()) {
+        for key in required.iter().filter_map(|k| k.as_str()) {
+            if !obj.contains_key(key) {
+                return Err(format!("missing required field '{}'", key));
+            }
+        }
+    }
+
+    if let Some(props) = schema.get("properties").and_then(|v| v.as_object()) {
+        for (key, prop) in props {
+            let (Some(value), Some(expected)) =
+                (obj.get(key), prop.get("type").and_then(|t| t.as_str()))
+            else {
+                continue;
+            };
+            let ok = match expected {
+                "string" => value.is_string(),
+                "number" => value.is_number(),
+                "integer" => value.is_i64() || value.is_u64(),
+                "boolean" => value.is_boolean(),
+                "array" => value.is_array(),
+                "object" => value.is_object(),
+                "null" => value.is_null(),
+                _ => true,
+            };
+            if !ok {
+                return Err(format!("field '{}' should be {}", key, expected));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// 传给插件的调用上下文
+fn action_context_json(ctx: &Context, args: &ActionArgs, action: &str) -> serde_json::Value {
+    serde_json::json!({
+        "action": action,
+        "node": args.node_name,
+        "task_id": ctx.task_id(),
+        "box": [args.box_.x, args.box_.y, args.box_.width, args.box_.height],
+    })
+}
+
+/// 解析参数并按 schema 校验
+fn checked_param(
+    action: &PluginActionManifest,
+    param_str: &str,
+) -> Result<serde_json::Value, String> {
+    let param: serde_json::Value = if param_str.trim().is_empty() {
+        serde_json::json!({})
+    } else {
+        serde_json::from_str(param_str).map_err(|e| format!("invalid param JSON: {}", e))?
+    };
+    if let Some(schema) = &action.param_schema {
+        validate_param(schema, &param)?;
+    }
+    Ok(param)
+}
+
+/// 调用动态库插件动作
+fn run_library_action(
+    func: PluginActionFn,
+    ctx: &Context,
+    args: &ActionArgs,
+    action: &PluginActionManifest,
+) -> bool {
+    let param = match checked_param(action, args.param) {
+        Ok(param) => param,
+        Err(e) => {
+            warn!("[Plugin] {}: {}", action.name, e);
+            return false;
+        }
+    };
+    let (Ok(context_c), Ok(param_c)) = (
+        CString::new(action_context_json(ctx, args, &action.name).to_string()),
+        CString::new(param.to_string()),
+    ) else {
+        warn!("[Plugin] {}: param contains NUL byte", action.name);
+        return false;
+    };
+
+    info!("[Plugin] Calling library action {}", action.name);
+    // 插件约定不得跨 FFI 边界展开（见模块文档），此处无法也不尝试捕获
+    let code = unsafe { func(context_c.as_ptr(), param_c.as_ptr()) };
+    info!("[Plugin] {} returned {}", action.name, code);
+    code != 0
+}
+
+/// 将清单中的相对路径解析到插件目录内，绝对路径或经 `..` 跳出插件目录时返回 None
+fn resolve_in_plugin_dir(plugin_dir: &Path, rel: &str) -> Option<PathBuf> {
+    let root = plugin_dir.canonicalize().ok()?;
+    let resolved = plugin_dir.join(rel).canonicalize().ok()?;
+    resolved.starts_with(&root).then_some(resolved)
+}
+
+/// 调用 command 插件动作：stdin 传入 {"context": ..., "param": ...}，退出码 0 为成功
+fn run_command_action(
+    plugin_dir: &Path,
+    manifest: &PluginManifest,
+    ctx: &Context,
+    args: &ActionArgs,
+    action: &PluginActionManifest,
+) -> bool {
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    let param = match checked_param(action, args.param) {
+        Ok(param) => param,
+        Err(e) => {
+            warn!("[Plugin] {}: {}", action.name, e);
+            return false;
+        }
+    };
+    let Some(program) = manifest.command.as_deref() else {
+        warn!("[Plugin] {}: manifest has no 'command'", manifest.name);
+        return false;
+    };
+    // 带路径的程序必须位于插件目录内；裸程序名优先使用插件目录内的同名文件，否则交给 PATH 查找（如 python）
+    let is_bare_name = Path::new(program).file_name() == Some(OsStr::new(program));
+    let program = match resolve_in_plugin_dir(plugin_dir, program).filter(|p| p.is_file()) {
+        Some(local) => local,
+        None if is_bare_name => PathBuf::from(program),
+        None => {
+            warn!(
+                "[Plugin] {}: command '{}' is outside the plugin directory",
+                manifest.name, program
+            );
+            return false;
+        }
+    };
+
+    let mut cmd = Command::new(&program);
+    cmd.args(&manifest.args)
+        .current_dir(plugin_dir)
+        .env("MXU_PLUGIN_ACTION", &action.name)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        cmd.creation_flags(CREATE_NO_WINDOW);
+    }
+
+    info!(
+        "[Plugin] Running {} for action {}",
+        program.display(),
+        action.name
+    );
+    let mut child = match cmd.spawn() {
+        Ok(child) => child,
+        Err(e) => {
+            warn!("[Plugin] Failed to spawn {}: {}", program.display(), e);
+            return false;
+        }
+    };
+
+    // 在后台读取输出，避免管道写满阻塞子进程
+    let stdout = child.stdout.take().map(|mut out| {
+        std::thread::spawn(move || {
+            let mut buf = String::new();
+            let _ = std::io::Read::read_to_string(&mut out, &mut buf);
+            buf
+        })
+    });
+    let stderr = child.stderr.take().map(|mut err| {
+        std::thread::spawn(move || {
+            let mut buf = String::new();
+            let _ = std::io::Read::read_to_string(&mut err, &mut buf);
+            buf
+        })
+    });
+
+    // stdin 同样在后台写入：插件不读取 stdin 或先输出再读取时，写入不会阻塞超时与停止检查
+    let input = serde_json::json!({
+        "context": action_context_json(ctx, args, &action.name),
+        "param": param,
+    });
+    if let Some(mut stdin) = child.stdin.take() {
+        std::thread::spawn(move || {
+            if let Err(e) = stdin.write_all(input.to_string().as_bytes()) {
+                warn!("[Plugin] Failed to write stdin: {}", e);
+            }
+        });
+    }
+
+    let timeout = std::time::Duration::from_secs(
+        manifest
+            .timeout_secs
+            .unwrap_or(COMMAND_DEFAULT_TIMEOUT_SECS),
+    );
+    let started = std::time::Instant::now();
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break Some(status),
+            Ok(None) => {}
+            Err(e) => {
+                warn!("[Plugin] Failed to wait for {}: {}", action.name, e);
+                break None;
+            }
+        }
+        if ctx.tasker().stopping() {
+            info!("[Plugin] Task stopping, killing {}", action.name);
+            let _ = child.kill();
+            let _ = child.wait();
+            break None;
+        }
+        if started.elapsed() >= timeout {
+            warn!("[Plugin] {} timed out after {:?}", action.name, timeout);
+            let _ = child.kill();
+            let _ = child.wait();
+            break None;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(100));
+    };
+
+    for (name, handle) in [("stdout", stdout), ("stderr", stderr)] {
+        if let Some(output) = handle.and_then(|h| h.join().ok()) {
+            let output = output.trim();
+            if !output.is_empty() {
+                info!("[Plugin] {} {}: {}", action.name, name, output);
+            }
+        }
+    }
+
+    match status {
+        Some(status) => {
+            info!("[Plugin] {} exited with {}", action.name, status);
+            status.success()
+        }
+        None => false,
+    }
+}
+
+/// 动作名是否使用了 MXU 内置动作的保留前缀
+fn is_reserved_action_name(name: &str) -> bool {
+    name.to_ascii_uppercase()
+        .starts_with(RESERVED_ACTION_PREFIX)
+}
+
+/// 为资源注册 plugins 目录中的所有插件动作
+/// 在 register_all_mxu_actions 之后调用；单个插件加载失败只记录日志，不影响资源创建
+pub fn register_plugin_actions(resource: &Resource) -> Result<(), MxuError> {
+    let mut registered: HashMap<String, String> = HashMap::new();
+    // 已注册的内置动作，插件不能覆盖
+    let builtin: HashSet<String> = resource
+        .custom_action_list()
+        .unwrap_or_else(|e| {
+            warn!("[Plugin] Failed to list registered actions: {}", e);
+            Vec::new()
+        })
+        .into_iter()
+        .collect();
+
+    for plugin in discover_plugins() {
+        let manifest = Arc::new(plugin.manifest);
+        let dir = Arc::new(plugin.dir);
+
+        let library = match manifest.kind {
+            PluginKind::Library => {
+                let Some(rel) = manifest.library.as_ref().and_then(|l| l.for_current_os()) else {
+                    warn!(
+                        "[Plugin] {} has no library for this platform, skipped",
+                        manifest.name
+                    );
+                    continue;
+                };
+                let Some(path) = resolve_in_plugin_dir(&dir, rel) else {
+                    warn!(
+                        "[Plugin] {}: library '{}' not found in the plugin directory, skipped",
+                        manifest.name, rel
+                    );
+                    continue;
+                };
+                match load_library(&path) {
+                    Ok(lib) => Some(lib),
+                    Err(e) => {
+                        warn!("[Plugin] Failed to load {}: {}", manifest.name, e);
+                        continue;
+                    }
+                }
+            }
+            PluginKind::Command => None,
+        };
+
+        for action in &manifest.actions {
+            let action = action.clone();
+            let name = action.name.clone();
+            if is_reserved_action_name(&name) || builtin.contains(&name) {
+                warn!(
+                    "[Plugin] Action {} from {} skipped, name is reserved by a built-in action",
+                    name, manifest.name
+                );
+                continue;
+            }
+            // 同名动作以先加载的插件为准
+            if let Some(owner) = registered.get(&name) {
+                warn!(
+                    "[Plugin] Action {} from {} skipped, already provided by {}",
+                    name, manifest.name, owner
+                );
+                continue;
+            }
+            let result = match &library {
+                Some(lib) => {
+                    let symbol = action.symbol.clone().unwrap_or_else(|| action.name.clone());
+                    let func = match unsafe { lib.get::<PluginActionFn>(symbol.as_bytes()) } {
+                        Ok(f) => *f,
+                        Err(e) => {
+                            warn!("[Plugin] {}: symbol {} not found: {}", name, symbol, e);
+                            continue;
+                        }
+                    };
+                    resource.register_custom_action(
+                        &name,
+                        Box::new(FnAction::new(move |ctx: &Context, args: &ActionArgs| {
+                            run_library_action(func, ctx, args, &action)
+                        })),
+                    )
+                }
+                None => {
+                    let manifest = manifest.clone();
+                    let dir = dir.clone();
+                    resource.register_custom_action(
+                        &name,
+                        Box::new(FnAction::new(move |ctx: &Context, args: &ActionArgs| {
+                            run_command_action(&dir, &manifest, ctx, args, &action)
+                        })),
+                    )
+                }
+            };
+
+            match result {
+                Ok(_) => {
+                    info!(
+                        "[Plugin] Custom action {} registered from {}",
+                        name, manifest.name
+                    );
+                    registered.insert(name, manifest.name.clone());
+                }
+                Err(e) => warn!("[Plugin] Failed to register {}: {:?}", name, e),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// 列出所有插件提供的动作（供前端展示参数说明）
+pub fn list_plugin_actions() -> Vec<PluginActionInfo> {
+    let mut seen: HashSet<String> = HashSet::new();
+    discover_plugins()
+        .into_iter()
+        .flat_map(|plugin| {
+            let manifest = plugin.manifest;
+            let kind = match manifest.kind {
+                PluginKind::Library => "library",
+                PluginKind::Command => "command",
+            };
+            manifest
+                .actions
+                .into_iter()
+                .map(|action| PluginActionInfo {
+                    plugin: manifest.name.clone(),
+                    version: manifest.version.clone(),
+                    kind: kind.to_string(),
+                    name: action.name,
+                    description: action.description,
+                    param_schema: action.param_schema,
+                })
+                .collect::<Vec<_>>()
+        })
+        // 跳过保留名称；同名动作以先加载的插件为准，与注册时一致
+        .filter(|info| !is_reserved_action_name(&info.name) && seen.insert(info.name.clone()))
+        .collect()
+}
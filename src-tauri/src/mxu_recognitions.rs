@@ -0,0 +1,313 @@
+//! MXU 内置 Custom Recognitions
+//!
+//! 提供基于宿主机状态的自定义识别（时间段、文件修改、上一个任务结果、进程是否运行），
+//! 让 pipeline 无需 agent 即可根据这些条件分支
+
+use chrono::{Datelike, Timelike};
+use log::{info, warn};
+use maa_framework::common::Rect;
+use maa_framework::custom::{FnRecognition, RecognitionResult};
+use maa_framework::resource::Resource;
+use maa_framework::MaaStatus;
+
+use crate::commands::error::MxuError;
+
+/// 向前查找上一个任务时最多检查的任务 ID 数
+const PREVIOUS_TASK_LOOKBACK: i64 = 64;
+
+/// 解析 custom_recognition_param
+fn parse_param(tag: &str, param_str: &str) -> Option<serde_json::Value> {
+    match serde_json::from_str::<serde_json::Value>(param_str) {
+        Ok(v) => Some(v),
+        Err(e) => {
+            warn!("[{}] Failed to parse param JSON: {}", tag, e);
+            None
+        }
+    }
+}
+
+/// 条件满足时返回命中结果（宿主机状态与画面无关，命中框为空）
+fn hit_if(matched: bool, detail: serde_json::Value) -> Option<RecognitionResult> {
+    matched.then(|| RecognitionResult {
+        box_: Rect::default(),
+        detail: detail.to_string(),
+    })
+}
+
+/// 解析 HH:MM 为当天分钟数
+fn parse_minutes(s: &str) -> Option<u32> {
+    let (h, m) = s.trim().split_once(':')?;
+    let h: u32 = h.parse().ok().filter(|h| *h < 24)?;
+    let m: u32 = m.parse().ok().filter(|m| *m < 60)?;
+    Some(h * 60 + m)
+}
+
+// ============================================================================
+// MXU_TIMEWINDOW_RECO Custom Recognition
+// ============================================================================
+
+/// MXU_TIMEWINDOW_RECO 识别名称常量
+const MXU_TIMEWINDOW_RECO: &str = "MXU_TIMEWINDOW_RECO";
+
+/// MXU_TIMEWINDOW_RECO custom recognition 回调函数
+/// 从 custom_recognition_param 中读取 start、end（HH:MM）和可选的 weekdays（1=周一 … 7=周日），
+/// 当前本地时间处于 [start, end) 内时命中；end 早于 start 时视为跨越午夜
+fn mxu_timewindow_reco_fn(
+    _ctx: &maa_framework::context::Context,
+    args: &maa_framework::custom::RecognitionArgs,
+) -> Option<RecognitionResult> {
+    let json = parse_param("MXU_TIMEWINDOW_RECO", args.param)?;
+
+    let (Some(start), Some(end)) = (
+        json.get("start")
+            .and_then(|v| v.as_str())
+            .and_then(parse_minutes),
+        json.get("end")
+            .and_then(|v| v.as_str())
+            .and_then(parse_minutes),
+    ) else {
+        warn!("[MXU_TIMEWINDOW_RECO] Missing or invalid 'start'/'end' (expected HH:MM)");
+        return None;
+    };
+
+    let now = chrono::Local::now();
+    let minutes = now.hour() * 60 + now.minute();
+    let weekday = now.weekday().number_from_monday();
+
+    let in_window = if start <= end {
+        minutes >= start && minutes < end
+    } else {
+        minutes >= start || minutes < end
+    };
+    let weekday_ok = match json.get("weekdays").and_then(|v| v.as_array()) {
+        Some(days) => days.iter().any(|d| d.as_u64() == Some(weekday as u64)),
+        None => true,
+    };
+
+    info!(
+        "[MXU_TIMEWINDOW_RECO] now {:02}:{:02} (weekday {}), in window: {}, weekday ok: {}",
+        now.hour(),
+        now.minute(),
+        weekday,
+        in_window,
+        weekday_ok
+    );
+    hit_if(
+        in_window && weekday_ok,
+        serde_json::json!({ "time": now.format("%H:%M").to_string(), "weekday": weekday }),
+    )
+}
+
+// ============================================================================
+// MXU_FILEMODIFIED_RECO Custom Recognition
+// ============================================================================
+
+/// MXU_FILEMODIFIED_RECO 识别名称常量
+const MXU_FILEMODIFIED_RECO: &str = "MXU_FILEMODIFIED_RECO";
+
+/// MXU_FILEMODIFIED_RECO custom recognition 回调函数
+/// 从 custom_recognition_param 中读取 path 和可选的 within_hours，
+/// 文件今天（本地日期）被修改过时命中；提供 within_hours 时改为判断最近 N 小时内是否修改
+fn mxu_filemodified_reco_fn(
+    _ctx: &maa_framework::context::Context,
+    args: &maa_framework::custom::RecognitionArgs,
+) -> Option<RecognitionResult> {
+    let json = parse_param("MXU_FILEMODIFIED_RECO", args.param)?;
+
+    let Some(path) = json
+        .get("path")
+        .and_then(|v| v.as_str())
+        .filter(|s| !s.trim().is_empty())
+    else {
+        warn!("[MXU_FILEMODIFIED_RECO] Missing or empty 'path' parameter");
+        return None;
+    };
+
+    let modified = match std::fs::metadata(path).and_then(|m| m.modified()) {
+        Ok(t) => chrono::DateTime::<chrono::Local>::from(t),
+        Err(e) => {
+            info!(
+                "[MXU_FILEMODIFIED_RECO] Cannot read mtime of {}: {}",
+                path, e
+            );
+            return None;
+        }
+    };
+
+    let now = chrono::Local::now();
+    let matched = match json.get("within_hours").and_then(|v| v.as_f64()) {
+        Some(hours) => {
+            let age = now.signed_duration_since(modified);
+            age.num_seconds() >= 0 && (age.num_seconds() as f64) <= hours * 3600.0
+        }
+        None => modified.date_naive() == now.date_naive(),
+    };
+
+    info!(
+        "[MXU_FILEMODIFIED_RECO] {} modified at {}, matched: {}",
+        path,
+        modified.format("%Y-%m-%d %H:%M:%S"),
+        matched
+    );
+    hit_if(
+        matched,
+        serde_json::json!({ "path": path, "modified": modified.to_rfc3339() }),
+    )
+}
+
+// ============================================================================
+// MXU_TASKSUCCEEDED_RECO Custom Recognition
+// ============================================================================
+
+/// MXU_TASKSUCCEEDED_RECO 识别名称常量
+const MXU_TASKSUCCEEDED_RECO: &str = "MXU_TASKSUCCEEDED_RECO";
+
+/// MXU_TASKSUCCEEDED_RECO custom recognition 回调函数
+/// 查找同一 tasker 中当前任务之前最近的一个任务（可用 entry 指定入口），该任务成功时命中
+fn mxu_tasksucceeded_reco_fn(
+    ctx: &maa_framework::context::Context,
+    args: &maa_framework::custom::RecognitionArgs,
+) -> Option<RecognitionResult> {
+    let json = parse_param("MXU_TASKSUCCEEDED_RECO", args.param)?;
+    let entry = json
+        .get("entry")
+        .and_then(|v| v.as_str())
+        .filter(|s| !s.trim().is_empty());
+
+    let tasker = ctx.tasker();
+    let current = ctx.task_id();
+
+    let previous = (1..=PREVIOUS_TASK_LOOKBACK)
+        .map(|offset| current - offset)
+        .take_while(|id| *id > 0)
+        .filter_map(|id| tasker.get_task_detail(id).ok().flatten())
+        .find(|detail| entry.is_none_or(|e| detail.entry == e));
+
+    let Some(detail) = previous else {
+        info!(
+            "[MXU_TASKSUCCEEDED_RECO] No previous task found (entry: {:?})",
+            entry
+        );
+        return None;
+    };
+
+    let succeeded = detail.status == MaaStatus::SUCCEEDED;
+    info!(
+        "[MXU_TASKSUCCEEDED_RECO] Previous task {} ({}) succeeded: {}",
+        detail.task_id, detail.entry, succeeded
+    );
+    hit_if(
+        succeeded,
+        serde_json::json!({ "task_id": detail.task_id, "entry": detail.entry }),
+    )
+}
+
+// ============================================================================
+// MXU_PROCESSRUNNING_RECO Custom Recognition
+// ============================================================================
+
+/// MXU_PROCESSRUNNING_RECO 识别名称常量
+const MXU_PROCESSRUNNING_RECO: &str = "MXU_PROCESSRUNNING_RECO";
+
+/// MXU_PROCESSRUNNING_RECO custom recognition 回调函数
+/// 从 custom_recognition_param 中读取 process_name，该进程正在运行时命中
+fn mxu_processrunning_reco_fn(
+    _ctx: &maa_framework::context::Context,
+    args: &maa_framework::custom::RecognitionArgs,
+) -> Option<RecognitionResult> {
+    let json = parse_param("MXU_PROCESSRUNNING_RECO", args.param)?;
+
+    let Some(process_name) = json
+        .get("process_name")
+        .and_then(|v| v.as_str())
+        .filter(|s| !s.trim().is_empty())
+    else {
+        warn!("[MXU_PROCESSRUNNING_RECO] Missing or empty 'process_name' parameter");
+        return None;
+    };
+
+    let running = is_process_running(process_name);
+    info!(
+        "[MXU_PROCESSRUNNING_RECO] {} running: {}",
+        process_name, running
+    );
+    hit_if(running, serde_json::json!({ "process_name": process_name }))
+}
+
+/// 按进程名检查进程是否在运行
+pub(crate) fn is_process_running(name: &str) -> bool {
+    use std::process::Command;
+
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+        let filter = format!("IMAGENAME eq {}", name);
+        match Command::new("tasklist")
+            .args(["/FI", &filter, "/NH", "/FO", "CSV"])
+            .creation_flags(CREATE_NO_WINDOW)
+            .output()
+        {
+            Ok(output) => String::from_utf8_lossy(&output.stdout)
+                .to_lowercase()
+                .contains(&format!("\"{}\"", name.to_lowercase())),
+            Err(e) => {
+                log::error!(
+                    "[MXU_PROCESSRUNNING_RECO] Failed to execute tasklist: {}",
+                    e
+                );
+                false
+            }
+        }
+    }
+
+    #[cfg(not(windows))]
+    {
+        match Command::new("pgrep").arg("-x").arg(name).output() {
+            Ok(output) => output.status.success(),
+            Err(e) => {
+                log::error!("[MXU_PROCESSRUNNING_RECO] Failed to execute pgrep: {}", e);
+                false
+            }
+        }
+    }
+}
+
+// ============================================================================
+// 注册入口
+// ============================================================================
+
+/// 为资源注册所有 MXU 内置 custom recognitions
+/// 在资源创建后调用此函数
+pub fn register_all_mxu_recognitions(resource: &Resource) -> Result<(), MxuError> {
+    let mut failed_count = 0;
+
+    // 定义一个局部宏打印日志并统计失败
+    macro_rules! reg_reco {
+        ($name:expr, $fn_name:expr) => {
+            if let Err(e) =
+                resource.register_custom_recognition($name, Box::new(FnRecognition::new($fn_name)))
+            {
+                warn!("[MXU] Failed to register {}: {:?}", $name, e);
+                failed_count += 1;
+            } else {
+                info!("[MXU] Custom recognition {} registered successfully", $name);
+            }
+        };
+    }
+
+    reg_reco!(MXU_TIMEWINDOW_RECO, mxu_timewindow_reco_fn);
+    reg_reco!(MXU_FILEMODIFIED_RECO, mxu_filemodified_reco_fn);
+    reg_reco!(MXU_TASKSUCCEEDED_RECO, mxu_tasksucceeded_reco_fn);
+    reg_reco!(MXU_PROCESSRUNNING_RECO, mxu_processrunning_reco_fn);
+
+    if failed_count > 0 {
+        return Err(MxuError::maa(
+            "register_custom_recognition",
+            format!("Failed to register {} custom recognitions", failed_count),
+        ));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,347 @@
+//! 离线回放控制器
+//!
+//! 基于 MaaFramework 自定义控制器，从文件夹读取截图作为画面（按文件名顺序，或由脚本决定），
+//! 并记录收到的点击/滑动等输入，用于在没有设备的环境（如 CI）中回归测试 pipeline
+
+use log::{debug, info, warn};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use serde::Deserialize;
+use serde_json::json;
+use tauri::{AppHandle, Emitter};
+
+use maa_framework::buffer::ImageBuffer;
+use maa_framework::custom_controller::CustomControllerCallback;
+
+use crate::commands::error::MxuError;
+use crate::commands::types::ReplayInputRecord;
+
+/// 支持的截图文件扩展名
+const IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "bmp"];
+
+/// 回放输入记录（在控制器和实例之间共享）
+pub type ReplayInputLog = Arc<Mutex<Vec<ReplayInputRecord>>>;
+
+/// 回放脚本
+///
+/// ```json
+/// {
+///   "frames": [
+///     { "image": "main.png", "transitions": [{ "region": [100, 200, 80, 40], "goto": 1 }] },
+///     { "image": "battle.png", "advance_after": 3 }
+///   ]
+/// }
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+struct ReplayScript {
+    frames: Vec<ReplayFrame>,
+}
+
+/// 脚本中的单帧
+#[derive(Debug, Clone, Deserialize)]
+struct ReplayFrame {
+    /// 相对于图片目录的文件名
+    image: String,
+    /// 收到输入时的跳转规则（按顺序匹配第一条）
+    #[serde(default)]
+    transitions: Vec<ReplayTransition>,
+    /// 截图若干次后自动前进到下一帧
+    #[serde(default)]
+    advance_after: Option<u32>,
+}
+
+/// 输入触发的帧跳转
+#[derive(Debug, Clone, Deserialize)]
+struct ReplayTransition {
+    /// 输入坐标需落在该区域 [x, y, width, height] 内，省略时任意输入都匹配
+    #[serde(default)]
+    region: Option<[i32; 4]>,
+    /// 目标帧下标
+    goto: usize,
+}
+
+/// 回放进度
+struct ReplayCursor {
+    /// 下一次截图返回的帧下标
+    index: usize,
+    /// 当前帧已被截图的次数
+    served: u32,
+    /// 最近一次截图返回的帧下标（输入针对的是该帧，尚未截图时为 None）
+    last_served: Option<usize>,
+}
+
+/// 离线回放控制器
+pub struct ReplayController {
+    instance_id: String,
+    image_dir: PathBuf,
+    frames: Vec<ReplayFrame>,
+    /// 无脚本时按顺序播放，是否在末尾循环
+    loop_images: bool,
+    scripted: bool,
+    cursor: Mutex<ReplayCursor>,
+    inputs: ReplayInputLog,
+    started_at: Instant,
+    app: AppHandle,
+}
+
+impl ReplayController {
+    /// 创建回放控制器
+    /// - `script`: 回放脚本路径（JSON），为空时按文件名顺序播放 image_dir 中的图片
+    pub fn new(
+        app: AppHandle,
+        instance_id: &str,
+        image_dir: &Path,
+        script: Option<&Path>,
+        loop_images: bool,
+    ) -> Result<Self, MxuError> {
+        if !image_dir.is_dir() {
+            return Err(MxuError::invalid_param(
+                "image_dir",
+                format!("directory not found: {}", image_dir.display()),
+            ));
+        }
+
+        let (frames, scripted) = match script {
+            Some(script_path) => {
+                let content = std::fs::read_to_string(script_path)
+                    .map_err(|e| MxuError::io("read replay script", script_path, e))?;
+                let script: ReplayScript = serde_json::from_str(&content)
+                    .map_err(|e| MxuError::invalid_param("script", e))?;
+                for (i, frame) in script.frames.iter().enumerate() {
+                    if frame
+                        .transitions
+                        .iter()
+                        .any(|t| t.goto >= script.frames.len())
+                    {
+                        return Err(MxuError::invalid_param(
+                            "script",
+                            format!("frame #{} has a transition out of range", i),
+                        ));
+                    }
+                }
+                (script.frames, true)
+            }
+            None => (list_images(image_dir)?, false),
+        };
+
+        if frames.is_empty() {
+            return Err(MxuError::invalid_param(
+                "image_dir",
+                format!("no images found in {}", image_dir.display()),
+            ));
+        }
+
+        info!(
+            "[Replay] Created replay controller for {}: {} frame(s), scripted: {}",
+            instance_id,
+            frames.len(),
+            scripted
+        );
+
+        Ok(Self {
+            instance_id: instance_id.to_string(),
+            image_dir: image_dir.to_path_buf(),
+            frames,
+            loop_images,
+            scripted,
+            cursor: Mutex::new(ReplayCursor {
+                index: 0,
+                served: 0,
+                last_served: None,
+            }),
+            inputs: Arc::new(Mutex::new(Vec::new())),
+            started_at: Instant::now(),
+            app,
+        })
+    }
+
+    /// 输入记录句柄
+    pub fn input_log(&self) -> ReplayInputLog {
+        self.inputs.clone()
+    }
+
+    /// 记录输入并按脚本跳转
+    /// 输入针对的是最近一次截图返回的帧（截图后 cursor.index 可能已前进到下一帧）
+    fn record(&self, kind: &str, point: Option<(i32, i32)>, params: serde_json::Value) -> bool {
+        let Ok(mut cursor) = self.cursor.lock() else {
+            return false;
+        };
+        let seen = cursor.last_served.unwrap_or(cursor.index);
+        let frame = self.frames.get(seen).map(|f| f.image.clone());
+
+        if self.scripted {
+            let transition =
+                self.frames[seen]
+                    .transitions
+                    .iter()
+                    .find(|t| match (t.region, point) {
+                        (None, _) => true,
+                        (Some([x, y, w, h]), Some((px, py))) => {
+                            px >= x && px < x + w && py >= y && py < y + h
+                        }
+                        (Some(_), None) => false,
+                    });
+            if let Some(t) = transition {
+                debug!("[Replay] {} on frame #{} -> frame #{}", kind, seen, t.goto);
+                cursor.index = t.goto;
+                cursor.served = 0;
+            }
+        }
+        drop(cursor);
+
+        let Ok(mut inputs) = self.inputs.lock() else {
+            return false;
+        };
+        let record = ReplayInputRecord {
+            seq: inputs.len() as u64,
+            kind: kind.to_string(),
+            params,
+            frame,
+            elapsed_ms: self.started_at.elapsed().as_millis() as u64,
+        };
+        if let Err(e) = self.app.emit(
+            "maa-replay-input",
+            json!({ "instance_id": self.instance_id, "input": record }),
+        ) {
+            warn!("[Replay] Failed to emit maa-replay-input: {}", e);
+        }
+        inputs.push(record);
+        true
+    }
+}
+
+/// 按文件名顺序列出目录中的图片
+fn list_images(dir: &Path) -> Result<Vec<ReplayFrame>, MxuError> {
+    let entries = std::fs::read_dir(dir).map_err(|e| MxuError::io("read dir", dir, e))?;
+    let mut names: Vec<String> = entries
+        .flatten()
+        .filter(|e| e.path().is_file())
+        .filter_map(|e| e.file_name().to_str().map(|s| s.to_string()))
+        .filter(|name| {
+            Path::new(name)
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| IMAGE_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+        })
+        .collect();
+    names.sort();
+
+    Ok(names
+        .into_iter()
+        .map(|image| ReplayFrame {
+            image,
+            transitions: Vec::new(),
+            advance_after: None,
+        })
+        .collect())
+}
+
+impl CustomControllerCallback for ReplayController {
+    fn connect(&self) -> bool {
+        true
+    }
+
+    fn request_uuid(&self) -> Option<String> {
+        Some(format!("replay:{}", self.image_dir.display()))
+    }
+
+    fn start_app(&self, intent: &str) -> bool {
+        self.record("start_app", None, json!({ "intent": intent }))
+    }
+
+    fn stop_app(&self, intent: &str) -> bool {
+        self.record("stop_app", None, json!({ "intent": intent }))
+    }
+
+    fn screencap(&self) -> Option<ImageBuffer> {
+        let image = {
+            let mut cursor = self.cursor.lock().ok()?;
+            let frame = &self.frames[cursor.index];
+            let image = frame.image.clone();
+            cursor.last_served = Some(cursor.index);
+            cursor.served += 1;
+
+            // 决定下一次截图的帧
+            let advance = if self.scripted {
+                frame.advance_after.is_some_and(|n| cursor.served >= n)
+            } else {
+                true
+            };
+            if advance {
+                if cursor.index + 1 < self.frames.len() {
+                    cursor.index += 1;
+                } else if self.loop_images {
+                    cursor.index = 0;
+                }
+                cursor.served = 0;
+            }
+            image
+        };
+
+        let path = self.image_dir.join(&image);
+        let bytes = match std::fs::read(&path) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                warn!("[Replay] Failed to read {}: {}", path.display(), e);
+                return None;
+            }
+        };
+        match ImageBuffer::from_encoded(&bytes) {
+            Ok(buffer) => Some(buffer),
+            Err(e) => {
+                warn!("[Replay] Failed to decode {}: {}", path.display(), e);
+                None
+            }
+        }
+    }
+
+    fn click(&self, x: i32, y: i32) -> bool {
+        self.record("click", Some((x, y)), json!({ "x": x, "y": y }))
+    }
+
+    fn swipe(&self, x1: i32, y1: i32, x2: i32, y2: i32, duration: i32) -> bool {
+        self.record(
+            "swipe",
+            Some((x1, y1)),
+            json!({ "x1": x1, "y1": y1, "x2": x2, "y2": y2, "duration": duration }),
+        )
+    }
+
+    fn touch_down(&self, contact: i32, x: i32, y: i32, pressure: i32) -> bool {
+        self.record(
+            "touch_down",
+            Some((x, y)),
+            json!({ "contact": contact, "x": x, "y": y, "pressure": pressure }),
+        )
+    }
+
+    fn touch_move(&self, contact: i32, x: i32, y: i32, pressure: i32) -> bool {
+        self.record(
+            "touch_move",
+            None,
+            json!({ "contact": contact, "x": x, "y": y, "pressure": pressure }),
+        )
+    }
+
+    fn touch_up(&self, contact: i32) -> bool {
+        self.record("touch_up", None, json!({ "contact": contact }))
+    }
+
+    fn click_key(&self, keycode: i32) -> bool {
+        self.record("click_key", None, json!({ "keycode": keycode }))
+    }
+
+    fn input_text(&self, text: &str) -> bool {
+        self.record("input_text", None, json!({ "text": text }))
+    }
+
+    fn key_down(&self, keycode: i32) -> bool {
+        self.record("key_down", None, json!({ "keycode": keycode }))
+    }
+
+    fn key_up(&self, keycode: i32) -> bool {
+        self.record("key_up", None, json!({ "keycode": keycode }))
+    }
+}
@@ -9,6 +9,8 @@ use tauri::{
     AppHandle, Emitter, Manager, Wry,
 };
 
+use crate::commands::error::MxuError;
+
 /// 全局设置：关闭时是否最小化到托盘
 static MINIMIZE_TO_TRAY: AtomicBool = AtomicBool::new(false);
 
@@ -31,9 +33,11 @@ pub fn init_tray(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
     let show_i = MenuItem::with_id(app, "show", "显示主窗口", true, None::<&str>)?;
     let start_i = MenuItem::with_id(app, "start", "开始任务", true, None::<&str>)?;
     let stop_i = MenuItem::with_id(app, "stop", "停止任务", true, None::<&str>)?;
+    let cancel_power_i =
+        MenuItem::with_id(app, "cancel_power", "取消关机倒计时", true, None::<&str>)?;
     let quit_i = MenuItem::with_id(app, "quit", "退出", true, None::<&str>)?;
 
-    let menu = Menu::with_items(app, &[&show_i, &start_i, &stop_i, &quit_i])?;
+    let menu = Menu::with_items(app, &[&show_i, &start_i, &stop_i, &cancel_power_i, &quit_i])?;
 
     // 获取图标
     let icon = app
@@ -65,6 +69,12 @@ pub fn init_tray(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
                         let _ = window.emit("tray-stop-tasks", ());
                     }
                 }
+                "cancel_power" => {
+                    // 取消 MXU_POWER 倒计时，并通知前端
+                    if crate::mxu_actions::cancel_power_countdown() {
+                        let _ = app.emit("mxu-power-cancelled", ());
+                    }
+                }
                 "quit" => {
                     // 真正退出应用
                     app.exit(0);
@@ -120,69 +130,64 @@ pub fn handle_close_requested(app: &AppHandle) -> bool {
 
 /// 更新托盘图标
 /// icon_path: 图标文件的相对路径（相对于 exe 目录）
-pub fn update_tray_icon(icon_path: &str) -> Result<(), String> {
+pub fn update_tray_icon(icon_path: &str) -> Result<(), MxuError> {
     // 路径安全校验：禁止路径遍历
     if icon_path.contains("..") {
-        return Err("Invalid icon path: path traversal not allowed".to_string());
+        return Err(MxuError::InvalidPath {
+            path: icon_path.to_string(),
+        });
     }
 
     // 获取 exe 目录
-    let exe_dir = std::env::current_exe()
-        .map_err(|e| format!("Failed to get exe path: {}", e))?
-        .parent()
-        .ok_or("Failed to get exe directory")?
-        .to_path_buf();
+    let exe_dir = crate::commands::utils::get_exe_directory()?;
 
     let full_path = exe_dir.join(icon_path);
 
     // 校验最终路径是否在 exe 目录内
     let canonical_path = full_path
         .canonicalize()
-        .map_err(|e| format!("Failed to resolve icon path: {}", e))?;
+        .map_err(|e| MxuError::io("resolve icon path", &full_path, e))?;
     let canonical_exe_dir = exe_dir
         .canonicalize()
-        .map_err(|e| format!("Failed to resolve exe directory: {}", e))?;
+        .map_err(|e| MxuError::io("resolve exe directory", &exe_dir, e))?;
 
     if !canonical_path.starts_with(&canonical_exe_dir) {
-        return Err("Invalid icon path: must be within application directory".to_string());
+        return Err(MxuError::InvalidPath {
+            path: icon_path.to_string(),
+        });
     }
 
     // 读取图标文件
     let icon_data = std::fs::read(&canonical_path)
-        .map_err(|e| format!("Failed to read icon file {:?}: {}", canonical_path, e))?;
+        .map_err(|e| MxuError::io("read icon file", &canonical_path, e))?;
 
     // 创建图标
-    let icon = Image::from_bytes(&icon_data).map_err(|e| format!("Failed to parse icon: {}", e))?;
+    let icon = Image::from_bytes(&icon_data)
+        .map_err(|e| MxuError::io("parse icon", &canonical_path, e))?;
 
     // 更新托盘图标
     let tray_mutex = TRAY_ICON.get_or_init(|| Mutex::new(None));
-    let guard = tray_mutex
-        .lock()
-        .map_err(|e| format!("Failed to lock tray mutex: {}", e))?;
+    let guard = tray_mutex.lock()?;
 
     if let Some(tray) = guard.as_ref() {
-        tray.set_icon(Some(icon))
-            .map_err(|e| format!("Failed to set tray icon: {}", e))?;
+        tray.set_icon(Some(icon))?;
         log::info!("Tray icon updated: {}", icon_path);
         Ok(())
     } else {
-        Err("Tray icon not initialized".to_string())
+        Err(MxuError::internal("Tray icon not initialized"))
     }
 }
 
 /// 更新托盘 tooltip
-pub fn update_tray_tooltip(tooltip: &str) -> Result<(), String> {
+pub fn update_tray_tooltip(tooltip: &str) -> Result<(), MxuError> {
     let tray_mutex = TRAY_ICON.get_or_init(|| Mutex::new(None));
-    let guard = tray_mutex
-        .lock()
-        .map_err(|e| format!("Failed to lock tray mutex: {}", e))?;
+    let guard = tray_mutex.lock()?;
 
     if let Some(tray) = guard.as_ref() {
-        tray.set_tooltip(Some(tooltip))
-            .map_err(|e| format!("Failed to set tray tooltip: {}", e))?;
+        tray.set_tooltip(Some(tooltip))?;
         log::info!("Tray tooltip updated: {}", tooltip);
         Ok(())
     } else {
-        Err("Tray icon not initialized".to_string())
+        Err(MxuError::internal("Tray icon not initialized"))
     }
 }
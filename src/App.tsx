@@ -42,6 +42,7 @@ import { register, unregisterAll } from '@tauri-apps/plugin-global-shortcut';
 import { loggers } from '@/utils/logger';
 import { useMaaCallbackLogger, useMaaAgentLogger } from '@/utils/useMaaCallbackLogger';
 import { getInterfaceLangKey } from '@/i18n';
+import { formatError } from '@/types/maa';
 import { applyTheme, resolveThemeMode } from '@/themes';
 import {
   isTauri,
@@ -54,7 +55,7 @@ import {
   focusWindow,
   MIN_LEFT_PANEL_WIDTH,
 } from '@/utils/windowUtils';
-import { VersionWarningModal, LoadingScreen } from './components/app';
+import { VersionWarningModal, LoadingScreen, PowerCountdownBanner } from './components/app';
 
 const log = loggers.app;
 
@@ -593,7 +594,7 @@ function App() {
       }
     } catch (err) {
       log.error('加载 interface.json 失败:', err);
-      setErrorMessage(err instanceof Error ? err.message : '加载失败');
+      setErrorMessage(formatError(err));
       setLoadingState('error');
     }
   };
@@ -948,6 +949,30 @@ function App() {
     };
   }, [hotkeys?.globalEnabled, hotkeys?.startTasks, hotkeys?.stopTasks]);
 
+  // 监听后台刷新后的 ADB 设备列表变化，更新设备缓存
+  useEffect(() => {
+    if (!isTauri()) return;
+
+    let unlisten: (() => void) | null = null;
+    let cancelled = false;
+
+    maaService
+      .onAdbDevicesChanged((event) => {
+        log.info(`ADB 设备列表已更新: ${event.devices.length} 个设备`);
+        useAppStore.getState().setCachedAdbDevices(event.devices);
+      })
+      .then((fn) => {
+        if (cancelled) fn();
+        else unlisten = fn;
+      })
+      .catch((err) => log.warn('注册 ADB 设备变化监听失败:', err));
+
+    return () => {
+      cancelled = true;
+      if (unlisten) unlisten();
+    };
+  }, []);
+
   // 监听托盘菜单事件（开始/停止任务）
   useEffect(() => {
     if (!isTauri()) return;
@@ -1088,6 +1113,9 @@ function App() {
       {/* 顶部标签栏 */}
       <TabBar />
 
+      {/* MXU_POWER 倒计时提示（可取消） */}
+      <PowerCountdownBanner />
+
       {/* 中控台视图 */}
       {dashboardView ? (
         <div
@@ -15,15 +15,22 @@ import {
   CheckCircle,
   Settings2,
   History,
+  Gauge,
 } from 'lucide-react';
 import clsx from 'clsx';
 import { maaService } from '@/services/maaService';
 import { useAppStore } from '@/stores/appStore';
 import { resolveI18nText } from '@/services/contentResolver';
-import type { AdbDevice, Win32Window, ControllerConfig } from '@/types/maa';
+import type { AdbDevice, Win32Window, ControllerConfig, ControllerMethodProbe } from '@/types/maa';
 import type { ControllerItem, ResourceItem } from '@/types/interface';
 import { computeResourcePaths } from '@/utils/resourcePath';
-import { parseWin32ScreencapMethod, parseWin32InputMethod } from '@/types/maa';
+import {
+  applySavedAdbMethods,
+  formatError,
+  matchSavedAdbDevices,
+  parseWin32ScreencapMethod,
+  parseWin32InputMethod,
+} from '@/types/maa';
 import { getInterfaceLangKey } from '@/i18n';
 import {
   startGlobalCallbackListener,
@@ -54,6 +61,7 @@ export function ConnectionPanel() {
     setInstanceConnectionStatus,
     setInstanceResourceLoaded,
     setInstanceSavedDevice,
+    setInstanceAdbMethods,
     connectionPanelExpanded,
     setConnectionPanelExpanded,
     registerCtrlIdName,
@@ -74,6 +82,8 @@ export function ConnectionPanel() {
   const [isConnecting, setIsConnecting] = useState(false);
   const [isConnected, setIsConnected] = useState(false);
   const [deviceError, setDeviceError] = useState<string | null>(null);
+  const [isProbing, setIsProbing] = useState(false);
+  const [probeMessage, setProbeMessage] = useState<string | null>(null);
   const [selectedAdbDevice, setSelectedAdbDevice] = useState<AdbDevice | null>(null);
   const [selectedWindow, setSelectedWindow] = useState<Win32Window | null>(null);
   const [showDeviceDropdown, setShowDeviceDropdown] = useState(false);
@@ -235,7 +245,7 @@ export function ConnectionPanel() {
     const savedDevice = activeInstance?.savedDevice;
     if (savedDevice?.adbDeviceName && cachedAdbDevices.length > 0) {
       // 从缓存中找到匹配的 ADB 设备
-      const matchedDevice = cachedAdbDevices.find((d) => d.name === savedDevice.adbDeviceName);
+      const [matchedDevice] = matchSavedAdbDevices(cachedAdbDevices, savedDevice);
       setSelectedAdbDevice(matchedDevice || null);
     } else {
       setSelectedAdbDevice(null);
@@ -378,7 +388,7 @@ export function ConnectionPanel() {
         // 3. 如果有保存的设备但匹配不到 → 显示下拉框让用户选择
         let autoSelected: AdbDevice | null = null;
         if (savedDevice?.adbDeviceName) {
-          const matched = devices.filter((d) => d.name === savedDevice.adbDeviceName);
+          const matched = matchSavedAdbDevices(devices, savedDevice);
           if (matched.length === 1) {
             autoSelected = matched[0];
           }
@@ -421,7 +431,7 @@ export function ConnectionPanel() {
         }
       }
     } catch (err) {
-      setDeviceError(err instanceof Error ? err.message : t('controller.connectionFailed'));
+      setDeviceError(formatError(err));
     } finally {
       setIsSearching(false);
     }
@@ -473,12 +483,13 @@ export function ConnectionPanel() {
       let targetType: 'device' | 'window' = 'device';
 
       if (controllerType === 'Adb' && selectedAdbDevice) {
+        const methods = applySavedAdbMethods(selectedAdbDevice, activeInstance?.savedDevice);
         config = {
           type: 'Adb',
           adb_path: selectedAdbDevice.adb_path,
           address: selectedAdbDevice.address,
-          screencap_methods: selectedAdbDevice.screencap_methods,
-          input_methods: selectedAdbDevice.input_methods,
+          screencap_methods: methods.screencap_methods,
+          input_methods: methods.input_methods,
           config: selectedAdbDevice.config,
         };
         deviceName = selectedAdbDevice.name || selectedAdbDevice.address;
@@ -520,7 +531,7 @@ export function ConnectionPanel() {
 
       await connectControllerInternal(config, deviceName, targetType);
     } catch (err) {
-      setDeviceError(err instanceof Error ? err.message : t('controller.connectionFailed'));
+      setDeviceError(formatError(err));
       setIsConnected(false);
       setInstanceConnectionStatus(instanceId, 'Disconnected');
       setIsConnecting(false);
@@ -573,7 +584,7 @@ export function ConnectionPanel() {
         setIsLoadingResource(false);
       }
     } catch (err) {
-      setResourceError(err instanceof Error ? err.message : t('resource.loadFailed'));
+      setResourceError(formatError(err));
       setIsResourceLoaded(false);
       setInstanceResourceLoaded(instanceId, false);
       setIsLoadingResource(false);
@@ -595,7 +606,7 @@ export function ConnectionPanel() {
       // 加载新资源
       await loadResourceInternal(newResource);
     } catch (err) {
-      setResourceError(err instanceof Error ? err.message : t('resource.switchFailed'));
+      setResourceError(formatError(err));
       setIsLoadingResource(false);
       lastLoadedResourceRef.current = null;
     }
@@ -674,13 +685,55 @@ export function ConnectionPanel() {
     return t('controller.selectDevice');
   };
 
+  // 探测控制方式：保存最快的可用截图 / 输入方式，下次连接时生效
+  const handleProbeMethods = async () => {
+    if (!selectedAdbDevice || !instanceId) return;
+
+    setIsProbing(true);
+    setDeviceError(null);
+    setProbeMessage(null);
+    try {
+      const report = await maaService.probeAdbController(selectedAdbDevice);
+      if (!report.best_screencap_methods && !report.best_input_methods) {
+        setDeviceError(t('controller.probeNoUsable'));
+        return;
+      }
+      setInstanceAdbMethods(
+        instanceId,
+        report.best_screencap_methods ?? undefined,
+        report.best_input_methods ?? undefined,
+      );
+      const methodName = (probes: ControllerMethodProbe[], method: string | null) =>
+        probes.find((p) => p.method === method)?.name ?? '-';
+      setProbeMessage(
+        t('controller.probeApplied', {
+          screencap: methodName(report.screencap, report.best_screencap_methods),
+          input: methodName(report.input, report.best_input_methods),
+        }),
+      );
+    } catch (err) {
+      setDeviceError(formatError(err));
+    } finally {
+      setIsProbing(false);
+    }
+  };
+
   // 选择 ADB 设备并自动连接（如已连接会先断开旧连接）
   const handleSelectAdbDevice = async (device: AdbDevice) => {
     setSelectedAdbDevice(device);
     setShowDeviceDropdown(false);
-
-    // 保存设备名称到实例配置
-    setInstanceSavedDevice(instanceId, { adbDeviceName: device.name });
+    setProbeMessage(null);
+
+    // 保存设备名称到实例配置，重新选择同一设备时保留探测得到的截图 / 输入方式
+    const previous = activeInstance?.savedDevice;
+    const sameDevice = !!previous && matchSavedAdbDevices([device], previous).length > 0;
+    const savedDevice = {
+      adbDeviceName: device.name,
+      adbDeviceIdentity: device.identity ?? undefined,
+      adbScreencapMethods: sameDevice ? previous.adbScreencapMethods : undefined,
+      adbInputMethods: sameDevice ? previous.adbInputMethods : undefined,
+    };
+    setInstanceSavedDevice(instanceId, savedDevice);
 
     // 自动连接
     setIsConnecting(true);
@@ -700,18 +753,19 @@ export function ConnectionPanel() {
 
       await maaService.createInstance(instanceId).catch(() => {});
 
+      const methods = applySavedAdbMethods(device, savedDevice);
       const config: ControllerConfig = {
         type: 'Adb',
         adb_path: device.adb_path,
         address: device.address,
-        screencap_methods: device.screencap_methods,
-        input_methods: device.input_methods,
+        screencap_methods: methods.screencap_methods,
+        input_methods: methods.input_methods,
         config: device.config,
       };
 
       await connectControllerInternal(config, device.name || device.address, 'device');
     } catch (err) {
-      setDeviceError(err instanceof Error ? err.message : t('controller.connectionFailed'));
+      setDeviceError(formatError(err));
       setIsConnected(false);
       setInstanceConnectionStatus(instanceId, 'Disconnected');
       setIsConnecting(false);
@@ -762,7 +816,7 @@ export function ConnectionPanel() {
 
       await connectControllerInternal(config, win.window_name || win.class_name, 'window');
     } catch (err) {
-      setDeviceError(err instanceof Error ? err.message : t('controller.connectionFailed'));
+      setDeviceError(formatError(err));
       setIsConnected(false);
       setInstanceConnectionStatus(instanceId, 'Disconnected');
       setIsConnecting(false);
@@ -791,7 +845,7 @@ export function ConnectionPanel() {
 
         // 尝试匹配保存的设备名称
         if (savedDevice?.adbDeviceName) {
-          const matched = devices.find((d) => d.name === savedDevice.adbDeviceName);
+          const [matched] = matchSavedAdbDevices(devices, savedDevice);
           if (matched) {
             // 找到匹配的，自动连接
             setIsSearching(false);
@@ -833,7 +887,7 @@ export function ConnectionPanel() {
         }
       }
     } catch (err) {
-      setDeviceError(err instanceof Error ? err.message : t('controller.connectionFailed'));
+      setDeviceError(formatError(err));
     } finally {
       setIsSearching(false);
     }
@@ -1268,6 +1322,36 @@ export function ConnectionPanel() {
                     <RefreshCw className="w-3.5 h-3.5 text-text-secondary" />
                   )}
                 </button>
+
+                {/* 控制方式探测按钮 - 仅 Adb */}
+                {controllerType === 'Adb' && (
+                  <button
+                    onClick={handleProbeMethods}
+                    disabled={!selectedAdbDevice || isProbing || isConnecting || isRunning}
+                    className={clsx(
+                      'flex items-center justify-center px-3 py-1.5 rounded-md border transition-colors',
+                      'bg-bg-tertiary border-border',
+                      !selectedAdbDevice || isProbing || isConnecting || isRunning
+                        ? 'opacity-50 cursor-not-allowed'
+                        : 'hover:bg-bg-hover hover:border-accent',
+                    )}
+                    title={t('controller.probeMethods')}
+                  >
+                    {isProbing ? (
+                      <Loader2 className="w-3.5 h-3.5 animate-spin text-text-secondary" />
+                    ) : (
+                      <Gauge className="w-3.5 h-3.5 text-text-secondary" />
+                    )}
+                  </button>
+                )}
+              </div>
+            )}
+
+            {/* 控制方式探测结果 */}
+            {probeMessage && !deviceError && (
+              <div className="flex items-center gap-2 px-2.5 py-1.5 rounded-md bg-success/10 text-success text-xs">
+                <CheckCircle className="w-3.5 h-3.5 flex-shrink-0" />
+                <span>{probeMessage}</span>
               </div>
             )}
 
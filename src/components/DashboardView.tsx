@@ -33,6 +33,8 @@ import type { TaskConfig } from '@/types/maa';
 import { normalizeAgentConfigs } from '@/types/interface';
 import { getInterfaceLangKey } from '@/i18n';
 import { getMxuSpecialTask } from '@/types/specialTasks';
+import type { SelectedTask } from '@/types/interface';
+import { startPlanListener, trackPlanRun, untrackPlanRun } from './toolbar/planTracking';
 
 const log = loggers.ui;
 
@@ -204,6 +206,7 @@ function InstanceCard({ instanceId, instanceName, isActive, onSelect }: Instance
           setInstanceCurrentTaskId(instanceId, null);
           clearTaskRunStatus(instanceId);
           clearPendingTasks(instanceId);
+          untrackPlanRun(instanceId);
           runningInstanceIdRef.current = null;
         } catch (err) {
           log.error(`[${instanceName}] 停止任务失败:`, err);
@@ -257,7 +260,7 @@ function InstanceCard({ instanceId, instanceName, isActive, onSelect }: Instance
           setShowAddTaskPanel(false);
 
           // 启动任务
-          const taskIds = await maaService.startTasks(
+          const { task_ids: taskIds, plan } = await maaService.startTasks(
             instanceId,
             taskConfigs,
             agentConfigs,
@@ -271,22 +274,25 @@ function InstanceCard({ instanceId, instanceName, isActive, onSelect }: Instance
           const enabledTaskIds = enabledTasks.map((t) => t.id);
           setAllTasksRunStatus(instanceId, enabledTaskIds, 'pending');
 
+          // 任务显示名，MXU 特殊任务的 label 需要用 t() 翻译
+          const getTaskDisplayName = (task: SelectedTask) => {
+            const specialTask = getMxuSpecialTask(task.taskName);
+            const taskDef =
+              specialTask?.taskDef || projectInterface?.task.find((t) => t.name === task.taskName);
+            return (
+              task.customName ||
+              (specialTask && taskDef?.label
+                ? t(taskDef.label)
+                : resolveI18nText(taskDef?.label, translations)) ||
+              task.taskName
+            );
+          };
+
           // 记录映射关系
           taskIds.forEach((maaTaskId, index) => {
             if (enabledTasks[index]) {
               registerMaaTaskMapping(instanceId, maaTaskId, enabledTasks[index].id);
-              // MXU 特殊任务的 label 需要用 t() 翻译
-              const specialTask = getMxuSpecialTask(enabledTasks[index].taskName);
-              const taskDef =
-                specialTask?.taskDef ||
-                projectInterface?.task.find((t) => t.name === enabledTasks[index].taskName);
-              const taskDisplayName =
-                enabledTasks[index].customName ||
-                (specialTask && taskDef?.label
-                  ? t(taskDef.label)
-                  : resolveI18nText(taskDef?.label, translations)) ||
-                enabledTasks[index].taskName;
-              registerTaskIdName(maaTaskId, taskDisplayName);
+              registerTaskIdName(maaTaskId, getTaskDisplayName(enabledTasks[index]));
             }
           });
 
@@ -295,6 +301,21 @@ function InstanceCard({ instanceId, instanceName, isActive, onSelect }: Instance
             setTaskRunStatus(instanceId, enabledTasks[0].id, 'running');
           }
 
+          // 执行计划逐个提交任务，task_id 与任务状态由 planTracking 根据计划事件更新
+          if (plan) {
+            startPlanListener();
+            trackPlanRun(
+              instanceId,
+              enabledTasks.map((task) => ({
+                selectedTaskId: task.id,
+                name: getTaskDisplayName(task),
+              })),
+            );
+            runningInstanceIdRef.current = instanceId;
+            setIsStarting(false);
+            return;
+          }
+
           // 设置任务队列
           runningInstanceIdRef.current = instanceId;
           setPendingTaskIds(instanceId, taskIds);
@@ -19,7 +19,7 @@ import { maaService } from '@/services/maaService';
 import { useAppStore } from '@/stores/appStore';
 import type { AdbDevice, Win32Window, ControllerConfig } from '@/types/maa';
 import type { ControllerItem } from '@/types/interface';
-import { parseWin32ScreencapMethod, parseWin32InputMethod } from '@/types/maa';
+import { parseWin32ScreencapMethod, parseWin32InputMethod, formatError } from '@/types/maa';
 import { loggers } from '@/utils/logger';
 
 const log = loggers.device;
@@ -189,7 +189,7 @@ export function DeviceSelector({
       }
     } catch (err) {
       log.error('搜索设备失败:', err);
-      setError(err instanceof Error ? err.message : '搜索失败');
+      setError(formatError(err));
     } finally {
       setIsSearching(false);
     }
@@ -264,7 +264,7 @@ export function DeviceSelector({
       setPendingCtrlId(ctrlId);
     } catch (err) {
       log.error('连接失败:', err);
-      setError(err instanceof Error ? err.message : '连接失败');
+      setError(formatError(err));
       setIsConnected(false);
       onConnectionChange?.(false);
       setIsConnecting(false);
@@ -329,7 +329,7 @@ export function DeviceSelector({
       setPendingCtrlId(ctrlId);
     } catch (err) {
       log.error('自动连接失败:', err);
-      setError(err instanceof Error ? err.message : '连接失败');
+      setError(formatError(err));
       setIsConnected(false);
       onConnectionChange?.(false);
       setIsConnecting(false);
@@ -378,7 +378,7 @@ export function DeviceSelector({
       setPendingCtrlId(ctrlId);
     } catch (err) {
       log.error('自动连接失败:', err);
-      setError(err instanceof Error ? err.message : '连接失败');
+      setError(formatError(err));
       setIsConnected(false);
       onConnectionChange?.(false);
       setIsConnecting(false);
@@ -6,6 +6,7 @@ import { maaService } from '@/services/maaService';
 import { useAppStore } from '@/stores/appStore';
 import { resolveI18nText } from '@/services/contentResolver';
 import type { ResourceItem } from '@/types/interface';
+import { formatError } from '@/types/maa';
 import { getInterfaceLangKey } from '@/i18n';
 import { computeResourcePaths } from '@/utils/resourcePath';
 
@@ -177,7 +178,7 @@ export function ResourceSelector({
       // 记录等待中的 res_ids，后续由回调处理完成状态
       setPendingResIds(new Set(resIds));
     } catch (err) {
-      setError(err instanceof Error ? err.message : '资源加载失败');
+      setError(formatError(err));
       setIsLoaded(false);
       onLoadStatusChange?.(false);
       setIsLoading(false);
@@ -199,7 +200,7 @@ export function ResourceSelector({
       // 加载新资源
       await loadResource(newResource);
     } catch (err) {
-      setError(err instanceof Error ? err.message : '切换资源失败');
+      setError(formatError(err));
       setIsLoading(false);
       lastLoadedResourceRef.current = null;
     }
@@ -17,13 +17,20 @@ import { loggers, generateTaskPipelineOverride, computeResourcePaths } from '@/u
 import { getMxuSpecialTask } from '@/types/specialTasks';
 import type { TaskConfig, ControllerConfig } from '@/types/maa';
 import { normalizeAgentConfigs } from '@/types/interface';
-import { parseWin32ScreencapMethod, parseWin32InputMethod } from '@/types/maa';
+import {
+  applySavedAdbMethods,
+  matchSavedAdbDevices,
+  parseWin32ScreencapMethod,
+  parseWin32InputMethod,
+  formatError,
+} from '@/types/maa';
 import { SchedulePanel } from './SchedulePanel';
-import type { Instance } from '@/types/interface';
+import type { Instance, SelectedTask } from '@/types/interface';
 import { resolveI18nText } from '@/services/contentResolver';
 import { getInterfaceLangKey } from '@/i18n';
 import { PermissionModal } from './toolbar/PermissionModal';
 import { ScheduleButton } from './toolbar/ScheduleButton';
+import { startPlanListener, trackPlanRun, untrackPlanRun } from './toolbar/planTracking';
 
 const log = loggers.task;
 
@@ -124,6 +131,11 @@ export function Toolbar({ showAddPanel, onToggleAddPanel }: ToolbarProps) {
   // 只要有启用的任务就可以运行（连接和资源加载会在 startTasksForInstance 中自动处理）
   const canRun = tasks.some((t) => t.enabled);
 
+  // 设置了执行计划策略的任务由全局监听器根据 maa-plan-step / maa-plan-finished 更新状态
+  useEffect(() => {
+    startPlanListener();
+  }, []);
+
   // 监听任务完成回调
   useEffect(() => {
     if (pendingTaskIds.length === 0) return;
@@ -374,7 +386,7 @@ export function Toolbar({ showAddPanel, onToggleAddPanel }: ToolbarProps) {
                 try {
                   if (controllerType === 'Adb' && savedDevice.adbDeviceName) {
                     const devices = await maaService.findAdbDevices();
-                    deviceFound = devices.some((d) => d.name === savedDevice.adbDeviceName);
+                    deviceFound = matchSavedAdbDevices(devices, savedDevice).length > 0;
                   } else if (
                     (controllerType === 'Win32' || controllerType === 'Gamepad') &&
                     savedDevice.windowName
@@ -433,7 +445,9 @@ export function Toolbar({ showAddPanel, onToggleAddPanel }: ToolbarProps) {
             log.error(`实例 ${targetInstance.name}: 前置动作执行失败:`, err);
             addLog(targetId, {
               type: 'error',
-              message: t('action.preActionFailed', { error: String(err) }),
+              message: t('action.preActionFailed', {
+                error: formatError(err),
+              }),
             });
             // 前置动作失败不阻止任务执行，继续
           }
@@ -459,17 +473,18 @@ export function Toolbar({ showAddPanel, onToggleAddPanel }: ToolbarProps) {
 
             if (controllerType === 'Adb' && savedDevice.adbDeviceName) {
               const devices = await maaService.findAdbDevices();
-              const matchedDevice = devices.find((d) => d.name === savedDevice.adbDeviceName);
+              const [matchedDevice] = matchSavedAdbDevices(devices, savedDevice);
               if (!matchedDevice) {
                 log.warn(`实例 ${targetInstance.name}: 未找到设备 ${savedDevice.adbDeviceName}`);
                 return false;
               }
+              const methods = applySavedAdbMethods(matchedDevice, savedDevice);
               config = {
                 type: 'Adb',
                 adb_path: matchedDevice.adb_path,
                 address: matchedDevice.address,
-                screencap_methods: matchedDevice.screencap_methods,
-                input_methods: matchedDevice.input_methods,
+                screencap_methods: methods.screencap_methods,
+                input_methods: methods.input_methods,
                 config: matchedDevice.config,
               };
               deviceName = matchedDevice.name || matchedDevice.address;
@@ -757,7 +772,7 @@ export function Toolbar({ showAddPanel, onToggleAddPanel }: ToolbarProps) {
         }
 
         // 启动任务
-        const taskIds = await maaService.startTasks(
+        const { task_ids: taskIds, plan } = await maaService.startTasks(
           targetId,
           taskConfigs,
           agentConfigs,
@@ -774,23 +789,25 @@ export function Toolbar({ showAddPanel, onToggleAddPanel }: ToolbarProps) {
         // 开始任务时折叠所有任务
         collapseAllTasks(targetId, false);
 
+        // 任务显示名（使用自定义名称或 label），MXU 特殊任务的 label 需要用 t() 翻译
+        const getTaskDisplayName = (task: SelectedTask) => {
+          const specialTask = getMxuSpecialTask(task.taskName);
+          const taskDef =
+            specialTask?.taskDef || projectInterface?.task.find((t) => t.name === task.taskName);
+          return (
+            task.customName ||
+            (specialTask && taskDef?.label
+              ? t(taskDef.label)
+              : resolveI18nText(taskDef?.label, translations)) ||
+            task.taskName
+          );
+        };
+
         // 记录映射关系，并注册 task_id 与任务名的映射用于日志显示
         taskIds.forEach((maaTaskId, index) => {
           if (enabledTasks[index]) {
             registerMaaTaskMapping(targetId, maaTaskId, enabledTasks[index].id);
-            // 注册 task_id 与任务名的映射（使用自定义名称或 label）
-            // MXU 特殊任务的 label 需要用 t() 翻译
-            const specialTask = getMxuSpecialTask(enabledTasks[index].taskName);
-            const taskDef =
-              specialTask?.taskDef ||
-              projectInterface?.task.find((t) => t.name === enabledTasks[index].taskName);
-            const taskDisplayName =
-              enabledTasks[index].customName ||
-              (specialTask && taskDef?.label
-                ? t(taskDef.label)
-                : resolveI18nText(taskDef?.label, translations)) ||
-              enabledTasks[index].taskName;
-            registerTaskIdName(maaTaskId, taskDisplayName);
+            registerTaskIdName(maaTaskId, getTaskDisplayName(enabledTasks[index]));
           }
         });
 
@@ -799,6 +816,19 @@ export function Toolbar({ showAddPanel, onToggleAddPanel }: ToolbarProps) {
           setTaskRunStatus(targetId, enabledTasks[0].id, 'running');
         }
 
+        // 执行计划逐个提交任务，task_id 与任务状态由 planTracking 根据计划事件更新
+        if (plan) {
+          trackPlanRun(
+            targetId,
+            enabledTasks.map((task) => ({
+              selectedTaskId: task.id,
+              name: getTaskDisplayName(task),
+            })),
+          );
+          runningInstanceIdRef.current = targetId;
+          return true;
+        }
+
         // 设置任务队列
         runningInstanceIdRef.current = targetId;
         setPendingTaskIds(targetId, taskIds);
@@ -1030,6 +1060,7 @@ export function Toolbar({ showAddPanel, onToggleAddPanel }: ToolbarProps) {
       setInstanceCurrentTaskId(targetInstanceId, null);
       clearTaskRunStatus(targetInstanceId);
       clearPendingTasks(targetInstanceId);
+      untrackPlanRun(targetInstanceId);
       clearScheduleExecution(targetInstanceId);
       runningInstanceIdRef.current = null;
     } finally {
@@ -1075,7 +1106,7 @@ export function Toolbar({ showAddPanel, onToggleAddPanel }: ToolbarProps) {
         }
       } catch (err) {
         log.error('任务启动异常:', err);
-        setAutoConnectError(err instanceof Error ? err.message : String(err));
+        setAutoConnectError(formatError(err));
         setAutoConnectPhase('idle');
       } finally {
         setIsStarting(false);
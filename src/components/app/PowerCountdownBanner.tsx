@@ -0,0 +1,52 @@
+import { useEffect, useState } from 'react';
+import { useTranslation } from 'react-i18next';
+import { Power } from 'lucide-react';
+import { maaService } from '@/services/maaService';
+import { loggers } from '@/utils/logger';
+
+const log = loggers.app;
+
+/** MXU_POWER 倒计时提示条，倒计时结束前可取消电源操作 */
+export function PowerCountdownBanner() {
+  const { t } = useTranslation();
+  const [remaining, setRemaining] = useState<number | null>(null);
+
+  useEffect(() => {
+    const unlisteners = [
+      maaService.onActionProgress((event) => {
+        if (event.action !== 'MXU_POWER') return;
+        setRemaining(event.remaining_secs > 0 ? event.remaining_secs : null);
+      }),
+      maaService.onPowerCancelled(() => setRemaining(null)),
+    ];
+    return () => {
+      unlisteners.forEach((p) => p.then((unlisten) => unlisten()).catch(() => {}));
+    };
+  }, []);
+
+  if (remaining === null) return null;
+
+  const handleCancel = async () => {
+    try {
+      await maaService.cancelPowerCountdown();
+    } catch (err) {
+      log.warn('取消电源操作倒计时失败:', err);
+    }
+    setRemaining(null);
+  };
+
+  return (
+    <div className="flex items-center gap-3 px-4 py-2 bg-amber-500/10 border-b border-amber-500/30 text-sm">
+      <Power className="w-4 h-4 text-amber-500 shrink-0" />
+      <span className="flex-1 text-text-primary">
+        {t('powerCountdown.message', { seconds: remaining })}
+      </span>
+      <button
+        onClick={handleCancel}
+        className="px-3 py-1 bg-amber-500 text-white rounded-md hover:bg-amber-500/90 transition-colors"
+      >
+        {t('powerCountdown.cancel')}
+      </button>
+    </div>
+  );
+}
@@ -1,2 +1,3 @@
 export { VersionWarningModal } from './VersionWarningModal';
 export { LoadingScreen } from './LoadingScreen';
+export { PowerCountdownBanner } from './PowerCountdownBanner';
@@ -3,7 +3,7 @@ import { useTranslation } from 'react-i18next';
 import { maaService } from '@/services/maaService';
 import { useAppStore } from '@/stores/appStore';
 import type { AdbDevice, Win32Window, ControllerConfig } from '@/types/maa';
-import { parseWin32ScreencapMethod, parseWin32InputMethod } from '@/types/maa';
+import { parseWin32ScreencapMethod, parseWin32InputMethod, formatError } from '@/types/maa';
 import type { ControllerItem } from '@/types/interface';
 import { waitForCtrlResult } from './callbackCache';
 
@@ -141,7 +141,7 @@ export function useDeviceConnection({
         }
       }
     } catch (err) {
-      setDeviceError(err instanceof Error ? err.message : t('controller.connectionFailed'));
+      setDeviceError(formatError(err));
     } finally {
       setIsSearching(false);
     }
@@ -189,7 +189,7 @@ export function useDeviceConnection({
 
         await connectControllerInternal(config, device.name || device.address, 'device');
       } catch (err) {
-        setDeviceError(err instanceof Error ? err.message : t('controller.connectionFailed'));
+        setDeviceError(formatError(err));
         setIsConnected(false);
         setInstanceConnectionStatus(instanceId, 'Disconnected');
         setIsConnecting(false);
@@ -247,7 +247,7 @@ export function useDeviceConnection({
 
         await connectControllerInternal(config, win.window_name || win.class_name, 'window');
       } catch (err) {
-        setDeviceError(err instanceof Error ? err.message : t('controller.connectionFailed'));
+        setDeviceError(formatError(err));
         setIsConnected(false);
         setInstanceConnectionStatus(instanceId, 'Disconnected');
         setIsConnecting(false);
@@ -288,7 +288,7 @@ export function useDeviceConnection({
 
       await connectControllerInternal(config, playcoverAddress, 'device');
     } catch (err) {
-      setDeviceError(err instanceof Error ? err.message : t('controller.connectionFailed'));
+      setDeviceError(formatError(err));
       setIsConnected(false);
       setInstanceConnectionStatus(instanceId, 'Disconnected');
       setIsConnecting(false);
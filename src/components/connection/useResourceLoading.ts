@@ -4,6 +4,7 @@ import { maaService } from '@/services/maaService';
 import { useAppStore } from '@/stores/appStore';
 import { resolveI18nText } from '@/services/contentResolver';
 import type { ResourceItem, ControllerItem } from '@/types/interface';
+import { formatError } from '@/types/maa';
 import { waitForResResult } from './callbackCache';
 import { computeResourcePaths } from '@/utils/resourcePath';
 
@@ -77,7 +78,7 @@ export function useResourceLoading({
           return true;
         }
       } catch (err) {
-        setResourceError(err instanceof Error ? err.message : t('resource.loadFailed'));
+        setResourceError(formatError(err));
         setIsResourceLoaded(false);
         setInstanceResourceLoaded(instanceId, false);
         setIsLoadingResource(false);
@@ -108,13 +109,13 @@ export function useResourceLoading({
         await maaService.destroyResource(instanceId);
         return await loadResourceInternal(newResource);
       } catch (err) {
-        setResourceError(err instanceof Error ? err.message : t('resource.switchFailed'));
+        setResourceError(formatError(err));
         setIsLoadingResource(false);
         lastLoadedResourceRef.current = null;
         return false;
       }
     },
-    [instanceId, loadResourceInternal, setInstanceResourceLoaded, t],
+    [instanceId, loadResourceInternal, setInstanceResourceLoaded],
   );
 
   // 处理资源选择
@@ -6,6 +6,7 @@ import { useAppStore } from '@/stores/appStore';
 import { clearAllCache, getCacheStats } from '@/services/cacheService';
 import { maaService } from '@/services/maaService';
 import { loggers } from '@/utils/logger';
+import { formatError } from '@/types/maa';
 import { isTauri, getDebugDir, getConfigDir, openDirectory } from '@/utils/paths';
 import { useExportLogs } from '@/utils/useExportLogs';
 import { SwitchButton } from '@/components/FormControls';
@@ -153,7 +154,7 @@ export function DebugSection() {
       setCacheEntryCount(0);
       addDebugLog('缓存已清空');
     } catch (err) {
-      addDebugLog(`清空缓存失败: ${err}`);
+      addDebugLog(`清空缓存失败: ${formatError(err)}`);
     }
   };
 
@@ -30,6 +30,7 @@ import { createProxySettings, shouldUseProxy } from '@/services/proxyService';
 import { resolveI18nText } from '@/services/contentResolver';
 import { getInterfaceLangKey } from '@/i18n';
 import { loggers } from '@/utils/logger';
+import { formatError } from '@/types/maa';
 import { ReleaseNotes, DownloadProgressBar } from '../UpdateInfoCard';
 
 export function UpdateSection() {
@@ -237,7 +238,7 @@ export function UpdateSection() {
             }
           }
         } catch (err) {
-          addDebugLog(`切换下载源失败: ${err}`);
+          addDebugLog(`切换下载源失败: ${formatError(err)}`);
         } finally {
           setUpdateCheckLoading(false);
         }
@@ -338,7 +339,7 @@ export function UpdateSection() {
         addDebugLog('检查更新失败');
       }
     } catch (err) {
-      addDebugLog(`检查更新出错: ${err}`);
+      addDebugLog(`检查更新出错: ${formatError(err)}`);
     } finally {
       setUpdateCheckLoading(false);
     }
@@ -0,0 +1,89 @@
+import { maaService } from '@/services/maaService';
+import { useAppStore } from '@/stores/appStore';
+import type { TaskRunStatus } from '@/stores/types';
+import type { TaskStatus } from '@/types/maa';
+import { normalizeAgentConfigs } from '@/types/interface';
+import { loggers } from '@/utils';
+
+const log = loggers.task;
+
+/** 执行计划中的单个任务（与提交的任务列表按下标对应） */
+export interface PlanRunTask {
+  selectedTaskId: string;
+  name: string;
+}
+
+// 正在由执行计划运行的实例：instanceId -> 任务列表
+const planRuns = new Map<string, PlanRunTask[]>();
+
+// 全局监听器是否已启动
+let planListenerStarted = false;
+
+function toRunStatus(status: TaskStatus): TaskRunStatus {
+  switch (status) {
+    case 'Running':
+      return 'running';
+    case 'Succeeded':
+      return 'succeeded';
+    case 'Failed':
+    case 'TimedOut':
+      return 'failed';
+    default:
+      return 'pending';
+  }
+}
+
+/**
+ * 记录以执行计划方式启动的实例（startTasks 返回 plan: true 时调用）
+ */
+export function trackPlanRun(instanceId: string, tasks: PlanRunTask[]) {
+  planRuns.set(instanceId, tasks);
+}
+
+/**
+ * 停止跟踪实例的执行计划（用户停止任务后调用）
+ */
+export function untrackPlanRun(instanceId: string) {
+  planRuns.delete(instanceId);
+}
+
+/**
+ * 启动执行计划的全局监听器（只启动一次）
+ * 不依赖 Toolbar 是否挂载，切换到中控台或设置页时计划状态同样会更新
+ */
+export function startPlanListener() {
+  if (planListenerStarted) return;
+  planListenerStarted = true;
+
+  maaService.onPlanStep((event) => {
+    const task = planRuns.get(event.instance_id)?.[event.index];
+    if (!task) return;
+
+    const store = useAppStore.getState();
+    if (event.task_id !== null) {
+      store.registerMaaTaskMapping(event.instance_id, event.task_id, task.selectedTaskId);
+      store.registerTaskIdName(event.task_id, task.name);
+      store.setInstanceCurrentTaskId(event.instance_id, event.task_id);
+    }
+    store.setTaskRunStatus(event.instance_id, task.selectedTaskId, toRunStatus(event.status));
+  });
+
+  maaService.onPlanFinished((event) => {
+    if (!planRuns.has(event.instance_id)) return;
+    planRuns.delete(event.instance_id);
+    log.info('执行计划完成:', event.instance_id, ', success:', event.success);
+
+    const store = useAppStore.getState();
+    const agentConfigs = normalizeAgentConfigs(store.projectInterface?.agent);
+    if (agentConfigs && agentConfigs.length > 0) {
+      maaService.stopAgent(event.instance_id).catch((err) => {
+        log.error('停止 Agent 失败:', err);
+      });
+    }
+
+    store.setInstanceTaskStatus(event.instance_id, event.success ? 'Succeeded' : 'Failed');
+    store.updateInstance(event.instance_id, { isRunning: false });
+    store.setInstanceCurrentTaskId(event.instance_id, null);
+    store.clearPendingTasks(event.instance_id);
+  });
+}
@@ -167,6 +167,14 @@ export default {
       screenoff: 'Turn Off Screen',
       sleep: 'Sleep',
     },
+    screenshot: {
+      label: '📷 Save Screenshot',
+      optionLabel: 'Screenshot Settings',
+      filenameLabel: 'File Name Template',
+      filenamePlaceholder: 'Variables: {instance} {task} {node} {date} {time} {datetime}',
+      maxFilesLabel: 'Max Screenshots Kept',
+      maxFilesError: 'Please enter a non-negative integer (0 = unlimited)',
+    },
   },
 
   // Task list
@@ -324,6 +332,9 @@ export default {
     lastSelected: 'Last selected · Click to search',
     savedDeviceNotFound: 'Previous device not found, please check connection or select another',
     savedWindowNotFound: 'Previous window not found, please check connection or select another',
+    probeMethods: 'Test control methods',
+    probeNoUsable: 'No usable screencap / input method found',
+    probeApplied: 'Saved: screencap {{screencap}}, input {{input}} (applies after reconnecting)',
   },
 
   // Resource
@@ -660,6 +671,35 @@ export default {
     taskNotFound: 'Task not found',
     controllerNotFound: 'Controller not found',
     resourceNotFound: 'Resource not found',
+    // Backend error codes (MxuError.code)
+    backend: {
+      libraryNotFound: 'MaaFramework library directory not found: {{path}}',
+      libraryLoadFailed: 'Failed to load MaaFramework: {{detail}}',
+      notInitialized: 'MaaFramework is not initialized',
+      instanceNotFound: 'Instance not found',
+      controllerNotConnected: 'Controller is not connected',
+      resourceNotLoaded: 'Resource is not loaded',
+      taskerNotCreated: 'Tasker has not been created',
+      taskerNotInitialized: 'Tasker failed to initialize, please check the controller and resource',
+      invalidParameter: 'Invalid parameter {{name}}: {{detail}}',
+      maaCallFailed: 'MaaFramework call failed ({{operation}}): {{detail}}',
+      agentStartFailed: 'Agent #{{index}} failed to start: {{detail}}',
+      downloadCancelled: 'Download cancelled',
+      downloadFailed: 'Download failed: {{detail}}',
+      httpStatus: 'HTTP error: {{status}}',
+      invalidProxy:
+        'Invalid proxy configuration: {{detail}}. Please check the format (http:// or socks5:// supported)',
+      extractFailed: 'Failed to extract archive: {{detail}}',
+      invalidPath: 'Invalid path: {{path}}',
+      ioFailed: 'File operation failed ({{operation}}): {{path}}',
+      unsupported: 'This feature is not supported on the current platform',
+      internal: 'Internal error: {{detail}}',
+      invalidPipelineOverride: 'Invalid pipeline override for task {{entry}} ({{count}} error(s))',
+      taskerBusy: 'A task is running on this instance, please stop it first',
+      emulatorBootTimeout:
+        'Emulator {{address}} did not finish booting within {{timeout_secs}} seconds',
+      adbConnectFailed: 'Failed to connect to {{address}}: {{detail}}',
+    },
   },
 
   // Context Menu
@@ -723,6 +763,12 @@ export default {
     understand: 'I Understand',
   },
 
+  // Power action countdown
+  powerCountdown: {
+    message: 'Power action (shutdown / restart / screen off / sleep) in {{seconds}}s',
+    cancel: 'Cancel',
+  },
+
   // Permission prompt
   permission: {
     title: 'Administrator Privileges Required',
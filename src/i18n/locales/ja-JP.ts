@@ -166,6 +166,14 @@ export default {
       screenoff: '画面オフ',
       sleep: 'スリープ',
     },
+    screenshot: {
+      label: '📷 スクリーンショット保存',
+      optionLabel: 'スクリーンショット設定',
+      filenameLabel: 'ファイル名テンプレート',
+      filenamePlaceholder: '変数：{instance} {task} {node} {date} {time} {datetime}',
+      maxFilesLabel: '最大保存枚数',
+      maxFilesError: '0 以上の整数を入力してください（0 は無制限）',
+    },
   },
 
   // タスクリスト
@@ -325,6 +333,10 @@ export default {
       '前回のデバイスが見つかりません。接続を確認するか、別のデバイスを選択してください',
     savedWindowNotFound:
       '前回のウィンドウが見つかりません。接続を確認するか、別のウィンドウを選択してください',
+    probeMethods: '制御方式をテスト',
+    probeNoUsable: '使用可能なスクリーンショット / 入力方式が見つかりません',
+    probeApplied:
+      '保存しました：スクリーンショット {{screencap}}、入力 {{input}}（再接続後に反映）',
   },
 
   // リソース
@@ -664,6 +676,36 @@ export default {
     taskNotFound: 'タスクが見つかりません',
     controllerNotFound: 'コントローラーが見つかりません',
     resourceNotFound: 'リソースパックが見つかりません',
+    // バックエンドエラーコード（MxuError.code）
+    backend: {
+      libraryNotFound: 'MaaFramework ライブラリディレクトリが見つかりません：{{path}}',
+      libraryLoadFailed: 'MaaFramework の読み込みに失敗しました：{{detail}}',
+      notInitialized: 'MaaFramework が初期化されていません',
+      instanceNotFound: 'インスタンスが見つかりません',
+      controllerNotConnected: 'コントローラーが接続されていません',
+      resourceNotLoaded: 'リソースが読み込まれていません',
+      taskerNotCreated: 'Tasker が作成されていません',
+      taskerNotInitialized:
+        'Tasker の初期化に失敗しました。コントローラーとリソースを確認してください',
+      invalidParameter: 'パラメータ {{name}} が無効です：{{detail}}',
+      maaCallFailed: 'MaaFramework の呼び出しに失敗しました（{{operation}}）：{{detail}}',
+      agentStartFailed: 'Agent #{{index}} の起動に失敗しました：{{detail}}',
+      downloadCancelled: 'ダウンロードがキャンセルされました',
+      downloadFailed: 'ダウンロードに失敗しました：{{detail}}',
+      httpStatus: 'HTTP エラー：{{status}}',
+      invalidProxy:
+        'プロキシ設定が無効です：{{detail}}。形式を確認してください（http:// または socks5:// に対応）',
+      extractFailed: '展開に失敗しました：{{detail}}',
+      invalidPath: '不正なパス：{{path}}',
+      ioFailed: 'ファイル操作に失敗しました（{{operation}}）：{{path}}',
+      unsupported: 'この機能は現在のプラットフォームでは利用できません',
+      internal: '内部エラー：{{detail}}',
+      invalidPipelineOverride:
+        'タスク {{entry}} の Pipeline オーバーライドに誤りがあります（エラー {{count}} 件）',
+      taskerBusy: 'このインスタンスではタスクが実行中です。先に停止してください',
+      emulatorBootTimeout: 'エミュレーター {{address}} が {{timeout_secs}} 秒以内に起動しませんでした',
+      adbConnectFailed: '{{address}} に接続できません：{{detail}}',
+    },
   },
 
   // コンテキストメニュー
@@ -728,6 +770,13 @@ export default {
     understand: '了解しました',
   },
 
+  // 電源操作カウントダウン
+  powerCountdown: {
+    message:
+      '{{seconds}} 秒後に電源操作（シャットダウン / 再起動 / 画面オフ / スリープ）を実行します',
+    cancel: 'キャンセル',
+  },
+
   // 権限プロンプト
   permission: {
     title: '管理者権限が必要です',
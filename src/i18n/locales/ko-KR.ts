@@ -166,6 +166,14 @@ export default {
       screenoff: '화면 끄기',
       sleep: '절전 모드',
     },
+    screenshot: {
+      label: '📷 스크린샷 저장',
+      optionLabel: '스크린샷 설정',
+      filenameLabel: '파일 이름 템플릿',
+      filenamePlaceholder: '변수: {instance} {task} {node} {date} {time} {datetime}',
+      maxFilesLabel: '최대 보관 개수',
+      maxFilesError: '0 이상의 정수를 입력하세요 (0은 무제한)',
+    },
   },
 
   // 작업 목록
@@ -323,6 +331,9 @@ export default {
     savedDeviceNotFound: '이전 기기를 찾을 수 없습니다. 연결을 확인하거나 다른 기기를 선택하세요',
     savedWindowNotFound:
       '이전 윈도우를 찾을 수 없습니다. 연결을 확인하거나 다른 윈도우를 선택하세요',
+    probeMethods: '제어 방식 테스트',
+    probeNoUsable: '사용 가능한 스크린샷 / 입력 방식을 찾을 수 없습니다',
+    probeApplied: '저장됨: 스크린샷 {{screencap}}, 입력 {{input}} (다시 연결하면 적용)',
   },
 
   // 리소스
@@ -659,6 +670,34 @@ export default {
     taskNotFound: '작업을 찾을 수 없습니다',
     controllerNotFound: '컨트롤러를 찾을 수 없습니다',
     resourceNotFound: '리소스 팩을 찾을 수 없습니다',
+    // 백엔드 오류 코드 (MxuError.code)
+    backend: {
+      libraryNotFound: 'MaaFramework 라이브러리 디렉터리를 찾을 수 없습니다: {{path}}',
+      libraryLoadFailed: 'MaaFramework 로드 실패: {{detail}}',
+      notInitialized: 'MaaFramework가 초기화되지 않았습니다',
+      instanceNotFound: '인스턴스를 찾을 수 없습니다',
+      controllerNotConnected: '컨트롤러가 연결되지 않았습니다',
+      resourceNotLoaded: '리소스가 로드되지 않았습니다',
+      taskerNotCreated: 'Tasker가 생성되지 않았습니다',
+      taskerNotInitialized: 'Tasker 초기화에 실패했습니다. 컨트롤러와 리소스를 확인하세요',
+      invalidParameter: '잘못된 매개변수 {{name}}: {{detail}}',
+      maaCallFailed: 'MaaFramework 호출 실패 ({{operation}}): {{detail}}',
+      agentStartFailed: 'Agent #{{index}} 시작 실패: {{detail}}',
+      downloadCancelled: '다운로드가 취소되었습니다',
+      downloadFailed: '다운로드 실패: {{detail}}',
+      httpStatus: 'HTTP 오류: {{status}}',
+      invalidProxy: '프록시 설정 실패: {{detail}}. 형식을 확인하세요 (http:// 또는 socks5:// 지원)',
+      extractFailed: '압축 해제 실패: {{detail}}',
+      invalidPath: '잘못된 경로: {{path}}',
+      ioFailed: '파일 작업 실패 ({{operation}}): {{path}}',
+      unsupported: '현재 플랫폼에서 지원되지 않는 기능입니다',
+      internal: '내부 오류: {{detail}}',
+      invalidPipelineOverride:
+        '작업 {{entry}}의 Pipeline 재정의 설정이 잘못되었습니다 (오류 {{count}}개)',
+      taskerBusy: '이 인스턴스에서 작업이 실행 중입니다. 먼저 중지하세요',
+      emulatorBootTimeout: '에뮬레이터 {{address}}가 {{timeout_secs}}초 안에 부팅되지 않았습니다',
+      adbConnectFailed: '{{address}}에 연결할 수 없습니다: {{detail}}',
+    },
   },
 
   // 컨텍스트 메뉴
@@ -722,6 +761,12 @@ export default {
     understand: '확인했습니다',
   },
 
+  // 전원 작업 카운트다운
+  powerCountdown: {
+    message: '{{seconds}}초 후 전원 작업(종료 / 재시작 / 화면 끄기 / 절전)을 실행합니다',
+    cancel: '취소',
+  },
+
   // 권한 프롬프트
   permission: {
     title: '관리자 권한이 필요합니다',
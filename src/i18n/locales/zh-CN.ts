@@ -164,6 +164,14 @@ export default {
       screenoff: '息屏',
       sleep: '睡眠',
     },
+    screenshot: {
+      label: '📷 保存截图',
+      optionLabel: '截图设置',
+      filenameLabel: '文件名模板',
+      filenamePlaceholder: '可用变量：{instance} {task} {node} {date} {time} {datetime}',
+      maxFilesLabel: '最多保留截图数',
+      maxFilesError: '请输入非负整数（0 表示不限制）',
+    },
   },
 
   // 任务列表
@@ -318,6 +326,9 @@ export default {
     lastSelected: '上次选择 · 点击搜索',
     savedDeviceNotFound: '未找到上次的设备，请检查连接或重新选择',
     savedWindowNotFound: '未找到上次的窗口，请检查连接或重新选择',
+    probeMethods: '测试控制方式',
+    probeNoUsable: '未找到可用的截图 / 输入方式',
+    probeApplied: '已保存：截图 {{screencap}}，输入 {{input}}（重新连接后生效）',
   },
 
   // 资源
@@ -662,6 +673,33 @@ export default {
     taskNotFound: '任务不存在',
     controllerNotFound: '控制器不存在',
     resourceNotFound: '资源包不存在',
+    // 后端错误码（MxuError.code）
+    backend: {
+      libraryNotFound: 'MaaFramework 库目录不存在：{{path}}',
+      libraryLoadFailed: 'MaaFramework 加载失败：{{detail}}',
+      notInitialized: 'MaaFramework 未初始化',
+      instanceNotFound: '实例不存在',
+      controllerNotConnected: '控制器未连接',
+      resourceNotLoaded: '资源未加载',
+      taskerNotCreated: 'Tasker 未创建',
+      taskerNotInitialized: 'Tasker 初始化失败，请检查控制器和资源',
+      invalidParameter: '参数 {{name}} 无效：{{detail}}',
+      maaCallFailed: 'MaaFramework 调用失败（{{operation}}）：{{detail}}',
+      agentStartFailed: 'Agent #{{index}} 启动失败：{{detail}}',
+      downloadCancelled: '下载已取消',
+      downloadFailed: '下载失败：{{detail}}',
+      httpStatus: 'HTTP 错误：{{status}}',
+      invalidProxy: '代理配置失败：{{detail}}。请检查代理格式是否正确（支持 http:// 或 socks5://）',
+      extractFailed: '解压失败：{{detail}}',
+      invalidPath: '非法路径：{{path}}',
+      ioFailed: '文件操作失败（{{operation}}）：{{path}}',
+      unsupported: '当前平台不支持此功能',
+      internal: '内部错误：{{detail}}',
+      invalidPipelineOverride: '任务 {{entry}} 的 Pipeline 覆盖配置有误（{{count}} 个错误）',
+      taskerBusy: '当前实例正在运行任务，请先停止',
+      emulatorBootTimeout: '模拟器 {{address}} 未在 {{timeout_secs}} 秒内完成启动',
+      adbConnectFailed: '无法连接 {{address}}：{{detail}}',
+    },
   },
 
   // 右键菜单
@@ -725,6 +763,12 @@ export default {
     understand: '我已了解',
   },
 
+  // 电源操作倒计时
+  powerCountdown: {
+    message: '{{seconds}} 秒后将执行电脑操作（关机 / 重启 / 息屏 / 睡眠）',
+    cancel: '取消',
+  },
+
   // 权限提示
   permission: {
     title: '需要管理员权限',
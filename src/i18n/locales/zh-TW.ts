@@ -163,6 +163,14 @@ export default {
       screenoff: '關閉螢幕',
       sleep: '睡眠',
     },
+    screenshot: {
+      label: '📷 儲存截圖',
+      optionLabel: '截圖設定',
+      filenameLabel: '檔名範本',
+      filenamePlaceholder: '可用變數：{instance} {task} {node} {date} {time} {datetime}',
+      maxFilesLabel: '最多保留截圖數',
+      maxFilesError: '請輸入非負整數（0 表示不限制）',
+    },
   },
 
   // 任務列表
@@ -317,6 +325,9 @@ export default {
     lastSelected: '上次選擇 · 點擊搜尋',
     savedDeviceNotFound: '未找到上次的裝置，請檢查連接或重新選擇',
     savedWindowNotFound: '未找到上次的視窗，請檢查連接或重新選擇',
+    probeMethods: '測試控制方式',
+    probeNoUsable: '未找到可用的截圖 / 輸入方式',
+    probeApplied: '已儲存：截圖 {{screencap}}，輸入 {{input}}（重新連接後生效）',
   },
 
   // 資源
@@ -650,6 +661,33 @@ export default {
     taskNotFound: '任務不存在',
     controllerNotFound: '控制器不存在',
     resourceNotFound: '資源包不存在',
+    // 後端錯誤碼（MxuError.code）
+    backend: {
+      libraryNotFound: 'MaaFramework 函式庫目錄不存在：{{path}}',
+      libraryLoadFailed: 'MaaFramework 載入失敗：{{detail}}',
+      notInitialized: 'MaaFramework 未初始化',
+      instanceNotFound: '實例不存在',
+      controllerNotConnected: '控制器未連線',
+      resourceNotLoaded: '資源未載入',
+      taskerNotCreated: 'Tasker 未建立',
+      taskerNotInitialized: 'Tasker 初始化失敗，請檢查控制器和資源',
+      invalidParameter: '參數 {{name}} 無效：{{detail}}',
+      maaCallFailed: 'MaaFramework 呼叫失敗（{{operation}}）：{{detail}}',
+      agentStartFailed: 'Agent #{{index}} 啟動失敗：{{detail}}',
+      downloadCancelled: '下載已取消',
+      downloadFailed: '下載失敗：{{detail}}',
+      httpStatus: 'HTTP 錯誤：{{status}}',
+      invalidProxy: '代理設定失敗：{{detail}}。請檢查代理格式是否正確（支援 http:// 或 socks5://）',
+      extractFailed: '解壓縮失敗：{{detail}}',
+      invalidPath: '非法路徑：{{path}}',
+      ioFailed: '檔案操作失敗（{{operation}}）：{{path}}',
+      unsupported: '目前平台不支援此功能',
+      internal: '內部錯誤：{{detail}}',
+      invalidPipelineOverride: '任務 {{entry}} 的 Pipeline 覆寫設定有誤（{{count}} 個錯誤）',
+      taskerBusy: '目前實例正在執行任務，請先停止',
+      emulatorBootTimeout: '模擬器 {{address}} 未在 {{timeout_secs}} 秒內完成啟動',
+      adbConnectFailed: '無法連線 {{address}}：{{detail}}',
+    },
   },
 
   // 右鍵選單
@@ -713,6 +751,12 @@ export default {
     understand: '我已了解',
   },
 
+  // 電源操作倒數計時
+  powerCountdown: {
+    message: '{{seconds}} 秒後將執行電腦操作（關機 / 重新啟動 / 關閉螢幕 / 睡眠）',
+    cancel: '取消',
+  },
+
   // 權限提示
   permission: {
     title: '需要管理員權限',
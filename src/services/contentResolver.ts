@@ -13,6 +13,7 @@ import { marked } from 'marked';
 import DOMPurify from 'dompurify';
 import { cachedFetch } from './cacheService';
 import { isTauri } from '@/utils/paths';
+import { formatError } from '@/types/maa';
 
 const log = loggers.app;
 
@@ -307,7 +308,7 @@ export async function resolveDescriptionContent(
 
     return { content: loadedContent, type, loaded: true };
   } catch (err) {
-    const errorMsg = err instanceof Error ? err.message : String(err);
+    const errorMsg = formatError(err);
     log.warn(`加载描述内容失败 [${type}: ${resolved}]:`, err);
     // 加载失败时返回原始文本，并附带错误信息
     return { content: resolved, type, loaded: false, error: errorMsg };
@@ -4,14 +4,34 @@
 import { invoke } from '@tauri-apps/api/core';
 import { listen, UnlistenFn } from '@tauri-apps/api/event';
 import type {
+  ActionDebugResult,
   AdbDevice,
+  AdbDevicesChangedEvent,
+  Win32ProcessFilter,
   Win32Window,
   ControllerConfig,
   ConnectionStatus,
+  ControllerProbeReport,
+  EmulatorConfig,
+  EmulatorStatus,
   TaskStatus,
   AgentConfig,
+  StartTasksResult,
   TaskConfig,
   InstanceRuntimeInfo,
+  MxuActionProgressEvent,
+  OrchestrationFinishedEvent,
+  OrchestrationPlan,
+  OrchestrationProgressEvent,
+  PlanFinishedEvent,
+  PlanStepEvent,
+  PluginActionInfo,
+  RecognitionDebugResult,
+  ReplayInputRecord,
+  ResourceBundleEvent,
+  ResourceCustomList,
+  ResourceLoadReport,
+  ResourceReloadEvent,
 } from '@/types/maa';
 import { loggers } from '@/utils/logger';
 import { isTauri } from '@/utils/paths';
@@ -103,21 +123,100 @@ export const maaService = {
     return devices;
   },
 
+  /**
+   * 手动探测 ADB 地址（adb connect 并确认设备在线），结果会写入设备缓存
+   * @param address host:port
+   * @param adbPath adb 路径（可选，缺省时使用已搜索到设备的 adb）
+   */
+  async probeAdbAddress(address: string, adbPath?: string): Promise<AdbDevice> {
+    log.info('探测 ADB 地址:', address);
+    const device = await invoke<AdbDevice>('maa_probe_adb_address', {
+      address,
+      adbPath: adbPath || null,
+    });
+    log.info('探测成功:', device.name, ', identity:', device.identity ?? '(无)');
+    return device;
+  },
+
+  /**
+   * 探测设备可用的截图 / 输入方式并按速度排序（每种方式会创建临时控制器，耗时较长）
+   * @param device 目标设备
+   * @param samples 每种截图方式的截图次数
+   * @param tap 是否实际点击屏幕测试输入方式（默认只验证连接）
+   */
+  async probeAdbController(
+    device: AdbDevice,
+    samples?: number,
+    tap?: boolean,
+  ): Promise<ControllerProbeReport> {
+    log.info('探测控制方式:', device.address);
+    const report = await invoke<ControllerProbeReport>('maa_probe_adb_controller', {
+      adbPath: device.adb_path,
+      address: device.address,
+      config: device.config,
+      samples: samples ?? null,
+      tap: tap ?? null,
+    });
+    log.info(
+      '控制方式探测完成:',
+      `截图 ${report.best_screencap_methods ?? '(无)'}, 输入 ${report.best_input_methods ?? '(无)'}`,
+    );
+    return report;
+  },
+
+  /**
+   * 启动模拟器并等待开机完成（已开机时直接返回）
+   * @param config 模拟器配置
+   */
+  async startEmulator(config: EmulatorConfig): Promise<EmulatorStatus> {
+    log.info('启动模拟器:', config.kind, ', 序号:', config.index ?? 0);
+    const status = await invoke<EmulatorStatus>('maa_start_emulator', { config });
+    log.info('模拟器已就绪:', status.address, ', 耗时:', status.elapsed_ms, 'ms');
+    return status;
+  },
+
+  /**
+   * 关闭模拟器
+   * @param config 模拟器配置
+   */
+  async stopEmulator(config: EmulatorConfig): Promise<void> {
+    log.info('关闭模拟器:', config.kind, ', 序号:', config.index ?? 0);
+    await invoke('maa_stop_emulator', { config });
+  },
+
+  /**
+   * 查询模拟器是否已开机
+   * @param config 模拟器配置
+   */
+  async getEmulatorStatus(config: EmulatorConfig): Promise<EmulatorStatus> {
+    return await invoke<EmulatorStatus>('maa_get_emulator_status', { config });
+  },
+
   /**
    * 查找 Win32 窗口
    * @param classRegex 窗口类名正则表达式（可选）
    * @param windowRegex 窗口标题正则表达式（可选）
+   * @param processFilter 按所属进程名 / 可执行文件路径 / PID 过滤（可选）
    */
-  async findWin32Windows(classRegex?: string, windowRegex?: string): Promise<Win32Window[]> {
+  async findWin32Windows(
+    classRegex?: string,
+    windowRegex?: string,
+    processFilter?: Win32ProcessFilter,
+  ): Promise<Win32Window[]> {
     log.info(
       '搜索 Win32 窗口, classRegex:',
       classRegex || '(无)',
       ', windowRegex:',
       windowRegex || '(无)',
+      ', processFilter:',
+      processFilter ? JSON.stringify(processFilter) : '(无)',
     );
     const windows = await invoke<Win32Window[]>('maa_find_win32_windows', {
       classRegex: classRegex || null,
       windowRegex: windowRegex || null,
+      processName: processFilter?.processName || null,
+      exePath: processFilter?.exePath || null,
+      pid: processFilter?.pid ?? null,
     });
     log.info('找到 Win32 窗口:', windows.length, '个');
     windows.forEach((win, i) => {
@@ -128,6 +227,33 @@ export const maaService = {
     return windows;
   },
 
+  /**
+   * 等待匹配的 Win32 窗口出现（如 MXU_LAUNCH 启动游戏后）
+   * @param classRegex 窗口类名正则表达式（可选）
+   * @param windowRegex 窗口标题正则表达式（可选）
+   * @param processFilter 按所属进程过滤（可选）
+   * @param timeoutMs 超时时间（毫秒，默认 60 秒）
+   * @returns 匹配的窗口，超时时为空数组
+   */
+  async waitForWin32Window(
+    classRegex?: string,
+    windowRegex?: string,
+    processFilter?: Win32ProcessFilter,
+    timeoutMs?: number,
+  ): Promise<Win32Window[]> {
+    log.info('等待 Win32 窗口出现, timeoutMs:', timeoutMs ?? '(默认)');
+    const windows = await invoke<Win32Window[]>('maa_wait_for_win32_window', {
+      classRegex: classRegex || null,
+      windowRegex: windowRegex || null,
+      processName: processFilter?.processName || null,
+      exePath: processFilter?.exePath || null,
+      pid: processFilter?.pid ?? null,
+      timeoutMs: timeoutMs ?? null,
+    });
+    log.info('等待结束, 匹配窗口:', windows.length, '个');
+    return windows;
+  },
+
   /**
    * 创建实例
    * @param instanceId 实例 ID
@@ -190,6 +316,16 @@ export const maaService = {
     return status;
   },
 
+  /**
+   * 获取回放控制器收到的输入记录
+   * @param instanceId 实例 ID
+   * @param clear 读取后是否清空
+   */
+  async getReplayInputs(instanceId: string, clear: boolean = false): Promise<ReplayInputRecord[]> {
+    if (!isTauri()) return [];
+    return await invoke<ReplayInputRecord[]>('maa_get_replay_inputs', { instanceId, clear });
+  },
+
   /**
    * 加载资源（异步，通过回调通知完成状态）
    * @param instanceId 实例 ID
@@ -197,16 +333,66 @@ export const maaService = {
    * @returns 资源加载请求 ID 列表，通过监听 maa-callback 事件获取完成状态
    */
   async loadResource(instanceId: string, paths: string[]): Promise<number[]> {
-    log.info('加载资源, 实例:', instanceId, ', 路径数:', paths.length);
+    const report = await this.loadResourceWithReport(instanceId, paths);
+    return report.bundles.flatMap((b) => (b.job_id === null ? [] : [b.job_id]));
+  },
+
+  /**
+   * 加载资源并返回逐路径报告
+   * @param instanceId 实例 ID
+   * @param paths 资源路径列表
+   * @param wait 是否等待所有资源包加载完成后再返回
+   * @returns 与 paths 一一对应的加载报告（含请求 ID 或失败原因）
+   */
+  async loadResourceWithReport(
+    instanceId: string,
+    paths: string[],
+    wait: boolean = false,
+  ): Promise<ResourceLoadReport> {
+    log.info('加载资源, 实例:', instanceId, ', 路径数:', paths.length, ', wait:', wait);
     paths.forEach((path, i) => {
       log.debug(`  路径[${i}]: ${path}`);
     });
     if (!isTauri()) {
-      return paths.map((_, i) => i + 1);
+      return {
+        bundles: paths.map((path, i) => ({
+          path,
+          job_id: i + 1,
+          error: null,
+          missing_dirs: [],
+          status: wait ? 'Succeeded' : null,
+          elapsed_ms: wait ? 0 : null,
+        })),
+      };
+    }
+    const report = await invoke<ResourceLoadReport>('maa_load_resource', {
+      instanceId,
+      paths,
+      wait,
+    });
+    for (const bundle of report.bundles) {
+      if (bundle.error) {
+        log.warn('资源包提交失败:', bundle.path, bundle.error);
+      } else if (bundle.missing_dirs.length > 0) {
+        log.warn('资源包缺少目录:', bundle.path, bundle.missing_dirs);
+      }
+    }
+    log.info('资源加载请求已发送, resIds:', report.bundles.map((b) => b.job_id));
+    return report;
+  },
+
+  /**
+   * 监听单个资源包加载完成事件
+   * @param callback 回调函数，接收资源包完成事件
+   * @returns 取消监听的函数
+   */
+  async onResourceBundle(callback: (event: ResourceBundleEvent) => void): Promise<UnlistenFn> {
+    if (!isTauri()) {
+      return () => {};
     }
-    const resIds = await invoke<number[]>('maa_load_resource', { instanceId, paths });
-    log.info('资源加载请求已发送, resIds:', resIds);
-    return resIds;
+    return await listen<ResourceBundleEvent>('maa-resource-bundle', (event) => {
+      callback(event.payload);
+    });
   },
 
   /**
@@ -232,6 +418,118 @@ export const maaService = {
     log.info('销毁资源成功:', instanceId);
   },
 
+  /**
+   * 获取资源中所有节点名
+   * @param instanceId 实例 ID
+   */
+  async getNodeList(instanceId: string): Promise<string[]> {
+    if (!isTauri()) return [];
+    return await invoke<string[]>('maa_get_node_list', { instanceId });
+  },
+
+  /**
+   * 获取节点合并后的定义
+   * @param instanceId 实例 ID
+   * @param nodeName 节点名
+   * @returns 节点 JSON，节点不存在时为 null
+   */
+  async getNodeData(
+    instanceId: string,
+    nodeName: string,
+  ): Promise<Record<string, unknown> | null> {
+    if (!isTauri()) return null;
+    return await invoke<Record<string, unknown> | null>('maa_get_node_data', {
+      instanceId,
+      nodeName,
+    });
+  },
+
+  /**
+   * 获取已注册的自定义动作和识别
+   * @param instanceId 实例 ID
+   */
+  async getCustomList(instanceId: string): Promise<ResourceCustomList> {
+    if (!isTauri()) return { actions: [], recognitions: [] };
+    return await invoke<ResourceCustomList>('maa_get_custom_list', { instanceId });
+  },
+
+  /**
+   * 列出 plugins 目录中插件提供的自定义动作
+   */
+  async listPluginActions(): Promise<PluginActionInfo[]> {
+    if (!isTauri()) return [];
+    return await invoke<PluginActionInfo[]>('list_plugin_actions');
+  },
+
+  /**
+   * 获取资源哈希（用于缓存失效判断）
+   * @param instanceId 实例 ID
+   */
+  async getResourceHash(instanceId: string): Promise<string> {
+    if (!isTauri()) return '';
+    return await invoke<string>('maa_get_resource_hash', { instanceId });
+  },
+
+  /**
+   * 开启或关闭资源热重载（资源文件变化且没有任务运行时自动重新加载）
+   * @param instanceId 实例 ID
+   * @param enabled 是否开启
+   * @param intervalMs 文件轮询间隔（毫秒）
+   */
+  async setHotReload(instanceId: string, enabled: boolean, intervalMs?: number): Promise<void> {
+    if (!isTauri()) return;
+    log.info('设置资源热重载, 实例:', instanceId, ', enabled:', enabled);
+    await invoke('maa_set_hot_reload', { instanceId, enabled, intervalMs });
+  },
+
+  /**
+   * 监听资源热重载事件
+   * @param callback 回调函数，接收变化的文件和重载结果
+   * @returns 取消监听的函数
+   */
+  async onResourceReloaded(callback: (event: ResourceReloadEvent) => void): Promise<UnlistenFn> {
+    if (!isTauri()) {
+      return () => {};
+    }
+    return await listen<ResourceReloadEvent>('maa-resource-reloaded', (event) => {
+      callback(event.payload);
+    });
+  },
+
+  /**
+   * 对单个节点执行识别（调试用）
+   * @param instanceId 实例 ID
+   * @param nodeName 节点名
+   * @param imagePath 图片文件路径，为空时使用控制器最近一次截图
+   */
+  async debugRecognition(
+    instanceId: string,
+    nodeName: string,
+    imagePath?: string,
+  ): Promise<RecognitionDebugResult> {
+    log.info('调试识别节点, 实例:', instanceId, ', 节点:', nodeName);
+    return await invoke<RecognitionDebugResult>('maa_debug_recognition', {
+      instanceId,
+      nodeName,
+      imagePath,
+    });
+  },
+
+  /**
+   * 对单个节点执行动作（调试用）
+   * @param instanceId 实例 ID
+   * @param nodeName 节点名
+   * @param targetBox 动作目标框 [x, y, width, height]
+   */
+  async debugAction(
+    instanceId: string,
+    nodeName: string,
+    targetBox?: [number, number, number, number],
+  ): Promise<ActionDebugResult> {
+    log.info('调试动作节点, 实例:', instanceId, ', 节点:', nodeName);
+    return await invoke<ActionDebugResult>('maa_debug_action', { instanceId, nodeName, targetBox });
+  },
+
   /**
    * 运行任务
    * @param instanceId 实例 ID
@@ -359,7 +657,8 @@ export const maaService = {
    * @param agentConfigs Agent 配置列表（可选，支持多个 Agent）
    * @param cwd 工作目录（Agent 子进程的 CWD）
    * @param tcpCompatMode 通信兼容模式（强制使用 TCP）
-   * @returns 任务 ID 列表
+   * @param emulator 模拟器配置（可选，启动前确保模拟器已开机，可在任务结束后关闭）
+   * @returns 已提交的任务 ID；设置了执行计划策略时 plan 为 true，task_id 通过 onPlanStep 通知
    */
   async startTasks(
     instanceId: string,
@@ -367,7 +666,8 @@ export const maaService = {
     agentConfigs?: AgentConfig[],
     cwd?: string,
     tcpCompatMode?: boolean,
-  ): Promise<number[]> {
+    emulator?: EmulatorConfig,
+  ): Promise<StartTasksResult> {
     log.info('启动任务, 实例:', instanceId, ', 任务数:', tasks.length, ', cwd:', cwd || '.');
     tasks.forEach((task, i) => {
       log.debug(`  任务[${i}]: entry=${task.entry}, pipelineOverride=${task.pipeline_override}`);
@@ -383,17 +683,18 @@ export const maaService = {
       );
     }
     if (!isTauri()) {
-      return tasks.map((_, i) => i + 1);
+      return { task_ids: tasks.map((_, i) => i + 1), plan: false };
     }
-    const taskIds = await invoke<number[]>('maa_start_tasks', {
+    const result = await invoke<StartTasksResult>('maa_start_tasks', {
       instanceId,
       tasks,
       agentConfigs: agentConfigs && agentConfigs.length > 0 ? agentConfigs : null,
       cwd: cwd || '.',
       tcpCompatMode: tcpCompatMode || false,
+      emulator: emulator ?? null,
     });
-    log.info('任务已提交, taskIds:', taskIds);
-    return taskIds;
+    log.info('任务已提交, taskIds:', result.task_ids, result.plan ? '(执行计划)' : '');
+    return result;
   },
 
   /**
@@ -441,6 +742,141 @@ export const maaService = {
     });
   },
 
+  /**
+   * 监听 MXU 内置动作（MXU_SLEEP、MXU_WAITUNTIL 等）的倒计时进度
+   * @param callback 回调函数，接收剩余秒数等信息
+   * @returns 取消监听的函数
+   */
+  async onActionProgress(callback: (event: MxuActionProgressEvent) => void): Promise<UnlistenFn> {
+    if (!isTauri()) {
+      return () => {};
+    }
+    return await listen<MxuActionProgressEvent>('mxu-action-progress', (event) => {
+      callback(event.payload);
+    });
+  },
+
+  /**
+   * 监听执行计划中每个任务的状态变化（任务设置了执行计划策略时 startTasks 返回 plan: true）
+   * @returns 取消监听的函数
+   */
+  async onPlanStep(callback: (event: PlanStepEvent) => void): Promise<UnlistenFn> {
+    if (!isTauri()) {
+      return () => {};
+    }
+    return await listen<PlanStepEvent>('maa-plan-step', (event) => {
+      callback(event.payload);
+    });
+  },
+
+  /**
+   * 监听执行计划完成
+   * @returns 取消监听的函数
+   */
+  async onPlanFinished(callback: (event: PlanFinishedEvent) => void): Promise<UnlistenFn> {
+    if (!isTauri()) {
+      return () => {};
+    }
+    return await listen<PlanFinishedEvent>('maa-plan-finished', (event) => {
+      callback(event.payload);
+    });
+  },
+
+  /**
+   * 启动多实例编排（分组按顺序执行，组内实例并行执行）
+   * @param plan 编排计划
+   * @returns 编排 ID，进度与结果通过 onOrchestrationProgress / onOrchestrationFinished 通知
+   */
+  async runOrchestration(plan: OrchestrationPlan): Promise<string> {
+    log.info(
+      '启动多实例编排, 分组数:',
+      plan.groups.length,
+      ', 实例:',
+      plan.groups.map((g) => g.instances.map((e) => e.instance_id).join('+')).join(' -> '),
+    );
+    if (!isTauri()) return '';
+    return await invoke<string>('maa_run_orchestration', { plan });
+  },
+
+  /**
+   * 取消多实例编排
+   * @param runId 编排 ID
+   * @returns 编排是否仍在运行
+   */
+  async cancelOrchestration(runId: string): Promise<boolean> {
+    log.info('取消多实例编排:', runId);
+    if (!isTauri()) return false;
+    return await invoke<boolean>('maa_cancel_orchestration', { runId });
+  },
+
+  /**
+   * 监听编排进度
+   * @returns 取消监听的函数
+   */
+  async onOrchestrationProgress(
+    callback: (event: OrchestrationProgressEvent) => void,
+  ): Promise<UnlistenFn> {
+    if (!isTauri()) {
+      return () => {};
+    }
+    return await listen<OrchestrationProgressEvent>('maa-orchestration-progress', (event) => {
+      callback(event.payload);
+    });
+  },
+
+  /**
+   * 监听编排完成
+   * @returns 取消监听的函数
+   */
+  async onOrchestrationFinished(
+    callback: (event: OrchestrationFinishedEvent) => void,
+  ): Promise<UnlistenFn> {
+    if (!isTauri()) {
+      return () => {};
+    }
+    return await listen<OrchestrationFinishedEvent>('maa-orchestration-finished', (event) => {
+      callback(event.payload);
+    });
+  },
+
+  /**
+   * 监听后台刷新发现的 ADB 设备列表变化（启动时加载持久化缓存后会在后台重新扫描）
+   * @returns 取消监听的函数
+   */
+  async onAdbDevicesChanged(
+    callback: (event: AdbDevicesChangedEvent) => void,
+  ): Promise<UnlistenFn> {
+    if (!isTauri()) {
+      return () => {};
+    }
+    return await listen<AdbDevicesChangedEvent>('maa-adb-devices-changed', (event) => {
+      callback(event.payload);
+    });
+  },
+
+  /**
+   * 取消正在进行的 MXU_POWER 倒计时
+   * @returns 是否存在可取消的倒计时
+   */
+  async cancelPowerCountdown(): Promise<boolean> {
+    if (!isTauri()) return false;
+    log.info('取消电源操作倒计时');
+    return await invoke<boolean>('cancel_power_countdown');
+  },
+
+  /**
+   * 监听从托盘菜单取消 MXU_POWER 倒计时的事件
+   * @returns 取消监听的函数
+   */
+  async onPowerCancelled(callback: () => void): Promise<UnlistenFn> {
+    if (!isTauri()) {
+      return () => {};
+    }
+    return await listen('mxu-power-cancelled', () => {
+      callback();
+    });
+  },
+
   /**
    * 等待单个操作完成的一次性回调（适用于截图等需要立即获取结果的场景）
    * 注意：此函数会阻塞调用者直到回调到达，适合在非 UI 线程或循环中使用
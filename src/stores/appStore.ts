@@ -999,6 +999,16 @@ export const useAppStore = create<AppState>()(
           });
         });
       }
+
+      // 同步下载代理到后端（供后端发起的 HTTP 请求复用）
+      const proxyUrl = config.settings.proxy?.url;
+      if (proxyUrl) {
+        import('@tauri-apps/api/core').then(({ invoke }) => {
+          invoke('set_download_proxy', { proxyUrl }).catch((err) => {
+            loggers.app.error('同步代理设置失败:', err);
+          });
+        });
+      }
     },
 
     // MaaFramework 状态
@@ -1120,6 +1130,22 @@ export const useAppStore = create<AppState>()(
         instances: state.instances.map((i) => (i.id === instanceId ? { ...i, savedDevice } : i)),
       })),
 
+    setInstanceAdbMethods: (instanceId, screencapMethods, inputMethods) =>
+      set((state) => ({
+        instances: state.instances.map((i) =>
+          i.id === instanceId
+            ? {
+                ...i,
+                savedDevice: {
+                  ...i.savedDevice,
+                  adbScreencapMethods: screencapMethods,
+                  adbInputMethods: inputMethods,
+                },
+              }
+            : i,
+        ),
+      })),
+
     setInstancePreAction: (instanceId: string, action: ActionConfig | undefined) =>
       set((state) => ({
         instances: state.instances.map((i) =>
@@ -1229,7 +1255,15 @@ export const useAppStore = create<AppState>()(
 
     // 代理设置
     proxySettings: undefined,
-    setProxySettings: (settings) => set({ proxySettings: settings }),
+    setProxySettings: (settings) => {
+      set({ proxySettings: settings });
+      // 同步到后端
+      import('@tauri-apps/api/core').then(({ invoke }) => {
+        invoke('set_download_proxy', { proxyUrl: settings?.url || null }).catch((err) => {
+          loggers.app.error('同步代理设置失败:', err);
+        });
+      });
+    },
 
     // 任务选项预览显示设置
     showOptionPreview: true,
@@ -226,6 +226,12 @@ export interface AppState {
 
   // 设备信息保存
   setInstanceSavedDevice: (instanceId: string, savedDevice: SavedDeviceInfo) => void;
+  /** 保存控制方式探测得到的截图 / 输入方式（传 undefined 恢复设备默认值） */
+  setInstanceAdbMethods: (
+    instanceId: string,
+    screencapMethods: string | undefined,
+    inputMethods: string | undefined,
+  ) => void;
 
   setInstancePreAction: (instanceId: string, action: ActionConfig | undefined) => void;
 
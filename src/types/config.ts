@@ -25,6 +25,11 @@ export interface SavedTask {
 export interface SavedDeviceInfo {
   // ADB 设备：保存设备名称
   adbDeviceName?: string;
+  // ADB 设备稳定标识（端口变化后仍可识别同一设备）
+  adbDeviceIdentity?: string;
+  // ADB 控制方式探测得到的截图 / 输入方式（u64 字符串），连接同一设备时覆盖默认值
+  adbScreencapMethods?: string;
+  adbInputMethods?: string;
   // Win32/Gamepad：保存窗口名称
   windowName?: string;
   // PlayCover：保存地址
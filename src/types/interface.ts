@@ -190,6 +190,9 @@ export type OptionValue =
 // 保存的设备信息（运行时使用）
 export interface SavedDeviceInfo {
   adbDeviceName?: string;
+  adbDeviceIdentity?: string;
+  adbScreencapMethods?: string;
+  adbInputMethods?: string;
   windowName?: string;
   playcoverAddress?: string;
 }
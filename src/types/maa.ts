@@ -1,5 +1,7 @@
 // MaaFramework 类型定义
 
+import i18n from '@/i18n';
+
 /** ADB 设备信息 */
 export interface AdbDevice {
   name: string;
@@ -8,6 +10,79 @@ export interface AdbDevice {
   screencap_methods: string; // u64 作为字符串传递，避免 JS 精度丢失
   input_methods: string; // u64 作为字符串传递
   config: string;
+  /** 设备稳定标识（ro.serialno / AVD 名称等），端口变化后仍可据此识别同一设备 */
+  identity?: string | null;
+  /** 手动添加的时间（Unix 毫秒），扫描发现的设备为 null */
+  manual_added_at?: number | null;
+}
+
+/** 后台刷新发现 ADB 设备列表变化事件 */
+export interface AdbDevicesChangedEvent {
+  devices: AdbDevice[];
+  /** 更新时间（Unix 毫秒） */
+  updated_at: number;
+}
+
+/** 按保存的设备信息匹配 ADB 设备：优先按稳定标识，其次按设备名称 */
+export function matchSavedAdbDevices(
+  devices: AdbDevice[],
+  saved: { adbDeviceName?: string; adbDeviceIdentity?: string },
+): AdbDevice[] {
+  if (saved.adbDeviceIdentity) {
+    const byIdentity = devices.filter((d) => d.identity === saved.adbDeviceIdentity);
+    if (byIdentity.length > 0) {
+      return byIdentity;
+    }
+  }
+  return devices.filter((d) => d.name === saved.adbDeviceName);
+}
+
+/**
+ * 应用保存的截图 / 输入方式（控制方式探测结果）
+ * 仅当设备与保存的设备匹配时覆盖，否则原样返回
+ */
+export function applySavedAdbMethods(
+  device: AdbDevice,
+  saved?: {
+    adbDeviceName?: string;
+    adbDeviceIdentity?: string;
+    adbScreencapMethods?: string;
+    adbInputMethods?: string;
+  },
+): AdbDevice {
+  if (!saved || matchSavedAdbDevices([device], saved).length === 0) {
+    return device;
+  }
+  return {
+    ...device,
+    screencap_methods: saved.adbScreencapMethods || device.screencap_methods,
+    input_methods: saved.adbInputMethods || device.input_methods,
+  };
+}
+
+/** 单个 ADB 截图 / 输入方式的探测结果 */
+export interface ControllerMethodProbe {
+  method: string; // u64 作为字符串传递
+  name: string;
+  connected: boolean;
+  attempts: number;
+  successes: number;
+  black_frames: number;
+  avg_latency_ms: number | null;
+  min_latency_ms: number | null;
+  /** 全部成功且无黑屏 */
+  usable: boolean;
+  error: string | null;
+}
+
+/** ADB 控制方式探测报告（各列表已按可用性和速度排序） */
+export interface ControllerProbeReport {
+  address: string;
+  screencap: ControllerMethodProbe[];
+  input: ControllerMethodProbe[];
+  best_screencap_methods: string | null;
+  best_input_methods: string | null;
+  elapsed_ms: number;
 }
 
 /** Win32 窗口信息 */
@@ -15,6 +90,21 @@ export interface Win32Window {
   handle: number;
   class_name: string;
   window_name: string;
+  /** 所属进程 ID */
+  pid?: number | null;
+  /** 所属进程名（可执行文件名） */
+  process_name?: string | null;
+  /** 所属进程的可执行文件路径 */
+  exe_path?: string | null;
+}
+
+/** 按所属进程过滤 Win32 窗口（全部满足才匹配） */
+export interface Win32ProcessFilter {
+  /** 进程名（不区分大小写，如 game.exe） */
+  processName?: string;
+  /** 可执行文件完整路径 */
+  exePath?: string;
+  pid?: number;
 }
 
 /** ADB 控制器配置 */
@@ -49,18 +139,192 @@ export interface GamepadControllerConfig {
   handle: number;
 }
 
+/** 离线回放控制器配置（从文件夹读取截图，记录输入） */
+export interface ReplayControllerConfig {
+  type: 'Replay';
+  image_dir: string;
+  /** 回放脚本路径（JSON），为空时按文件名顺序播放 */
+  script?: string;
+  /** 按顺序播放到末尾后是否循环 */
+  loop_images?: boolean;
+}
+
 /** 控制器配置 */
 export type ControllerConfig =
   | AdbControllerConfig
   | Win32ControllerConfig
   | PlayCoverControllerConfig
-  | GamepadControllerConfig;
+  | GamepadControllerConfig
+  | ReplayControllerConfig;
 
 /** 连接状态 */
 export type ConnectionStatus = 'Disconnected' | 'Connecting' | 'Connected' | { Failed: string };
 
 /** 任务状态 */
-export type TaskStatus = 'Pending' | 'Running' | 'Succeeded' | 'Failed';
+export type TaskStatus = 'Pending' | 'Running' | 'Succeeded' | 'Failed' | 'Skipped' | 'TimedOut';
+
+/** 后端命令错误（对应 Rust 端 MxuError） */
+export interface MxuError {
+  /** 稳定错误码，如 'InstanceNotFound' */
+  code: string;
+  /** i18n key，如 'errors.backend.instanceNotFound' */
+  message_key: string;
+  /** 英文错误描述（用于日志） */
+  message: string;
+  /** 结构化上下文字段（作为 i18n 插值参数） */
+  context: Record<string, unknown>;
+}
+
+/** 单个资源包的加载结果 */
+export interface BundleLoadEntry {
+  path: string;
+  /** 提交成功时的加载请求 ID */
+  job_id: number | null;
+  /** 提交失败原因 */
+  error: string | null;
+  /** 缺失的常规子目录（pipeline / image） */
+  missing_dirs: string[];
+  /** 加载完成状态（仅 wait 时填充） */
+  status: TaskStatus | null;
+  /** 加载耗时毫秒（仅 wait 时填充） */
+  elapsed_ms: number | null;
+}
+
+/** 资源加载报告 */
+export interface ResourceLoadReport {
+  bundles: BundleLoadEntry[];
+}
+
+/** 单个资源包加载完成事件（maa-resource-bundle） */
+export interface ResourceBundleEvent {
+  instance_id: string;
+  path: string;
+  job_id: number;
+  status: TaskStatus;
+  elapsed_ms: number;
+}
+
+/** 资源文件错误（热重载时的 JSON 解析错误等） */
+export interface ResourceFileError {
+  file: string;
+  line: number | null;
+  column: number | null;
+  message: string;
+}
+
+/** 资源热重载事件（maa-resource-reloaded） */
+export interface ResourceReloadEvent {
+  instance_id: string;
+  added: string[];
+  modified: string[];
+  removed: string[];
+  /** 是否已替换为新资源 */
+  reloaded: boolean;
+  errors: ResourceFileError[];
+  elapsed_ms: number;
+}
+
+/** 单节点识别调试结果 */
+export interface RecognitionDebugResult {
+  node: string;
+  algorithm: string;
+  hit: boolean;
+  /** 命中框 [x, y, width, height] */
+  box: [number, number, number, number] | null;
+  score: number | null;
+  detail: unknown;
+  /** 调试绘制图（base64 data URL） */
+  draws: string[];
+  elapsed_ms: number;
+}
+
+/** 单节点动作调试结果 */
+export interface ActionDebugResult {
+  node: string;
+  action: string;
+  success: boolean;
+  box: [number, number, number, number];
+  detail: unknown;
+  elapsed_ms: number;
+}
+
+/** 回放控制器收到的输入 */
+export interface ReplayInputRecord {
+  seq: number;
+  /** 输入类型（click、swipe、input_text 等） */
+  kind: string;
+  params: Record<string, unknown>;
+  /** 收到输入时的画面文件名 */
+  frame: string | null;
+  elapsed_ms: number;
+}
+
+/** MXU 长时间运行动作的倒计时进度事件（mxu-action-progress） */
+export interface MxuActionProgressEvent {
+  /** 动作名，如 'MXU_SLEEP' */
+  action: string;
+  task_id: number;
+  node: string;
+  total_secs: number;
+  remaining_secs: number;
+}
+
+/** 资源中已注册的自定义动作和识别 */
+export interface ResourceCustomList {
+  actions: string[];
+  recognitions: string[];
+}
+
+/** plugins 目录中插件提供的自定义动作 */
+export interface PluginActionInfo {
+  plugin: string;
+  version: string | null;
+  kind: 'library' | 'command';
+  name: string;
+  description: string | null;
+  /** 参数 JSON Schema */
+  param_schema: Record<string, unknown> | null;
+}
+
+/** Pipeline override 诊断信息 */
+export interface PipelineDiagnostic {
+  severity: 'Error' | 'Warning';
+  node: string | null;
+  field: string | null;
+  message: string;
+  line: number | null;
+  column: number | null;
+}
+
+/** Pipeline override 校验结果 */
+export interface PipelineValidationReport {
+  valid: boolean;
+  diagnostics: PipelineDiagnostic[];
+}
+
+/** 判断 invoke 抛出的错误是否为 MxuError */
+export function isMxuError(err: unknown): err is MxuError {
+  return (
+    typeof err === 'object' &&
+    err !== null &&
+    typeof (err as MxuError).code === 'string' &&
+    typeof (err as MxuError).message_key === 'string'
+  );
+}
+
+/** 将错误转为可显示文本：MxuError 按 message_key 翻译，其余取 message 或字符串形式 */
+export function formatError(err: unknown): string {
+  if (isMxuError(err)) {
+    return i18n.t(err.message_key, err.context);
+  }
+  if (err instanceof Error) {
+    return err.message;
+  }
+  if (typeof err === 'object' && err !== null && typeof (err as Error).message === 'string') {
+    return (err as Error).message;
+  }
+  return String(err);
+}
 
 /** MaaFramework 初始化状态 */
 export interface MaaInitState {
@@ -160,4 +424,134 @@ export interface AgentConfig {
 export interface TaskConfig {
   entry: string;
   pipeline_override: string;
+  /** 以下为执行计划策略，全部缺省时任务按顺序一次性提交 */
+  /** 失败后继续（默认）或停止计划 */
+  on_failure?: 'continue' | 'stop';
+  /** 失败后重试次数 */
+  retry?: number;
+  /** 重试间隔（毫秒） */
+  retry_delay_ms?: number;
+  /** 前一个任务成功时跳过本任务 */
+  skip_if_previous_succeeded?: boolean;
+  /** 重复执行：until_success 为 true 时成功即停止，否则失败即停止 */
+  repeat?: { times: number; until_success?: boolean };
+  /** 收尾任务：计划中止时仍然执行 */
+  always_run?: boolean;
+  /** 超时时间（秒），超时后停止任务并标记为 TimedOut */
+  timeout_secs?: number;
+}
+
+/** startTasks 的返回值 */
+export interface StartTasksResult {
+  /** 已提交的 task_id（按任务顺序）；plan 为 true 时为空 */
+  task_ids: number[];
+  /** 是否由执行计划逐个提交，此时 task_id 通过 onPlanStep 通知 */
+  plan: boolean;
+}
+
+/** 执行计划单步状态事件 */
+export interface PlanStepEvent {
+  instance_id: string;
+  index: number;
+  entry: string;
+  task_id: number | null;
+  iteration: number;
+  attempt: number;
+  status: TaskStatus;
+}
+
+/** 执行计划中单个任务的最终结果 */
+export interface PlanStepResult {
+  index: number;
+  entry: string;
+  status: TaskStatus;
+  task_ids: number[];
+  elapsed_ms: number;
+}
+
+/** 执行计划完成事件 */
+export interface PlanFinishedEvent {
+  instance_id: string;
+  success: boolean;
+  results: PlanStepResult[];
+}
+
+/** 模拟器类型 */
+export type EmulatorKind = 'mumu' | 'ldplayer' | 'bluestacks' | 'avd';
+
+/** 模拟器配置 */
+export interface EmulatorConfig {
+  kind: EmulatorKind;
+  /** 安装目录（AVD 为 Android SDK 目录，缺省时使用 ANDROID_HOME / ANDROID_SDK_ROOT） */
+  install_dir?: string;
+  /** 多开实例序号 */
+  index?: number;
+  /** BlueStacks 实例名 / AVD 名称 */
+  instance_name?: string;
+  /** adb 路径，缺省时使用模拟器自带的 adb */
+  adb_path?: string;
+  /** adb 地址，缺省时按实例序号推算 */
+  address?: string;
+  /** 等待开机的超时时间（秒），默认 120 */
+  boot_timeout_secs?: number;
+  /** 作为 startTasks 后置步骤时，任务结束后关闭模拟器 */
+  close_after_tasks?: boolean;
+}
+
+/** 模拟器状态 */
+export interface EmulatorStatus {
+  address: string;
+  adb_path: string;
+  booted: boolean;
+  /** 本次调用启动了模拟器 */
+  launched: boolean;
+  elapsed_ms: number;
+}
+
+/** 多实例编排中单个实例的任务列表（参数与 startTasks 一致） */
+export interface OrchestrationEntry {
+  instance_id: string;
+  tasks: TaskConfig[];
+  agent_configs?: AgentConfig[] | null;
+  cwd?: string;
+  tcp_compat_mode?: boolean;
+  /** 接管指定实例的控制器（多个账号共用同一模拟器时使用） */
+  controller_from?: string;
+}
+
+/** 多实例编排计划：分组按顺序执行，组内实例并行执行 */
+export interface OrchestrationPlan {
+  groups: { instances: OrchestrationEntry[] }[];
+  /** 有实例失败时跳过后续分组 */
+  stop_on_failure?: boolean;
+}
+
+/** 编排进度事件 */
+export interface OrchestrationProgressEvent {
+  run_id: string;
+  group_index: number;
+  instance_id: string;
+  status: TaskStatus;
+  completed: number;
+  total: number;
+  error: string | null;
+}
+
+/** 编排中单个实例的最终结果 */
+export interface OrchestrationInstanceResult {
+  group_index: number;
+  instance_id: string;
+  status: TaskStatus;
+  error: string | null;
+  results: PlanStepResult[];
+  elapsed_ms: number;
+}
+
+/** 编排完成事件 */
+export interface OrchestrationFinishedEvent {
+  run_id: string;
+  success: boolean;
+  cancelled: boolean;
+  results: OrchestrationInstanceResult[];
+  elapsed_ms: number;
 }
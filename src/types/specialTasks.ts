@@ -33,7 +33,8 @@ export interface MxuSpecialTaskDefinition {
     | 'Play'
     | 'MessageSquare'
     | 'XCircle'
-    | 'Power';
+    | 'Power'
+    | 'Camera';
   /** 图标颜色 CSS 类 */
   iconColorClass: string;
 }
@@ -73,6 +74,11 @@ export const MXU_POWER_TASK_NAME = '__MXU_POWER__';
 export const MXU_POWER_ENTRY = 'MXU_POWER';
 export const MXU_POWER_ACTION = 'MXU_POWER_ACTION';
 
+// MXU_SCREENSHOT 特殊任务常量
+export const MXU_SCREENSHOT_TASK_NAME = '__MXU_SCREENSHOT__';
+export const MXU_SCREENSHOT_ENTRY = 'MXU_SCREENSHOT';
+export const MXU_SCREENSHOT_ACTION = 'MXU_SCREENSHOT_ACTION';
+
 // MXU_SLEEP 任务定义
 const MXU_SLEEP_TASK_DEF_INTERNAL: TaskItem = {
   name: MXU_SLEEP_TASK_NAME,
@@ -452,6 +458,51 @@ const MXU_POWER_OPTION_DEF_INTERNAL: SelectOption = {
  *
  * 排序（不含"前置任务"，前置任务在 AddTaskPanel 中独立渲染）：
  */
+// MXU_SCREENSHOT 任务定义
+const MXU_SCREENSHOT_TASK_DEF_INTERNAL: TaskItem = {
+  name: MXU_SCREENSHOT_TASK_NAME,
+  label: 'specialTask.screenshot.label',
+  entry: MXU_SCREENSHOT_ENTRY,
+  option: ['__MXU_SCREENSHOT_OPTION__'],
+  pipeline_override: {
+    [MXU_SCREENSHOT_ENTRY]: {
+      action: 'Custom',
+      custom_action: MXU_SCREENSHOT_ACTION,
+    },
+  },
+};
+
+// MXU_SCREENSHOT 输入选项定义（文件名模板和保留数量）
+const MXU_SCREENSHOT_OPTION_DEF_INTERNAL: InputOption = {
+  type: 'input',
+  label: 'specialTask.screenshot.optionLabel',
+  inputs: [
+    {
+      name: 'filename',
+      label: 'specialTask.screenshot.filenameLabel',
+      default: '{date}/{task}_{time}.png',
+      pipeline_type: 'string',
+      placeholder: 'specialTask.screenshot.filenamePlaceholder',
+    },
+    {
+      name: 'max_files',
+      label: 'specialTask.screenshot.maxFilesLabel',
+      default: '500',
+      pipeline_type: 'int',
+      verify: '^\\d+$',
+      pattern_msg: 'specialTask.screenshot.maxFilesError',
+    },
+  ],
+  pipeline_override: {
+    [MXU_SCREENSHOT_ENTRY]: {
+      custom_action_param: {
+        filename: '{filename}',
+        max_files: '{max_files}',
+      },
+    },
+  },
+};
+
 export const MXU_SPECIAL_TASKS: Record<string, MxuSpecialTaskDefinition> = {
   [MXU_SLEEP_TASK_NAME]: {
     taskName: MXU_SLEEP_TASK_NAME,
@@ -525,6 +576,16 @@ export const MXU_SPECIAL_TASKS: Record<string, MxuSpecialTaskDefinition> = {
     iconName: 'Bell',
     iconColorClass: 'text-accent/80',
   },
+  [MXU_SCREENSHOT_TASK_NAME]: {
+    taskName: MXU_SCREENSHOT_TASK_NAME,
+    entry: MXU_SCREENSHOT_ENTRY,
+    taskDef: MXU_SCREENSHOT_TASK_DEF_INTERNAL,
+    optionDefs: {
+      __MXU_SCREENSHOT_OPTION__: MXU_SCREENSHOT_OPTION_DEF_INTERNAL,
+    },
+    iconName: 'Camera',
+    iconColorClass: 'text-info/80',
+  },
 };
 
 // 导出兼容旧代码的常量（指向注册表中的定义）
@@ -1,6 +1,7 @@
 import { useState, useCallback } from 'react';
 import { isTauri } from '@/utils/paths';
 import { loggers } from '@/utils/logger';
+import { formatError } from '@/types/maa';
 import { useAppStore } from '@/stores/appStore';
 
 export type ExportStatus = 'idle' | 'exporting' | 'success' | 'error';
@@ -44,7 +45,7 @@ export function useExportLogs() {
       setExportModal({
         show: true,
         status: 'error',
-        error: err instanceof Error ? err.message : String(err),
+        error: formatError(err),
       });
     }
   }, [projectInterface?.name, projectInterface?.version]);